@@ -0,0 +1,358 @@
+//! Gas usage of autocompounder entry points.
+//! Runs compiled contract in cosmwasm VM with mocked nAsset, PSI, anAsset, rewards and pair,
+//! and compares gas of every step with 'gas_baseline.json'.
+//!
+//! cargo build --release --target wasm32-unknown-unknown --lib
+//! cargo bench --features testing --bench gas [-- --bless]
+//!
+//! Both commands use toolchain from 'rust-toolchain.toml' and versions from 'Cargo.lock':
+//! newer compilers emit sign-ext and bulk-memory wasm instructions, which cosmwasm VM 0.16
+//! rejects. Bench fails when baseline is missing or lacks some entry point, '--bless'
+//! rewrites it with current numbers.
+//! Wasm path can be overridden with 'NASSET_AUTOCOMPOUNDER_WASM', allowed increase
+//! with 'GAS_TOLERANCE_PERCENT' (1 by default).
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::{
+    to_binary, Binary, ContractResult, Empty, Querier, Reply, Response, SubMsgExecutionResponse,
+    SystemResult, Uint128,
+};
+use cosmwasm_vm::testing::{
+    execute, instantiate, mock_env, mock_info, reply, MockApi, MockStorage,
+};
+use cosmwasm_vm::{
+    Backend, BackendResult, GasInfo, Instance, InstanceOptions, Querier as VmQuerier,
+};
+use cw20::Cw20ReceiveMsg;
+use nasset_autocompounder::msg::{Cw20HookMsg, ExecuteMsg};
+use nasset_autocompounder::testing::{
+    Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR, PSI_TOKEN_ADDR,
+};
+use nasset_autocompounder::SubmsgIds;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+type GasReport = BTreeMap<String, u64>;
+
+const GAS_LIMIT: u64 = 500_000_000_000;
+const DEFAULT_TOLERANCE_PERCENT: u64 = 1;
+const USER_ADDR: &str = "addr0010";
+const KEEPER_ADDR: &str = "addr9999";
+// vault state before every scenario: 1:1 share price
+const INITIAL_NASSET_BALANCE: u128 = 1_000_000_000;
+const DEPOSIT_AMOUNT: u128 = 10_000_000;
+const PSI_CLAIMED: u128 = 256_000_000;
+const NASSET_BOUGHT: u128 = 2_000_000;
+
+/// Passes 'raw_query' to 'WasmMockQuerier' from 'testing' module, free of charge.
+struct MockQuerier {
+    querier: nasset_autocompounder::testing::WasmMockQuerier,
+    nasset_balance: Uint128,
+    psi_balance: Uint128,
+    auto_nasset_supply: Uint128,
+    user_auto_nasset_balance: Uint128,
+}
+
+impl VmQuerier for MockQuerier {
+    fn query_raw(
+        &self,
+        request: &[u8],
+        _gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        (Ok(self.querier.raw_query(request)), GasInfo::free())
+    }
+}
+
+impl MockQuerier {
+    fn new() -> Self {
+        let mut querier = MockQuerier {
+            querier: nasset_autocompounder::testing::mock_dependencies(&[]).querier,
+            nasset_balance: Uint128::from(INITIAL_NASSET_BALANCE),
+            psi_balance: Uint128::zero(),
+            auto_nasset_supply: Uint128::from(INITIAL_NASSET_BALANCE),
+            user_auto_nasset_balance: Uint128::from(DEPOSIT_AMOUNT),
+        };
+        querier.apply();
+        querier
+    }
+
+    fn apply(&mut self) {
+        let contract_addr = MOCK_CONTRACT_ADDR.to_string();
+        let user_addr = USER_ADDR.to_string();
+        let nasset_token = NASSET_TOKEN_ADDR.to_string();
+        let psi_token = PSI_TOKEN_ADDR.to_string();
+        let auto_nasset_token = AUTO_NASSET_TOKEN_ADDR.to_string();
+        self.querier.with_token_balances(&[
+            (&nasset_token, &[(&contract_addr, &self.nasset_balance)]),
+            (&psi_token, &[(&contract_addr, &self.psi_balance)]),
+            (
+                &auto_nasset_token,
+                &[(&user_addr, &self.user_auto_nasset_balance)],
+            ),
+        ]);
+        self.querier.with_token_supplies(
+            vec![(auto_nasset_token, self.auto_nasset_supply)]
+                .into_iter()
+                .collect(),
+        );
+        self.querier
+            .with_accrued_rewards(Uint128::from(PSI_CLAIMED));
+    }
+}
+
+struct Bench {
+    instance: Instance<MockApi, MockStorage, MockQuerier>,
+    report: GasReport,
+}
+
+impl Bench {
+    // instantiated autocompounder with external anAsset token
+    fn new(wasm: &[u8]) -> Self {
+        let backend = Backend {
+            api: MockApi::default(),
+            storage: MockStorage::default(),
+            querier: MockQuerier::new(),
+        };
+        let options = InstanceOptions {
+            gas_limit: GAS_LIMIT,
+            print_debug: false,
+        };
+        let instance = Instance::from_code(wasm, backend, options, None).unwrap();
+        let mut bench = Bench {
+            instance,
+            report: GasReport::new(),
+        };
+
+        bench.measure("instantiate", |instance| {
+            instantiate(
+                instance,
+                mock_env(),
+                mock_info(KEEPER_ADDR, &[]),
+                Sdk::instantiate_msg(),
+            )
+        });
+        bench.measure("instantiate_reply_init_token", |instance| {
+            reply(
+                instance,
+                mock_env(),
+                Sdk::auto_nasset_token_instantiated_reply(AUTO_NASSET_TOKEN_ADDR),
+            )
+        });
+        bench
+    }
+
+    fn measure<F>(&mut self, name: &str, call: F) -> u64
+    where
+        F: FnOnce(
+            &mut Instance<MockApi, MockStorage, MockQuerier>,
+        ) -> ContractResult<Response<Empty>>,
+    {
+        let gas_before = self.instance.get_gas_left();
+        let result = call(&mut self.instance);
+        if let ContractResult::Err(error) = result {
+            panic!("'{}' failed: {}", name, error);
+        }
+        let gas_used = gas_before - self.instance.get_gas_left();
+        self.report.insert(name.to_string(), gas_used);
+        gas_used
+    }
+
+    fn update_querier<F: FnOnce(&mut MockQuerier)>(&mut self, update: F) {
+        self.instance
+            .with_querier(|querier| {
+                update(querier);
+                querier.apply();
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    // PsiClaimed and PsiSold replies, returns gas of both
+    fn claim_and_sell_psi(&mut self, prefix: &str) -> u64 {
+        self.update_querier(|querier| querier.psi_balance = Uint128::from(PSI_CLAIMED));
+        let claimed_gas = self.measure(&format!("{}_reply_psi_claimed", prefix), |instance| {
+            reply(instance, mock_env(), reply_ok(SubmsgIds::PsiClaimed))
+        });
+
+        self.update_querier(|querier| {
+            querier.psi_balance = Uint128::zero();
+            querier.nasset_balance += Uint128::from(NASSET_BOUGHT);
+        });
+        let sold_gas = self.measure(&format!("{}_reply_psi_sold", prefix), |instance| {
+            reply(instance, mock_env(), reply_ok(SubmsgIds::PsiSold))
+        });
+        claimed_gas + sold_gas
+    }
+
+    fn deposit(&mut self) {
+        // nAsset is transferred before 'Receive' is called
+        self.update_querier(|querier| querier.nasset_balance += Uint128::from(DEPOSIT_AMOUNT));
+        self.measure("deposit", |instance| {
+            execute(
+                instance,
+                mock_env(),
+                mock_info(NASSET_TOKEN_ADDR, &[]),
+                ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: USER_ADDR.to_string(),
+                    amount: Uint128::from(DEPOSIT_AMOUNT),
+                    msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
+                }),
+            )
+        });
+    }
+
+    fn withdraw(&mut self) {
+        let execute_gas = self.measure("withdraw", |instance| {
+            execute(
+                instance,
+                mock_env(),
+                mock_info(AUTO_NASSET_TOKEN_ADDR, &[]),
+                ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: USER_ADDR.to_string(),
+                    amount: Uint128::from(DEPOSIT_AMOUNT),
+                    msg: to_binary(&Cw20HookMsg::Withdraw { recipient: None }).unwrap(),
+                }),
+            )
+        });
+        let replies_gas = self.claim_and_sell_psi("withdraw");
+        self.report
+            .insert("withdraw_total".to_string(), execute_gas + replies_gas);
+    }
+
+    fn compound(&mut self) {
+        let execute_gas = self.measure("compound", |instance| {
+            execute(
+                instance,
+                mock_env(),
+                mock_info(KEEPER_ADDR, &[]),
+                ExecuteMsg::Compound {
+                    nasset_token_addr: None,
+                },
+            )
+        });
+        let replies_gas = self.claim_and_sell_psi("compound");
+        self.report
+            .insert("compound_total".to_string(), execute_gas + replies_gas);
+    }
+}
+
+fn reply_ok(submsg_id: SubmsgIds) -> Reply {
+    Reply {
+        id: submsg_id.id(),
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    }
+}
+
+fn wasm_path() -> PathBuf {
+    match std::env::var("NASSET_AUTOCOMPOUNDER_WASM") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../target/wasm32-unknown-unknown/release/nasset_autocompounder.wasm"),
+    }
+}
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("benches/gas_baseline.json")
+}
+
+fn tolerance_percent() -> u64 {
+    std::env::var("GAS_TOLERANCE_PERCENT")
+        .ok()
+        .map(|value| {
+            value
+                .parse()
+                .expect("GAS_TOLERANCE_PERCENT is not a number")
+        })
+        .unwrap_or(DEFAULT_TOLERANCE_PERCENT)
+}
+
+fn main() {
+    let bless = std::env::args().any(|arg| arg == "--bless");
+    let wasm_path = wasm_path();
+    let wasm = fs::read(&wasm_path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read {}: {}, run 'cargo build --release --target wasm32-unknown-unknown --lib' first",
+            wasm_path.display(),
+            e
+        )
+    });
+
+    // every scenario starts from freshly instantiated contract,
+    // so numbers do not depend on order of scenarios
+    let mut report = GasReport::new();
+    let mut bench = Bench::new(&wasm);
+    bench.deposit();
+    report.append(&mut bench.report);
+
+    let mut bench = Bench::new(&wasm);
+    bench.withdraw();
+    report.append(&mut bench.report);
+
+    let mut bench = Bench::new(&wasm);
+    bench.compound();
+    report.append(&mut bench.report);
+
+    let baseline: GasReport = match fs::read(baseline_path()) {
+        Ok(content) => serde_json::from_slice(&content).expect("invalid gas baseline"),
+        Err(e) if bless => {
+            println!("no baseline at {}: {}", baseline_path().display(), e);
+            GasReport::new()
+        }
+        Err(e) => {
+            eprintln!("cannot read {}: {}", baseline_path().display(), e);
+            eprintln!("run with '-- --bless' to create it");
+            std::process::exit(1);
+        }
+    };
+
+    let tolerance = tolerance_percent();
+    let mut regressions = vec![];
+    let mut missing = vec![];
+    println!(
+        "{:<36} {:>16} {:>16} {:>9}",
+        "entry point", "gas", "baseline", "diff"
+    );
+    for (name, gas) in report.iter() {
+        match baseline.get(name) {
+            Some(baseline_gas) => {
+                let diff = (*gas as f64 - *baseline_gas as f64) / *baseline_gas as f64 * 100.0;
+                println!(
+                    "{:<36} {:>16} {:>16} {:>+8.2}%",
+                    name, gas, baseline_gas, diff
+                );
+                if *gas > baseline_gas + baseline_gas * tolerance / 100 {
+                    regressions.push(name.clone());
+                }
+            }
+            None => {
+                println!("{:<36} {:>16} {:>16} {:>9}", name, gas, "-", "new");
+                missing.push(name.clone());
+            }
+        }
+    }
+
+    if bless {
+        let content = serde_json::to_string_pretty(&report).unwrap();
+        fs::write(baseline_path(), content + "\n").unwrap();
+        println!("baseline updated: {}", baseline_path().display());
+        return;
+    }
+
+    if !missing.is_empty() {
+        eprintln!("no baseline for: {}", missing.join(", "));
+    }
+    if !regressions.is_empty() {
+        eprintln!(
+            "gas usage increased by more than {}% for: {}",
+            tolerance,
+            regressions.join(", ")
+        );
+    }
+    if !missing.is_empty() || !regressions.is_empty() {
+        eprintln!("run with '-- --bless' to accept new numbers");
+        std::process::exit(1);
+    }
+}
@@ -3,8 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use nasset_autocompounder::msg::{
-    AutoNassetValueResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, GovernanceMsg,
-    InstantiateMsg, QueryMsg,
+    AutoNassetValueResponse, ConfigResponse, Cw20HookMsg, DepositCapacityResponse, ExecuteMsg,
+    GovernanceMsg, InstantiateMsg, PendingConfigUpdateResponse, PendingRewardsResponse, QueryMsg,
+    SimulateCompoundResponse, SimulateWithdrawResponse, StatsResponse, TokenQueryModeResponse,
+    UnbondingsResponse, VaultAssetsResponse, VaultResponse, VaultSharesResponse, VaultsResponse,
 };
 
 fn main() {
@@ -19,4 +21,16 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(AutoNassetValueResponse), &out_dir);
+    export_schema(&schema_for!(PendingConfigUpdateResponse), &out_dir);
+    export_schema(&schema_for!(DepositCapacityResponse), &out_dir);
+    export_schema(&schema_for!(UnbondingsResponse), &out_dir);
+    export_schema(&schema_for!(VaultSharesResponse), &out_dir);
+    export_schema(&schema_for!(VaultAssetsResponse), &out_dir);
+    export_schema(&schema_for!(VaultResponse), &out_dir);
+    export_schema(&schema_for!(VaultsResponse), &out_dir);
+    export_schema(&schema_for!(PendingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(SimulateCompoundResponse), &out_dir);
+    export_schema(&schema_for!(StatsResponse), &out_dir);
+    export_schema(&schema_for!(TokenQueryModeResponse), &out_dir);
+    export_schema(&schema_for!(SimulateWithdrawResponse), &out_dir);
 }
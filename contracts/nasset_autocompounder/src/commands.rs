@@ -1,86 +1,383 @@
 use crate::{
     commands, concat,
-    msg::{Cw20HookMsg, NAssetTokenRewardsAnyoneMsg, NAssetTokenRewardsExecuteMsg},
+    msg::{
+        AstroportAsset, AstroportAssetInfo, AstroportQueryMsg, AstroportSimulationResponse,
+        Cw20HookMsg, Cw20StorageLayout, DepositCaps, NAssetTokenRewardsAccruedRewardsResponse,
+        NAssetTokenRewardsAnyoneMsg, NAssetTokenRewardsExecuteMsg, NAssetTokenRewardsQueryMsg,
+        PendingRewardsResponse, TokenQueryMode, WithdrawalFeeConfig, WithdrawalMode,
+    },
+    share_token,
     state::{
-        load_config, load_gov_update, load_withdraw_action, remove_gov_update,
-        remove_withdraw_action, store_config, store_gov_update, store_withdraw_action, Config,
-        GovernanceUpdateState, WithdrawAction,
+        load_config, load_token_query_mode, load_unbondings, load_vault, load_vaults,
+        load_withdraw_action, may_load_compound_state, may_load_config_update, may_load_user_state,
+        may_load_vault, may_load_vault_by_auto_nasset, remove_compound_state, remove_config_update,
+        remove_withdraw_action, store_compound_state, store_config, store_config_update,
+        store_pending_vault, store_token_query_mode, store_unbondings, store_user_state,
+        store_withdraw_action, update_stats, CompoundState, Config, ConfigUpdate, NewVault,
+        RouterUpdate, TokenQueryModeUpdate, Unbonding, UserState, Vault, WithdrawAction,
+        WithdrawalFee,
     },
     SubmsgIds,
 };
-use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    QuerierWrapper, QueryRequest, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
-    WasmQuery,
+    from_binary, from_slice, to_binary, Addr, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QuerierWrapper, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cosmwasm_storage::to_length_prefixed;
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
 use cw20_base::state::TokenInfo;
+use nasset_autocompounder_governance as governance;
+use nasset_autocompounder_interface::{
+    events::{CompoundEvent, DepositEvent, WithdrawEvent},
+    share_math::{self, Rounding},
+};
 
+pub use nasset_autocompounder_governance::update_governance_addr;
+
+#[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut,
-    mut current_config: Config,
+    env: Env,
+    current_config: Config,
     psi_token_addr: Option<String>,
     psi_to_nasset_pair_addr: Option<String>,
     nasset_token_rewards_addr: Option<String>,
+    config_update_delay: Option<u64>,
+    withdrawal_fee: Option<WithdrawalFeeConfig>,
+    withdrawal_mode: Option<WithdrawalMode>,
+) -> StdResult<Response> {
+    if psi_token_addr.is_none()
+        && psi_to_nasset_pair_addr.is_none()
+        && nasset_token_rewards_addr.is_none()
+        && config_update_delay.is_none()
+        && withdrawal_fee.is_none()
+        && withdrawal_mode.is_none()
+    {
+        return Err(StdError::generic_err("config update is empty"));
+    }
+
+    let config_update = ConfigUpdate {
+        psi_token: psi_token_addr
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        psi_to_nasset_pair: psi_to_nasset_pair_addr
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        nasset_token_rewards: nasset_token_rewards_addr
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        config_update_delay,
+        withdrawal_fee: withdrawal_fee
+            .map(|withdrawal_fee| validate_withdrawal_fee(deps.as_ref(), withdrawal_fee))
+            .transpose()?,
+        withdrawal_mode,
+        ..ConfigUpdate::default()
+    };
+    if config_update.enables_primary_only_features() && has_additional_vaults(deps.as_ref())? {
+        return Err(StdError::generic_err(
+            "withdrawal fee and unbonding can't be enabled with additional vaults",
+        ));
+    }
+    queue_config_update(deps, env, current_config, config_update)
+}
+
+fn has_additional_vaults(deps: Deps) -> StdResult<bool> {
+    Ok(!load_vaults(deps.storage, None, Some(1))?.is_empty())
+}
+
+fn queue_config_update(
+    deps: DepsMut,
+    env: Env,
+    current_config: Config,
+    mut config_update: ConfigUpdate,
 ) -> StdResult<Response> {
-    if let Some(ref psi_token_addr) = psi_token_addr {
-        current_config.psi_token = deps.api.addr_validate(psi_token_addr)?;
+    if may_load_config_update(deps.storage)?.is_some() {
+        return Err(StdError::generic_err(
+            "config update is already pending, cancel it first",
+        ));
+    }
+
+    config_update.execute_after = get_time(&env.block) + current_config.config_update_delay;
+    store_config_update(deps.storage, &config_update)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "queue_config_update"),
+        ("execute_after", &config_update.execute_after.to_string()),
+    ]))
+}
+
+pub fn execute_config_update(
+    deps: DepsMut,
+    env: Env,
+    mut current_config: Config,
+) -> StdResult<Response> {
+    let config_update = may_load_config_update(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("no pending config update"))?;
+
+    if get_time(&env.block) < config_update.execute_after {
+        return Err(StdError::generic_err(format!(
+            "config update is timelocked until {}",
+            config_update.execute_after
+        )));
+    }
+
+    if let Some(psi_token) = config_update.psi_token {
+        current_config.psi_token = psi_token;
+    }
+
+    if let Some(psi_to_nasset_pair) = config_update.psi_to_nasset_pair {
+        current_config.psi_to_nasset_pair = psi_to_nasset_pair;
+    }
+
+    if let Some(nasset_token_rewards) = config_update.nasset_token_rewards {
+        current_config.nasset_token_rewards = nasset_token_rewards;
+    }
+
+    if let Some(config_update_delay) = config_update.config_update_delay {
+        current_config.config_update_delay = config_update_delay;
+    }
+
+    if let Some(withdrawal_fee) = config_update.withdrawal_fee {
+        current_config.withdrawal_fee = withdrawal_fee;
+    }
+
+    if let Some(withdrawal_mode) = config_update.withdrawal_mode {
+        current_config.withdrawal_mode = withdrawal_mode;
+    }
+
+    if let Some(deposit_caps) = config_update.deposit_caps {
+        current_config.max_total_nasset = deposit_caps.max_total_nasset;
+        current_config.max_nasset_per_user = deposit_caps.max_nasset_per_user;
+    }
+
+    if let Some(router_update) = config_update.router {
+        current_config.router = router_update.router;
+    }
+
+    let mut response = Response::default();
+    if let Some(token_query_mode) = config_update.token_query_mode {
+        // token could be migrated while update was queued
+        check_token_query_mode(
+            deps.as_ref(),
+            &token_query_mode.token,
+            &token_query_mode.mode,
+        )?;
+        store_token_query_mode(
+            deps.storage,
+            &token_query_mode.token,
+            &token_query_mode.mode,
+        )?;
+        response = response.add_attribute("token", token_query_mode.token.as_str());
     }
 
-    if let Some(ref psi_to_nasset_pair_addr) = psi_to_nasset_pair_addr {
-        current_config.psi_to_nasset_pair = deps.api.addr_validate(psi_to_nasset_pair_addr)?;
+    if let Some(new_vault) = config_update.new_vault {
+        check_vault_is_new(deps.as_ref(), &new_vault.vault.nasset_token)?;
+        // stored as vault in InitANAsset reply, when anAsset address is known
+        store_pending_vault(deps.storage, &new_vault.vault)?;
+        response = response
+            .add_submessage(instantiate_auto_nasset_token(
+                &env,
+                &current_config.governance_contract,
+                new_vault.cw20_token_code_id,
+                &new_vault.collateral_token_symbol,
+            )?)
+            .add_attribute("nasset_token", new_vault.vault.nasset_token.as_str());
     }
 
-    if let Some(ref nasset_token_rewards_addr) = nasset_token_rewards_addr {
-        current_config.nasset_token_rewards = deps.api.addr_validate(nasset_token_rewards_addr)?;
+    store_config(deps.storage, &current_config)?;
+    remove_config_update(deps.storage);
+    Ok(response.add_attributes(vec![("action", "execute_config_update")]))
+}
+
+pub fn validate_withdrawal_fee(
+    deps: Deps,
+    withdrawal_fee: WithdrawalFeeConfig,
+) -> StdResult<WithdrawalFee> {
+    if withdrawal_fee.max_fee > Decimal::one() {
+        return Err(StdError::generic_err(
+            "withdrawal fee can't be greater than 100%",
+        ));
     }
 
+    Ok(WithdrawalFee {
+        max_fee: withdrawal_fee.max_fee,
+        decay_period: withdrawal_fee.decay_period,
+        treasury: withdrawal_fee
+            .treasury_addr
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+    })
+}
+
+pub fn cancel_config_update(deps: DepsMut) -> StdResult<Response> {
+    if may_load_config_update(deps.storage)?.is_none() {
+        return Err(StdError::generic_err("no pending config update"));
+    }
+
+    remove_config_update(deps.storage);
+    Ok(Response::default().add_attributes(vec![("action", "cancel_config_update")]))
+}
+
+// pause is not timelocked, so governance can react to incident in one transaction
+pub fn set_paused(deps: DepsMut, mut current_config: Config, paused: bool) -> StdResult<Response> {
+    current_config.paused = paused;
     store_config(deps.storage, &current_config)?;
-    Ok(Response::default())
+    Ok(Response::default().add_attributes(vec![
+        ("action", "set_paused"),
+        ("paused", &paused.to_string()),
+    ]))
+}
+
+fn check_not_paused(config: &Config) -> StdResult<()> {
+    if config.paused {
+        return Err(StdError::generic_err("autocompounder is paused"));
+    }
+    Ok(())
 }
 
-pub fn update_governance_addr(
+pub fn update_deposit_caps(
     deps: DepsMut,
     env: Env,
-    gov_addr: String,
-    seconds_to_wait_for_accept_gov_tx: u64,
+    current_config: Config,
+    max_total_nasset: Uint128,
+    max_nasset_per_user: Option<Uint128>,
 ) -> StdResult<Response> {
-    let current_time = get_time(&env.block);
-    let gov_update = GovernanceUpdateState {
-        new_governance_contract_addr: deps.api.addr_validate(&gov_addr)?,
-        wait_approve_until: current_time + seconds_to_wait_for_accept_gov_tx,
+    let config_update = ConfigUpdate {
+        deposit_caps: Some(DepositCaps {
+            max_total_nasset,
+            max_nasset_per_user,
+        }),
+        ..ConfigUpdate::default()
     };
-    store_gov_update(deps.storage, &gov_update)?;
-    Ok(Response::default())
+    queue_config_update(deps, env, current_config, config_update)
 }
 
-pub fn accept_governance(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
-    let gov_update = load_gov_update(deps.storage)?;
-    let current_time = get_time(&env.block);
+pub fn update_router(
+    deps: DepsMut,
+    env: Env,
+    current_config: Config,
+    router_addr: Option<String>,
+) -> StdResult<Response> {
+    let config_update = ConfigUpdate {
+        router: Some(RouterUpdate {
+            router: router_addr
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?,
+        }),
+        ..ConfigUpdate::default()
+    };
+    queue_config_update(deps, env, current_config, config_update)
+}
 
-    if gov_update.wait_approve_until < current_time {
+#[allow(clippy::too_many_arguments)]
+pub fn add_vault(
+    deps: DepsMut,
+    env: Env,
+    current_config: Config,
+    nasset_token_addr: String,
+    psi_to_nasset_pair_addr: String,
+    nasset_token_rewards_addr: String,
+    cw20_token_code_id: u64,
+    collateral_token_symbol: String,
+    max_total_nasset: Uint128,
+    max_nasset_per_user: Option<Uint128>,
+) -> StdResult<Response> {
+    let nasset_token = deps.api.addr_validate(&nasset_token_addr)?;
+    check_vault_is_new(deps.as_ref(), &nasset_token)?;
+    if current_config.has_primary_only_features() {
         return Err(StdError::generic_err(
-            "too late to accept governance owning",
+            "additional vaults don't support withdrawal fee and unbonding, disable them first",
         ));
     }
 
-    if info.sender != gov_update.new_governance_contract_addr {
-        return Err(StdError::generic_err("unauthorized"));
+    let config_update = ConfigUpdate {
+        new_vault: Some(NewVault {
+            vault: Vault {
+                nasset_token,
+                auto_nasset_token: Addr::unchecked(""),
+                psi_to_nasset_pair: deps.api.addr_validate(&psi_to_nasset_pair_addr)?,
+                nasset_token_rewards: deps.api.addr_validate(&nasset_token_rewards_addr)?,
+                max_total_nasset,
+                max_nasset_per_user,
+            },
+            cw20_token_code_id,
+            collateral_token_symbol,
+        }),
+        ..ConfigUpdate::default()
+    };
+    queue_config_update(deps, env, current_config, config_update)
+}
+
+fn check_vault_is_new(deps: Deps, nasset_token: &Addr) -> StdResult<()> {
+    if may_load_vault(deps.storage, nasset_token)?.is_some() {
+        return Err(StdError::generic_err(
+            "vault for this nAsset already exists",
+        ));
     }
 
-    let new_gov_add_str = gov_update.new_governance_contract_addr.to_string();
+    Ok(())
+}
+
+pub fn instantiate_auto_nasset_token(
+    env: &Env,
+    governance_contract: &Addr,
+    cw20_token_code_id: u64,
+    collateral_token_symbol: &str,
+) -> StdResult<SubMsg> {
+    let instantiate_msg = share_token::instantiate_msg(env, collateral_token_symbol);
+    Ok(SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin: Some(governance_contract.to_string()),
+            code_id: cw20_token_code_id,
+            msg: to_binary(&instantiate_msg)?,
+            funds: vec![],
+            //wasmd rejects empty labels
+            label: instantiate_msg.name,
+        }),
+        SubmsgIds::InitANAsset.id(),
+    ))
+}
 
-    let mut config = load_config(deps.storage)?;
-    config.governance_contract = gov_update.new_governance_contract_addr;
-    store_config(deps.storage, &config)?;
-    remove_gov_update(deps.storage);
+pub fn update_token_query_mode(
+    deps: DepsMut,
+    env: Env,
+    current_config: Config,
+    token_addr: String,
+    mode: TokenQueryMode,
+) -> StdResult<Response> {
+    let token = deps.api.addr_validate(&token_addr)?;
+    check_token_query_mode(deps.as_ref(), &token, &mode)?;
 
-    Ok(Response::default().add_attributes(vec![
-        ("action", "change_governance_contract"),
-        ("new_address", &new_gov_add_str),
-    ]))
+    let config_update = ConfigUpdate {
+        token_query_mode: Some(TokenQueryModeUpdate { token, mode }),
+        ..ConfigUpdate::default()
+    };
+    queue_config_update(deps, env, current_config, config_update)
+}
+
+fn check_token_query_mode(deps: Deps, token: &Addr, mode: &TokenQueryMode) -> StdResult<()> {
+    if let TokenQueryMode::Raw { layout } = mode {
+        let raw_supply = query_supply_raw(&deps.querier, token, layout)?;
+        let smart_supply = query_supply_smart(&deps.querier, token)?;
+        if raw_supply != smart_supply {
+            return Err(StdError::generic_err(
+                "raw storage layout does not match token",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn accept_governance(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    governance::accept_governance(deps, env, info, |storage, governance_contract| {
+        let mut config = load_config(storage)?;
+        config.governance_contract = governance_contract;
+        store_config(storage, &config)
+    })
 }
 
 pub fn receive_cw20(
@@ -90,8 +387,12 @@ pub fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> StdResult<Response> {
     match from_binary(&cw20_msg.msg)? {
-        Cw20HookMsg::Deposit {} => commands::receive_cw20_deposit(deps, env, info, cw20_msg),
-        Cw20HookMsg::Withdraw {} => commands::receive_cw20_withdraw(deps, env, info, cw20_msg),
+        Cw20HookMsg::Deposit { recipient } => {
+            commands::receive_cw20_deposit(deps, env, info, cw20_msg, recipient)
+        }
+        Cw20HookMsg::Withdraw { recipient } => {
+            commands::receive_cw20_withdraw(deps, env, info, cw20_msg, recipient)
+        }
     }
 }
 
@@ -100,61 +401,183 @@ pub fn receive_cw20_deposit(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
+    recipient: Option<String>,
 ) -> StdResult<Response> {
     let nasset_addr = info.sender;
-    // only nAsset contract can execute this message
+    // only nAsset contract of one of the vaults can execute this message
+    let vault = match may_load_vault(deps.storage, &nasset_addr)? {
+        Some(vault) => vault,
+        None => return Err(StdError::generic_err("unauthorized")),
+    };
+
+    //we trust cw20 contract
+    let sender_addr: Addr = Addr::unchecked(cw20_msg.sender);
+    let farmer_addr = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender_addr.clone(),
+    };
+
     let config: Config = load_config(deps.storage)?;
-    if nasset_addr != config.nasset_token {
-        return Err(StdError::generic_err("unauthorized"));
+    check_not_paused(&config)?;
+    check_deposit_caps(deps.as_ref(), &env, &vault, &farmer_addr, cw20_msg.amount)?;
+
+    let depositor = config.position_owner(&sender_addr, &farmer_addr).clone();
+    deposit_nasset(
+        deps,
+        env,
+        vault,
+        depositor,
+        farmer_addr,
+        cw20_msg.amount.into(),
+    )
+}
+
+fn check_deposit_caps(
+    deps: Deps,
+    env: &Env,
+    vault: &Vault,
+    farmer: &Addr,
+    deposit_amount: Uint128,
+) -> StdResult<()> {
+    // nAsset balance already includes deposited amount
+    let nasset_balance: Uint128 =
+        query_token_balance(deps, &vault.nasset_token, &env.contract.address)?;
+
+    if nasset_balance > vault.max_total_nasset {
+        return Err(StdError::generic_err("deposit exceeds vault capacity"));
     }
 
-    //we trust cw20 contract
-    let farmer_addr: Addr = Addr::unchecked(cw20_msg.sender);
+    if let Some(max_nasset_per_user) = vault.max_nasset_per_user {
+        let farmer_nasset = query_user_nasset(
+            deps,
+            env,
+            vault,
+            farmer,
+            nasset_balance.checked_sub(deposit_amount)?,
+        )?;
+
+        if farmer_nasset + deposit_amount > max_nasset_per_user {
+            return Err(StdError::generic_err("deposit exceeds user capacity"));
+        }
+    }
+
+    Ok(())
+}
+
+/// nAsset value of the user's anAsset tokens
+pub fn query_user_nasset(
+    deps: Deps,
+    env: &Env,
+    vault: &Vault,
+    user: &Addr,
+    nasset_balance: Uint128,
+) -> StdResult<Uint128> {
+    let auto_nasset_supply: Uint256 =
+        share_token::query_supply(deps, env, &vault.auto_nasset_token)?.into();
 
-    deposit_nasset(deps, env, config, farmer_addr, cw20_msg.amount.into())
+    if auto_nasset_supply.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let user_auto_nasset: Uint256 =
+        share_token::query_balance(deps, env, &vault.auto_nasset_token, user)?.into();
+
+    let user_nasset = share_math::convert_to_assets(
+        nasset_balance.into(),
+        auto_nasset_supply,
+        user_auto_nasset,
+        Rounding::Down,
+    )?;
+
+    Ok(user_nasset.into())
 }
 
 pub fn deposit_nasset(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    config: Config,
+    vault: Vault,
+    sender: Addr,
     farmer: Addr,
     deposit_amount: Uint256,
 ) -> StdResult<Response> {
+    let config: Config = load_config(deps.storage)?;
+    let is_primary_vault = config.is_primary_vault(&vault);
+
     let auto_nasset_supply: Uint256 =
-        query_supply(&deps.querier, &config.auto_nasset_token)?.into();
+        share_token::query_supply(deps.as_ref(), &env, &vault.auto_nasset_token)?.into();
 
     let nasset_balance: Uint256 =
-        query_token_balance(deps.as_ref(), &config.nasset_token, &env.contract.address).into();
+        query_token_balance(deps.as_ref(), &vault.nasset_token, &env.contract.address)?.into();
 
-    let is_first_depositor = auto_nasset_supply.is_zero();
+    // withdrawal fee decays since last deposit. Deposit to someone else's position
+    // doesn't reset its timer, otherwise anyone could keep it from decaying with dust
+    if is_primary_vault && sender == farmer {
+        store_user_state(
+            deps.storage,
+            &farmer,
+            &UserState {
+                last_deposit_time: get_time(&env.block),
+            },
+        )?;
+    }
+
+    // nAsset balance already includes deposited amount
+    if nasset_balance < deposit_amount {
+        return Err(StdError::generic_err(
+            "nAsset balance is less than deposit amount",
+        ));
+    }
 
     // anAsset tokens to mint:
     // user_share = (deposited_nasset / total_nasset)
     // anAsset_to_mint = anAsset_supply * user_share / (1 - user_share)
-    let auto_nasset_to_mint = if is_first_depositor {
-        deposit_amount
-    } else {
-        // 'nasset_supply' can't be zero here, cause we already mint some for first farmer
-        auto_nasset_supply * deposit_amount
-            / Decimal256::from_uint256(nasset_balance - deposit_amount)
-    };
+    let auto_nasset_to_mint = share_math::shares_to_mint(
+        nasset_balance - deposit_amount,
+        auto_nasset_supply,
+        deposit_amount,
+    )?;
+
+    let exchange_rate = share_math::exchange_rate(
+        nasset_balance.into(),
+        (auto_nasset_supply + auto_nasset_to_mint).into(),
+    );
+
+    update_stats(deps.storage, &vault.nasset_token, |stats| {
+        stats.nasset_deposited += Uint128::from(deposit_amount);
+    })?;
 
     //0. mint auto_nasset
-    Ok(Response::new()
-        .add_message(WasmMsg::Execute {
-            contract_addr: config.auto_nasset_token.to_string(),
+    let mut response = Response::new();
+    if share_token::is_embedded(&env, &vault.auto_nasset_token) {
+        share_token::mint_embedded(deps.branch(), &env, &farmer, auto_nasset_to_mint.into())?;
+    } else {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: vault.auto_nasset_token.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Mint {
                 recipient: farmer.to_string(),
                 amount: auto_nasset_to_mint.into(),
             })?,
             funds: vec![],
-        })
+        });
+    }
+
+    Ok(response
         .add_attributes(vec![
             ("action", "deposit_nasset"),
-            ("farmer", &farmer.to_string()),
+            ("farmer", farmer.as_ref()),
             ("amount", &deposit_amount.to_string()),
-        ]))
+        ])
+        .add_event(
+            DepositEvent {
+                nasset_token: vault.nasset_token,
+                sender,
+                recipient: farmer,
+                nasset_amount: deposit_amount.into(),
+                shares_minted: auto_nasset_to_mint.into(),
+                exchange_rate,
+            }
+            .into(),
+        ))
 }
 
 pub fn receive_cw20_withdraw(
@@ -162,31 +585,116 @@ pub fn receive_cw20_withdraw(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
+    recipient: Option<String>,
 ) -> StdResult<Response> {
     let contract_addr = info.sender;
-    // only anAsset contract can execute this message
+    // only anAsset contract of one of the vaults can execute this message
+    let vault = match may_load_vault_by_auto_nasset(deps.storage, &contract_addr)? {
+        Some(vault) => vault,
+        None => return Err(StdError::generic_err("unauthorized")),
+    };
+
+    //we trust cw20 contract
+    let sender_addr: Addr = Addr::unchecked(cw20_msg.sender);
+    let farmer_addr = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender_addr.clone(),
+    };
+
+    // withdrawal fee is charged by last deposit of the one whose anAsset is burned
     let config: Config = load_config(deps.storage)?;
-    if contract_addr != config.auto_nasset_token {
-        return Err(StdError::generic_err("unauthorized"));
+    let owner_addr = config.position_owner(&sender_addr, &farmer_addr).clone();
+    if !config.is_primary_vault(&vault) {
+        return withdraw_nasset(deps, env, vault, owner_addr, farmer_addr, cw20_msg.amount);
     }
 
-    //we trust cw20 contract
-    let farmer_addr: Addr = Addr::unchecked(cw20_msg.sender);
+    match config.withdrawal_mode {
+        WithdrawalMode::Instant {} => {
+            withdraw_nasset(deps, env, vault, owner_addr, farmer_addr, cw20_msg.amount)
+        }
+        WithdrawalMode::Unbonding { unbonding_period } => {
+            // unbonding is claimed by recipient and fee is charged on claim
+            if owner_addr != farmer_addr {
+                return Err(StdError::generic_err(
+                    "recipient has to be anAsset owner in unbonding mode",
+                ));
+            }
+            start_unbonding(deps, env, farmer_addr, cw20_msg.amount, unbonding_period)
+        }
+    }
+}
+
+pub fn start_unbonding(
+    deps: DepsMut,
+    env: Env,
+    farmer: Addr,
+    auto_nasset_amount: Uint128,
+    unbonding_period: u64,
+) -> StdResult<Response> {
+    // anAsset stays locked on contract balance until claim
+    let release_at = get_time(&env.block) + unbonding_period;
+    let mut unbondings = load_unbondings(deps.storage, &farmer)?;
+    unbondings.push(Unbonding {
+        auto_nasset_amount,
+        release_at,
+    });
+    store_unbondings(deps.storage, &farmer, &unbondings)?;
 
-    withdraw_nasset(deps, env, config, farmer_addr, cw20_msg.amount)
+    Ok(Response::new().add_attributes(vec![
+        ("action", "unbond"),
+        ("farmer", farmer.as_ref()),
+        ("auto_nasset_amount", &auto_nasset_amount.to_string()),
+        ("release_at", &release_at.to_string()),
+    ]))
+}
+
+pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = load_config(deps.storage)?;
+    let farmer = info.sender;
+    let current_time = get_time(&env.block);
+
+    let (released, unbonding): (Vec<Unbonding>, Vec<Unbonding>) =
+        load_unbondings(deps.storage, &farmer)?
+            .into_iter()
+            .partition(|unbonding| unbonding.release_at <= current_time);
+
+    let auto_nasset_to_withdraw: Uint128 = released
+        .iter()
+        .map(|unbonding| unbonding.auto_nasset_amount)
+        .sum();
+    if auto_nasset_to_withdraw.is_zero() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    store_unbondings(deps.storage, &farmer, &unbonding)?;
+
+    // paid with current rate, same way as instant withdraw
+    withdraw_nasset(
+        deps,
+        env,
+        config.primary_vault(),
+        farmer.clone(),
+        farmer,
+        auto_nasset_to_withdraw,
+    )
 }
 
 pub fn withdraw_nasset(
-    deps: DepsMut,
-    _env: Env,
-    config: Config,
+    mut deps: DepsMut,
+    env: Env,
+    vault: Vault,
+    sender: Addr,
     farmer: Addr,
     auto_nasset_to_withdraw_amount: Uint128,
 ) -> StdResult<Response> {
+    start_compound(deps.branch(), &env, &vault, sender.clone())?;
+
     //auto_nasset_to_withdraw_amount is not zero here, cw20 contract check it
     store_withdraw_action(
         deps.storage,
         WithdrawAction {
+            nasset_token: vault.nasset_token.clone(),
+            sender,
             farmer,
             auto_nasset_amount: auto_nasset_to_withdraw_amount,
         },
@@ -195,7 +703,7 @@ pub fn withdraw_nasset(
     Ok(Response::new()
         .add_submessage(SubMsg::reply_always(
             CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.nasset_token_rewards.to_string(),
+                contract_addr: vault.nasset_token_rewards.to_string(),
                 msg: to_binary(&NAssetTokenRewardsExecuteMsg::Anyone {
                     anyone_msg: NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient: None },
                 })?,
@@ -206,13 +714,21 @@ pub fn withdraw_nasset(
         .add_attributes(vec![("action", "claim_psi")]))
 }
 
-pub fn compound(deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Response> {
+pub fn compound(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nasset_token_addr: Option<String>,
+) -> StdResult<Response> {
     let config: Config = load_config(deps.storage)?;
+    check_not_paused(&config)?;
+    let vault = vault_or_primary(deps.as_ref(), &config, nasset_token_addr)?;
+    start_compound(deps.branch(), &env, &vault, info.sender)?;
 
     Ok(Response::new()
         .add_submessage(SubMsg::reply_on_success(
             CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.nasset_token_rewards.to_string(),
+                contract_addr: vault.nasset_token_rewards.to_string(),
                 msg: to_binary(&NAssetTokenRewardsExecuteMsg::Anyone {
                     anyone_msg: NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient: None },
                 })?,
@@ -223,43 +739,207 @@ pub fn compound(deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Respo
         .add_attributes(vec![("action", "claim_psi")]))
 }
 
-pub fn execute_withdraw(deps: DepsMut, env: Env) -> StdResult<Response> {
+/// Additional vault by nAsset token, primary vault when it is not specified
+pub fn vault_or_primary(
+    deps: Deps,
+    config: &Config,
+    nasset_token_addr: Option<String>,
+) -> StdResult<Vault> {
+    match nasset_token_addr {
+        Some(nasset_token_addr) => {
+            load_vault(deps.storage, &deps.api.addr_validate(&nasset_token_addr)?)
+        }
+        None => Ok(config.primary_vault()),
+    }
+}
+
+// remember PSI balance to know how much was claimed
+fn start_compound(deps: DepsMut, env: &Env, vault: &Vault, sender: Addr) -> StdResult<()> {
+    let config: Config = load_config(deps.storage)?;
+    let psi_balance_before_claim =
+        query_token_balance(deps.as_ref(), &config.psi_token, &env.contract.address)?;
+
+    store_compound_state(
+        deps.storage,
+        &CompoundState {
+            nasset_token: vault.nasset_token.clone(),
+            sender,
+            psi_balance_before_claim,
+            psi_claimed: Uint128::zero(),
+            psi_sold: Uint128::zero(),
+            nasset_balance_before_swap: Uint128::zero(),
+        },
+    )
+}
+
+/// Called in PsiClaimed reply, returns compounded vault and PSI amount to sell.
+/// Primary vault sells whole PSI balance, additional vaults sell only PSI they claimed
+pub fn record_psi_claimed(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+) -> StdResult<(Vault, Uint128)> {
+    let psi_balance = query_token_balance(deps.as_ref(), &config.psi_token, &env.contract.address)?;
+
+    let mut compound_state = match may_load_compound_state(deps.storage)? {
+        Some(compound_state) => compound_state,
+        None => return Ok((config.primary_vault(), psi_balance)),
+    };
+
+    let vault = load_vault(deps.storage, &compound_state.nasset_token)?;
+    let psi_claimed = psi_balance.saturating_sub(compound_state.psi_balance_before_claim);
+    let psi_to_sell = if config.is_primary_vault(&vault) {
+        psi_balance
+    } else {
+        psi_claimed
+    };
+
+    if psi_to_sell.is_zero() {
+        remove_compound_state(deps.storage);
+    } else {
+        compound_state.psi_claimed = psi_claimed;
+        compound_state.psi_sold = psi_to_sell;
+        compound_state.nasset_balance_before_swap =
+            query_token_balance(deps.as_ref(), &vault.nasset_token, &env.contract.address)?;
+        store_compound_state(deps.storage, &compound_state)?;
+    }
+
+    Ok((vault, psi_to_sell))
+}
+
+/// Called in PsiSold reply, finishes compound and executes pending withdraw (if any)
+pub fn execute_compound_and_withdraw(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let compound_event = match may_load_compound_state(deps.storage)? {
+        Some(compound_state) => {
+            remove_compound_state(deps.storage);
+            let vault = load_vault(deps.storage, &compound_state.nasset_token)?;
+            let compound_event = compound_event(deps.as_ref(), &env, &vault, compound_state)?;
+            update_stats(deps.storage, &vault.nasset_token, |stats| {
+                stats.psi_claimed += compound_event.psi_claimed;
+                stats.psi_swapped += compound_event.psi_sold;
+                stats.nasset_acquired += compound_event.nasset_bought;
+                stats.compounds_count += 1;
+                stats.last_compound_time = env.block.time.seconds();
+            })?;
+            Some(compound_event)
+        }
+        None => None,
+    };
+
+    let mut response = execute_withdraw(deps, env)?;
+    if let Some(compound_event) = compound_event {
+        // compound happens before withdraw
+        response.events.insert(0, compound_event.into());
+    }
+
+    Ok(response)
+}
+
+fn compound_event(
+    deps: Deps,
+    env: &Env,
+    vault: &Vault,
+    compound_state: CompoundState,
+) -> StdResult<CompoundEvent> {
+    let nasset_balance = query_token_balance(deps, &vault.nasset_token, &env.contract.address)?;
+    let auto_nasset_supply = share_token::query_supply(deps, env, &vault.auto_nasset_token)?;
+
+    Ok(CompoundEvent {
+        nasset_token: vault.nasset_token.clone(),
+        sender: compound_state.sender,
+        psi_claimed: compound_state.psi_claimed,
+        psi_sold: compound_state.psi_sold,
+        nasset_bought: nasset_balance.saturating_sub(compound_state.nasset_balance_before_swap),
+        exchange_rate: share_math::exchange_rate(nasset_balance, auto_nasset_supply),
+    })
+}
+
+pub fn execute_withdraw(mut deps: DepsMut, env: Env) -> StdResult<Response> {
     let config = load_config(deps.storage)?;
     if let Some(withdraw_action) = load_withdraw_action(deps.storage)? {
         remove_withdraw_action(deps.storage)?;
 
+        let vault = load_vault(deps.storage, &withdraw_action.nasset_token)?;
+        let withdrawal_fee_config = config.vault_withdrawal_fee(&vault);
+
         let nasset_balance: Uint256 = commands::query_token_balance(
             deps.as_ref(),
-            &config.nasset_token,
+            &vault.nasset_token,
             &env.contract.address,
-        )
+        )?
         .into();
 
         let auto_nasset_supply: Uint256 =
-            commands::query_supply(&deps.querier, &config.auto_nasset_token)?.into();
+            share_token::query_supply(deps.as_ref(), &env, &vault.auto_nasset_token)?.into();
+
+        let nasset_to_withdraw = share_math::assets_to_withdraw(
+            nasset_balance,
+            auto_nasset_supply,
+            withdraw_action.auto_nasset_amount.into(),
+        )?;
+
+        let withdrawal_fee = calculate_withdrawal_fee(
+            deps.as_ref(),
+            &env,
+            &withdrawal_fee_config,
+            &withdraw_action.sender,
+            nasset_to_withdraw.into(),
+        )?;
+        let nasset_to_withdraw: Uint256 = nasset_to_withdraw - withdrawal_fee.into();
+
+        // withdrawal fee stays in the vault without treasury
+        let mut nasset_balance_after = nasset_balance - nasset_to_withdraw;
+        if withdrawal_fee_config.treasury.is_some() {
+            nasset_balance_after = nasset_balance_after - withdrawal_fee.into();
+        }
+        let exchange_rate = share_math::exchange_rate(
+            nasset_balance_after.into(),
+            (auto_nasset_supply - withdraw_action.auto_nasset_amount.into()).into(),
+        );
 
-        let nasset_to_withdraw: Uint256 = nasset_balance
-            * Uint256::from(withdraw_action.auto_nasset_amount)
-            / Decimal256::from_uint256(auto_nasset_supply);
+        update_stats(deps.storage, &vault.nasset_token, |stats| {
+            stats.nasset_withdrawn += Uint128::from(nasset_to_withdraw);
+            stats.withdrawal_fees += withdrawal_fee;
+        })?;
 
         //0. send nasset to farmer
-        //1. burn anasset
-        Ok(Response::new()
-            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.nasset_token.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: withdraw_action.farmer.to_string(),
-                    amount: nasset_to_withdraw.into(),
-                })?,
-                funds: vec![],
-            }))
-            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.auto_nasset_token.to_string(),
+        //1. send withdrawal fee to treasury (if any)
+        //2. burn anasset
+        let mut response = Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: vault.nasset_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: withdraw_action.farmer.to_string(),
+                amount: nasset_to_withdraw.into(),
+            })?,
+            funds: vec![],
+        }));
+
+        if let Some(treasury) = withdrawal_fee_config.treasury {
+            if !withdrawal_fee.is_zero() {
+                response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: vault.nasset_token.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: treasury.to_string(),
+                        amount: withdrawal_fee,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+        }
+
+        if share_token::is_embedded(&env, &vault.auto_nasset_token) {
+            share_token::burn_embedded(deps.branch(), &env, withdraw_action.auto_nasset_amount)?;
+        } else {
+            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: vault.auto_nasset_token.to_string(),
                 msg: to_binary(&Cw20ExecuteMsg::Burn {
                     amount: withdraw_action.auto_nasset_amount,
                 })?,
                 funds: vec![],
-            }))
+            }));
+        }
+
+        Ok(response
             .add_attributes(vec![
                 ("action", "withdraw"),
                 (
@@ -267,84 +947,271 @@ pub fn execute_withdraw(deps: DepsMut, env: Env) -> StdResult<Response> {
                     &withdraw_action.auto_nasset_amount.to_string(),
                 ),
                 ("nasset_amount_withdrawed", &nasset_to_withdraw.to_string()),
-            ]))
+                ("withdrawal_fee", &withdrawal_fee.to_string()),
+            ])
+            .add_event(
+                WithdrawEvent {
+                    nasset_token: vault.nasset_token,
+                    sender: withdraw_action.sender,
+                    recipient: withdraw_action.farmer,
+                    shares_burned: withdraw_action.auto_nasset_amount,
+                    nasset_amount: nasset_to_withdraw.into(),
+                    withdrawal_fee,
+                    exchange_rate,
+                }
+                .into(),
+            ))
     } else {
         Ok(Response::new())
     }
 }
 
+pub fn calculate_withdrawal_fee(
+    deps: Deps,
+    env: &Env,
+    withdrawal_fee: &WithdrawalFee,
+    owner: &Addr,
+    nasset_amount: Uint128,
+) -> StdResult<Uint128> {
+    let decay_left = withdrawal_fee_decay_left(deps, env, withdrawal_fee, Some(owner))?;
+    if decay_left == 0 {
+        return Ok(Uint128::zero());
+    }
+
+    // fee = amount * max_fee * (decay_period - seconds_since_deposit) / decay_period
+    Ok((nasset_amount * withdrawal_fee.max_fee)
+        .multiply_ratio(decay_left, withdrawal_fee.decay_period))
+}
+
+/// Withdrawal fee of 'owner' as (nominator, denominator),
+/// maximum fee is returned when owner is not known
+pub fn withdrawal_fee_ratio(
+    deps: Deps,
+    env: &Env,
+    withdrawal_fee: &WithdrawalFee,
+    owner: Option<&Addr>,
+) -> StdResult<(Uint256, Uint256)> {
+    let decay_left = withdrawal_fee_decay_left(deps, env, withdrawal_fee, owner)?;
+    if decay_left == 0 {
+        return Ok((Uint256::zero(), Uint256::one()));
+    }
+
+    let max_fee_atomics = Uint128::new(DECIMAL_FRACTIONAL) * withdrawal_fee.max_fee;
+    Ok((
+        Uint256::from(max_fee_atomics) * Uint256::from(decay_left),
+        Uint256::from(DECIMAL_FRACTIONAL) * Uint256::from(withdrawal_fee.decay_period),
+    ))
+}
+
+/// Moves withdrawal fee timer together with embedded anAsset: receiver's last deposit time
+/// becomes average of both times weighted by balances, rounded up. Whole position carries
+/// sender's time and dust can't reset someone else's timer. No record counts as right now
+pub fn transfer_last_deposit_time(
+    deps: DepsMut,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    // anAsset sent to autocompounder itself is withdrawn by sender
+    if to == &env.contract.address || from == to || amount.is_zero() {
+        return Ok(());
+    }
+
+    let now = get_time(&env.block);
+    let last_deposit_time = |addr: &Addr| -> StdResult<u64> {
+        Ok(may_load_user_state(deps.storage, addr)?
+            .map_or(now, |user_state| user_state.last_deposit_time))
+    };
+    let from_time = last_deposit_time(from)?;
+    let to_time = last_deposit_time(to)?;
+    let to_balance = share_token::query_balance(deps.as_ref(), env, &env.contract.address, to)?;
+
+    // to_time + (from_time - to_time) * amount / (to_balance + amount)
+    let total = Uint256::from(to_balance + amount);
+    let shift = |seconds: u64, rounding: Rounding| -> u64 {
+        let shift = share_math::mul_div(seconds.into(), amount.into(), total, rounding);
+        Uint128::from(shift).u128() as u64
+    };
+    let last_deposit_time = if from_time >= to_time {
+        to_time + shift(from_time - to_time, Rounding::Up)
+    } else {
+        to_time - shift(to_time - from_time, Rounding::Down)
+    };
+
+    store_user_state(deps.storage, to, &UserState { last_deposit_time })
+}
+
+// seconds until withdrawal fee decays to zero, zero when there is no fee
+fn withdrawal_fee_decay_left(
+    deps: Deps,
+    env: &Env,
+    withdrawal_fee: &WithdrawalFee,
+    owner: Option<&Addr>,
+) -> StdResult<u64> {
+    if withdrawal_fee.max_fee.is_zero() {
+        return Ok(0);
+    }
+
+    let owner = match owner {
+        Some(owner) => owner,
+        None => return Ok(withdrawal_fee.decay_period),
+    };
+
+    // owner without deposit record got anAsset by transfer, e.g. from external share
+    // token, and pays full fee, otherwise fee is skipped by moving anAsset to new address
+    let last_deposit_time = match may_load_user_state(deps.storage, owner)? {
+        Some(user_state) => user_state.last_deposit_time,
+        None => return Ok(withdrawal_fee.decay_period),
+    };
+
+    let seconds_since_deposit = get_time(&env.block).saturating_sub(last_deposit_time);
+    Ok(withdrawal_fee
+        .decay_period
+        .saturating_sub(seconds_since_deposit))
+}
+
 fn get_time(block: &BlockInfo) -> u64 {
     block.time.seconds()
 }
 
 // ====================================================================================
 
-pub fn query_supply(querier: &QuerierWrapper, contract_addr: &Addr) -> StdResult<Uint128> {
-    if let Ok(supply) = query_supply_legacy(querier, contract_addr) {
-        return Ok(supply);
-    }
+pub fn query_accrued_psi_rewards(deps: Deps, vault: &Vault, env: &Env) -> StdResult<Uint128> {
+    let response: NAssetTokenRewardsAccruedRewardsResponse = deps.querier.query_wasm_smart(
+        vault.nasset_token_rewards.to_string(),
+        &NAssetTokenRewardsQueryMsg::AccruedRewards {
+            address: env.contract.address.to_string(),
+        },
+    )?;
 
-    query_supply_new(querier, contract_addr)
+    Ok(response.rewards)
 }
 
-fn query_supply_new(querier: &QuerierWrapper, contract_addr: &Addr) -> StdResult<Uint128> {
-    let token_info: TokenInfo = querier.query(&QueryRequest::Wasm(WasmQuery::Raw {
-        contract_addr: contract_addr.to_string(),
-        key: Binary::from(b"token_info"),
-    }))?;
+/// PSI amount which will be sold on compound: claimed rewards + PSI on contract balance
+pub fn query_psi_to_sell(
+    deps: Deps,
+    config: &Config,
+    vault: &Vault,
+    env: &Env,
+) -> StdResult<Uint128> {
+    let pending_rewards = query_pending_rewards(deps, config, vault, env)?;
+    Ok(pending_rewards.total_psi)
+}
 
-    Ok(token_info.total_supply)
+pub fn query_pending_rewards(
+    deps: Deps,
+    config: &Config,
+    vault: &Vault,
+    env: &Env,
+) -> StdResult<PendingRewardsResponse> {
+    let accrued_psi = query_accrued_psi_rewards(deps, vault, env)?;
+    // PSI on contract balance is sold by primary vault only, like in 'record_psi_claimed'
+    let psi_balance = if config.is_primary_vault(vault) {
+        query_token_balance(deps, &config.psi_token, &env.contract.address)?
+    } else {
+        Uint128::zero()
+    };
+
+    Ok(PendingRewardsResponse {
+        accrued_psi,
+        psi_balance,
+        total_psi: accrued_psi + psi_balance,
+    })
 }
 
-fn query_supply_legacy(querier: &QuerierWrapper, contract_addr: &Addr) -> StdResult<Uint128> {
-    let token_info: TokenInfo = querier.query(&QueryRequest::Wasm(WasmQuery::Raw {
-        contract_addr: contract_addr.to_string(),
-        key: Binary::from(to_length_prefixed(b"token_info")),
-    }))?;
+pub fn simulate_psi_swap(
+    deps: Deps,
+    config: &Config,
+    vault: &Vault,
+    psi_amount: Uint128,
+) -> StdResult<Uint128> {
+    if psi_amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
 
-    Ok(token_info.total_supply)
+    let response: AstroportSimulationResponse = deps.querier.query_wasm_smart(
+        vault.psi_to_nasset_pair.to_string(),
+        &AstroportQueryMsg::Simulation {
+            offer_asset: AstroportAsset {
+                info: AstroportAssetInfo::Token {
+                    contract_addr: config.psi_token.to_string(),
+                },
+                amount: psi_amount,
+            },
+        },
+    )?;
+
+    Ok(response.return_amount)
 }
 
 // ====================================================================================
 
-pub fn query_token_balance(deps: Deps, contract_addr: &Addr, account_addr: &Addr) -> Uint128 {
-    if let Ok(balance) = query_token_balance_legacy(&deps, contract_addr, account_addr) {
-        return balance;
+pub fn query_supply(deps: Deps, token: &Addr) -> StdResult<Uint128> {
+    match load_token_query_mode(deps.storage, token)? {
+        TokenQueryMode::Smart {} => query_supply_smart(&deps.querier, token),
+        TokenQueryMode::Raw { layout } => query_supply_raw(&deps.querier, token, &layout),
     }
+}
 
-    if let Ok(balance) = query_token_balance_new(&deps, contract_addr, account_addr) {
-        return balance;
-    }
+fn query_supply_smart(querier: &QuerierWrapper, token: &Addr) -> StdResult<Uint128> {
+    let token_info: TokenInfoResponse =
+        querier.query_wasm_smart(token.to_string(), &Cw20QueryMsg::TokenInfo {})?;
 
-    Uint128::zero()
+    Ok(token_info.total_supply)
 }
 
-fn query_token_balance_new(
-    deps: &Deps,
-    contract_addr: &Addr,
-    account_addr: &Addr,
+fn query_supply_raw(
+    querier: &QuerierWrapper,
+    token: &Addr,
+    layout: &Cw20StorageLayout,
 ) -> StdResult<Uint128> {
-    // load balance form the cw20 token contract version 0.6+
-    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Raw {
-        contract_addr: contract_addr.to_string(),
-        key: Binary::from(concat(
-            &to_length_prefixed(b"balance"),
-            account_addr.as_bytes(),
-        )),
-    }))
-}
-
-fn query_token_balance_legacy(
-    deps: &Deps,
-    contract_addr: &Addr,
-    account_addr: &Addr,
+    let key = match layout {
+        Cw20StorageLayout::Cw20Base {} => b"token_info".to_vec(),
+        Cw20StorageLayout::Legacy {} => to_length_prefixed(b"token_info"),
+    };
+
+    match querier.query_wasm_raw(token.to_string(), key)? {
+        Some(data) => Ok(from_slice::<TokenInfo>(&data)?.total_supply),
+        None => Err(StdError::generic_err(format!(
+            "no token info in {} storage",
+            token
+        ))),
+    }
+}
+
+// ====================================================================================
+
+pub fn query_token_balance(deps: Deps, token: &Addr, account: &Addr) -> StdResult<Uint128> {
+    match load_token_query_mode(deps.storage, token)? {
+        TokenQueryMode::Smart {} => {
+            let response: BalanceResponse = deps.querier.query_wasm_smart(
+                token.to_string(),
+                &Cw20QueryMsg::Balance {
+                    address: account.to_string(),
+                },
+            )?;
+            Ok(response.balance)
+        }
+        TokenQueryMode::Raw { layout } => query_token_balance_raw(deps, token, account, &layout),
+    }
+}
+
+fn query_token_balance_raw(
+    deps: Deps,
+    token: &Addr,
+    account: &Addr,
+    layout: &Cw20StorageLayout,
 ) -> StdResult<Uint128> {
-    // load balance form the cw20 token contract version 0.2.x
-    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Raw {
-        contract_addr: contract_addr.to_string(),
-        key: Binary::from(concat(
-            &to_length_prefixed(b"balance"),
-            (deps.api.addr_canonicalize(account_addr.as_str())?).as_slice(),
-        )),
-    }))
+    let account_key = match layout {
+        Cw20StorageLayout::Cw20Base {} => account.as_bytes().to_vec(),
+        Cw20StorageLayout::Legacy {} => deps.api.addr_canonicalize(account.as_str())?.to_vec(),
+    };
+    let key = concat(&to_length_prefixed(b"balance"), &account_key);
+
+    match deps.querier.query_wasm_raw(token.to_string(), key)? {
+        Some(data) => from_slice(&data),
+        // cw20 does not store empty balances
+        None => Ok(Uint128::zero()),
+    }
 }
@@ -1,29 +1,40 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply,
-    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    SubMsg, Uint128, WasmMsg,
 };
+use nasset_autocompounder_interface::reply_response::MsgInstantiateContractResponse;
+use nasset_autocompounder_interface::share_math::{self, Rounding};
 
 use crate::msg::{
-    AstroportCw20HookMsg, AutoNassetValueResponse, ConfigResponse, ExecuteMsg, GovernanceMsg,
-    InstantiateMsg, MigrateMsg, NassetValueResponse, QueryMsg,
+    AstroportCw20HookMsg, AutoNassetValueResponse, ConfigResponse, DepositCapacityResponse,
+    ExecuteMsg, GovernanceMsg, InstantiateMsg, MigrateMsg, NassetValueResponse, NewVault,
+    PendingConfigUpdateResponse, PendingRewardsResponse, QueryMsg, RouterUpdate, ShareTokenMode,
+    SimulateCompoundResponse, SimulateWithdrawResponse, StatsResponse, TokenQueryModeResponse,
+    TokenQueryModeUpdate, UnbondingResponse, UnbondingsResponse, VaultAssetsResponse,
+    VaultResponse, VaultSharesResponse, VaultsResponse, WithdrawalFeeConfig, WithdrawalMode,
 };
-use crate::reply_response::MsgInstantiateContractResponse;
-use crate::state::Config;
+use crate::share_token;
+use crate::state::{Config, Vault, WithdrawalFee};
 use crate::{
     commands,
-    state::{load_config, remove_withdraw_action, set_auto_nasset_token_addr, store_config},
+    state::{
+        load_config, load_legacy_config, load_stats, load_token_query_mode, load_unbondings,
+        load_vault, load_vaults, may_load_config_update, may_load_pending_vault,
+        remove_pending_vault, remove_withdraw_action, set_auto_nasset_token_addr, store_config,
+        store_vault,
+    },
     SubmsgIds,
 };
-use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_bignumber::Uint256;
 use cw20::Cw20ExecuteMsg;
-use cw20::MinterResponse;
-use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
-use protobuf::Message;
+use cw20_base::msg::QueryMsg as Cw20QueryMsg;
 use std::convert::TryFrom;
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
@@ -35,48 +46,77 @@ pub fn instantiate(
         psi_to_nasset_pair: deps.api.addr_validate(&msg.psi_to_nasset_pair_addr)?,
         governance_contract: deps.api.addr_validate(&msg.governance_contract_addr)?,
         nasset_token_rewards: deps.api.addr_validate(&msg.nasset_token_rewards_addr)?,
+        config_update_delay: msg.config_update_delay,
+        max_total_nasset: msg.max_total_nasset,
+        max_nasset_per_user: msg.max_nasset_per_user,
+        withdrawal_fee: match msg.withdrawal_fee {
+            Some(withdrawal_fee) => {
+                commands::validate_withdrawal_fee(deps.as_ref(), withdrawal_fee)?
+            }
+            None => WithdrawalFee::none(),
+        },
+        withdrawal_mode: msg.withdrawal_mode.unwrap_or(WithdrawalMode::Instant {}),
+        router: None,
+        paused: false,
     };
     store_config(deps.storage, &config)?;
     remove_withdraw_action(deps.storage)?;
 
-    Ok(Response::new().add_submessage(SubMsg::reply_on_success(
-        CosmosMsg::Wasm(WasmMsg::Instantiate {
-            admin: Some(config.governance_contract.to_string()),
-            code_id: msg.cw20_token_code_id,
-            msg: to_binary(&Cw20InstantiateMsg {
-                name: format!(
-                    "n{} autocompounder share representation",
-                    msg.collateral_token_symbol
-                ),
-                symbol: format!("cn{}", msg.collateral_token_symbol),
-                decimals: 6,
-                initial_balances: vec![],
-                mint: Some(MinterResponse {
-                    minter: env.contract.address.to_string(),
-                    cap: None,
-                }),
-                marketing: None,
-            })?,
-            funds: vec![],
-            label: "".to_string(),
-        }),
-        SubmsgIds::InitANAsset.id(),
-    )))
+    match msg.share_token_mode.unwrap_or(ShareTokenMode::External {}) {
+        ShareTokenMode::External {} => Ok(Response::new().add_submessage(
+            commands::instantiate_auto_nasset_token(
+                &env,
+                &config.governance_contract,
+                msg.cw20_token_code_id,
+                &msg.collateral_token_symbol,
+            )?,
+        )),
+        ShareTokenMode::Embedded {} => {
+            let auto_nasset_token_addr = share_token::instantiate_embedded(
+                deps.branch(),
+                &env,
+                &msg.collateral_token_symbol,
+            )?;
+            set_auto_nasset_token_addr(deps.storage, auto_nasset_token_addr.clone())?;
+
+            Ok(Response::new().add_attributes(vec![
+                ("action", "auto_nasset_token_initialized"),
+                ("auto_nasset_token_addr", auto_nasset_token_addr.as_str()),
+            ]))
+        }
+        ShareTokenMode::Existing { token_addr } => {
+            let auto_nasset_token_addr =
+                share_token::validate_existing(deps.as_ref(), &env, &token_addr)?;
+            set_auto_nasset_token_addr(deps.storage, auto_nasset_token_addr.clone())?;
+
+            Ok(Response::new().add_attributes(vec![
+                ("action", "auto_nasset_token_initialized"),
+                ("auto_nasset_token_addr", auto_nasset_token_addr.as_str()),
+            ]))
+        }
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+pub fn reply(mut deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     let submessage_enum = SubmsgIds::try_from(msg.id)?;
     match submessage_enum {
         SubmsgIds::InitANAsset => {
-            let data = msg.result.unwrap().data.unwrap();
-            let res: MsgInstantiateContractResponse = Message::parse_from_bytes(data.as_slice())
-                .map_err(|_| {
-                    StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
-                })?;
-
-            let auto_nasset_token_addr = res.get_contract_address();
-            set_auto_nasset_token_addr(deps.storage, Addr::unchecked(auto_nasset_token_addr))?;
+            let res = MsgInstantiateContractResponse::from_reply(msg)?;
+            let auto_nasset_token_addr = res.contract_address.as_str();
+            match may_load_pending_vault(deps.storage)? {
+                Some(mut vault) => {
+                    remove_pending_vault(deps.storage);
+                    vault.auto_nasset_token = Addr::unchecked(auto_nasset_token_addr);
+                    store_vault(deps.storage, &vault)?;
+                }
+                None => {
+                    set_auto_nasset_token_addr(
+                        deps.storage,
+                        Addr::unchecked(auto_nasset_token_addr),
+                    )?;
+                }
+            }
 
             Ok(Response::new().add_attributes(vec![
                 ("action", "auto_nasset_token_initialized"),
@@ -86,13 +126,9 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
 
         SubmsgIds::PsiClaimed => {
             let config = load_config(deps.storage)?;
-            let psi_balance = commands::query_token_balance(
-                deps.as_ref(),
-                &config.psi_token,
-                &env.contract.address,
-            );
+            let (vault, psi_to_sell) = commands::record_psi_claimed(deps.branch(), &env, &config)?;
 
-            if psi_balance.is_zero() {
+            if psi_to_sell.is_zero() {
                 return commands::execute_withdraw(deps, env);
             }
 
@@ -100,8 +136,8 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
                 WasmMsg::Execute {
                     contract_addr: config.psi_token.to_string(),
                     msg: to_binary(&Cw20ExecuteMsg::Send {
-                        amount: psi_balance,
-                        contract: config.psi_to_nasset_pair.to_string(),
+                        amount: psi_to_sell,
+                        contract: vault.psi_to_nasset_pair.to_string(),
                         msg: to_binary(&AstroportCw20HookMsg::Swap {
                             belief_price: None,
                             max_spread: None,
@@ -114,15 +150,103 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
             )))
         }
 
-        SubmsgIds::PsiSold => commands::execute_withdraw(deps, env),
+        SubmsgIds::PsiSold => commands::execute_compound_and_withdraw(deps, env),
     }
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::Receive(msg) => commands::receive_cw20(deps, env, info, msg),
-        ExecuteMsg::Compound {} => commands::compound(deps, env, info),
+        ExecuteMsg::Compound { nasset_token_addr } => {
+            commands::compound(deps, env, info, nasset_token_addr)
+        }
+        ExecuteMsg::Claim {} => commands::claim(deps, env, info),
+
+        ExecuteMsg::Transfer { recipient, amount } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::Transfer { recipient, amount },
+        ),
+        ExecuteMsg::Burn { amount } => {
+            share_token::execute(deps, env, info, Cw20ExecuteMsg::Burn { amount })
+        }
+        ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            },
+        ),
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::IncreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+        ),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::DecreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+        ),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            },
+        ),
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => share_token::execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::SendFrom {
+                owner,
+                contract,
+                amount,
+                msg,
+            },
+        ),
+        ExecuteMsg::BurnFrom { owner, amount } => {
+            share_token::execute(deps, env, info, Cw20ExecuteMsg::BurnFrom { owner, amount })
+        }
 
         ExecuteMsg::AcceptGovernance {} => commands::accept_governance(deps, env, info),
 
@@ -137,12 +261,36 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                     psi_token_addr,
                     psi_to_nasset_pair_addr,
                     nasset_token_rewards_addr,
+                    config_update_delay,
+                    withdrawal_fee,
+                    withdrawal_mode,
                 } => commands::update_config(
                     deps,
+                    env,
                     config,
                     psi_token_addr,
                     psi_to_nasset_pair_addr,
                     nasset_token_rewards_addr,
+                    config_update_delay,
+                    withdrawal_fee,
+                    withdrawal_mode,
+                ),
+
+                GovernanceMsg::ExecuteConfigUpdate {} => {
+                    commands::execute_config_update(deps, env, config)
+                }
+
+                GovernanceMsg::CancelConfigUpdate {} => commands::cancel_config_update(deps),
+
+                GovernanceMsg::UpdateDepositCaps {
+                    max_total_nasset,
+                    max_nasset_per_user,
+                } => commands::update_deposit_caps(
+                    deps,
+                    env,
+                    config,
+                    max_total_nasset,
+                    max_nasset_per_user,
                 ),
 
                 GovernanceMsg::UpdateGovernanceContract {
@@ -154,19 +302,171 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                     gov_addr,
                     seconds_to_wait_for_accept_gov_tx,
                 ),
+
+                GovernanceMsg::UpdateTokenQueryMode { token_addr, mode } => {
+                    commands::update_token_query_mode(deps, env, config, token_addr, mode)
+                }
+
+                GovernanceMsg::UpdateRouter { router_addr } => {
+                    commands::update_router(deps, env, config, router_addr)
+                }
+
+                GovernanceMsg::SetPaused { paused } => commands::set_paused(deps, config, paused),
+
+                GovernanceMsg::AddVault {
+                    nasset_token_addr,
+                    psi_to_nasset_pair_addr,
+                    nasset_token_rewards_addr,
+                    cw20_token_code_id,
+                    collateral_token_symbol,
+                    max_total_nasset,
+                    max_nasset_per_user,
+                } => commands::add_vault(
+                    deps,
+                    env,
+                    config,
+                    nasset_token_addr,
+                    psi_to_nasset_pair_addr,
+                    nasset_token_rewards_addr,
+                    cw20_token_code_id,
+                    collateral_token_symbol,
+                    max_total_nasset,
+                    max_nasset_per_user,
+                ),
             }
         }
     }
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::AutoNassetValue { amount } => {
-            to_binary(&query_auto_nasset_value(deps, env, amount)?)
+        QueryMsg::AutoNassetValue {
+            amount,
+            nasset_token_addr,
+        } => to_binary(&query_auto_nasset_value(
+            deps,
+            env,
+            amount,
+            nasset_token_addr,
+        )?),
+        QueryMsg::NAssetValue {
+            amount,
+            nasset_token_addr,
+        } => to_binary(&query_nasset_value(deps, env, amount, nasset_token_addr)?),
+        QueryMsg::PendingConfigUpdate {} => to_binary(&query_pending_config_update(deps)?),
+        QueryMsg::DepositCapacity {
+            address,
+            nasset_token_addr,
+        } => to_binary(&query_deposit_capacity(
+            deps,
+            env,
+            address,
+            nasset_token_addr,
+        )?),
+        QueryMsg::Unbondings { address } => to_binary(&query_unbondings(deps, address)?),
+        QueryMsg::ConvertToShares {
+            assets,
+            nasset_token_addr,
+        } => to_binary(&query_convert_to_shares(
+            deps,
+            env,
+            assets,
+            nasset_token_addr,
+        )?),
+        QueryMsg::ConvertToAssets {
+            shares,
+            nasset_token_addr,
+        } => to_binary(&query_convert_to_assets(
+            deps,
+            env,
+            shares,
+            nasset_token_addr,
+        )?),
+        // there is no deposit fee, so it is the same as ConvertToShares
+        QueryMsg::PreviewDeposit {
+            assets,
+            nasset_token_addr,
+        } => to_binary(&query_convert_to_shares(
+            deps,
+            env,
+            assets,
+            nasset_token_addr,
+        )?),
+        QueryMsg::PreviewWithdraw {
+            assets,
+            owner,
+            nasset_token_addr,
+        } => to_binary(&query_preview_withdraw(
+            deps,
+            env,
+            assets,
+            owner,
+            nasset_token_addr,
+        )?),
+        QueryMsg::MaxDeposit {
+            receiver,
+            nasset_token_addr,
+        } => to_binary(&query_max_deposit(deps, env, receiver, nasset_token_addr)?),
+        QueryMsg::MaxWithdraw {
+            owner,
+            nasset_token_addr,
+        } => to_binary(&query_max_withdraw(deps, env, owner, nasset_token_addr)?),
+        QueryMsg::TotalAssets { nasset_token_addr } => {
+            to_binary(&query_total_assets(deps, env, nasset_token_addr)?)
+        }
+        QueryMsg::Vault { nasset_token_addr } => {
+            to_binary(&query_vault(deps, env, nasset_token_addr)?)
+        }
+        QueryMsg::Vaults { start_after, limit } => {
+            to_binary(&query_vaults(deps, env, start_after, limit)?)
+        }
+        QueryMsg::PendingRewards { nasset_token_addr } => {
+            to_binary(&query_pending_rewards(deps, env, nasset_token_addr)?)
+        }
+        QueryMsg::SimulateCompound { nasset_token_addr } => {
+            to_binary(&query_simulate_compound(deps, env, nasset_token_addr)?)
+        }
+        QueryMsg::SimulateWithdraw {
+            cnasset_amount,
+            address,
+            nasset_token_addr,
+        } => to_binary(&query_simulate_withdraw(
+            deps,
+            env,
+            cnasset_amount,
+            address,
+            nasset_token_addr,
+        )?),
+        QueryMsg::Stats { nasset_token_addr } => to_binary(&query_stats(deps, nasset_token_addr)?),
+        QueryMsg::TokenQueryMode { token_addr } => {
+            to_binary(&query_token_query_mode(deps, token_addr)?)
+        }
+        QueryMsg::Balance { address } => {
+            share_token::query(deps, env, Cw20QueryMsg::Balance { address })
+        }
+        QueryMsg::TokenInfo {} => share_token::query(deps, env, Cw20QueryMsg::TokenInfo {}),
+        QueryMsg::Minter {} => share_token::query(deps, env, Cw20QueryMsg::Minter {}),
+        QueryMsg::Allowance { owner, spender } => {
+            share_token::query(deps, env, Cw20QueryMsg::Allowance { owner, spender })
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => share_token::query(
+            deps,
+            env,
+            Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            },
+        ),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            share_token::query(deps, env, Cw20QueryMsg::AllAccounts { start_after, limit })
         }
-        QueryMsg::NAssetValue { amount } => to_binary(&query_nasset_value(deps, env, amount)?),
     }
 }
 
@@ -179,48 +479,432 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         psi_to_nasset_pair_addr: config.psi_to_nasset_pair.to_string(),
         governance_contract_addr: config.governance_contract.to_string(),
         nasset_token_rewards_addr: config.nasset_token_rewards.to_string(),
+        config_update_delay: config.config_update_delay,
+        max_total_nasset: config.max_total_nasset,
+        max_nasset_per_user: config.max_nasset_per_user,
+        withdrawal_fee: withdrawal_fee_to_response(config.withdrawal_fee),
+        withdrawal_mode: config.withdrawal_mode,
+        router_addr: config.router.map(|addr| addr.to_string()),
+        paused: config.paused,
     })
 }
 
+pub fn query_pending_config_update(deps: Deps) -> StdResult<Option<PendingConfigUpdateResponse>> {
+    let config_update = may_load_config_update(deps.storage)?;
+    Ok(config_update.map(|update| PendingConfigUpdateResponse {
+        psi_token_addr: update.psi_token.map(|addr| addr.to_string()),
+        psi_to_nasset_pair_addr: update.psi_to_nasset_pair.map(|addr| addr.to_string()),
+        nasset_token_rewards_addr: update.nasset_token_rewards.map(|addr| addr.to_string()),
+        config_update_delay: update.config_update_delay,
+        withdrawal_fee: update.withdrawal_fee.map(withdrawal_fee_to_response),
+        withdrawal_mode: update.withdrawal_mode,
+        deposit_caps: update.deposit_caps,
+        token_query_mode: update
+            .token_query_mode
+            .map(|token_query_mode| TokenQueryModeUpdate {
+                token_addr: token_query_mode.token.to_string(),
+                mode: token_query_mode.mode,
+            }),
+        new_vault: update.new_vault.map(|new_vault| NewVault {
+            nasset_token_addr: new_vault.vault.nasset_token.to_string(),
+            psi_to_nasset_pair_addr: new_vault.vault.psi_to_nasset_pair.to_string(),
+            nasset_token_rewards_addr: new_vault.vault.nasset_token_rewards.to_string(),
+            cw20_token_code_id: new_vault.cw20_token_code_id,
+            collateral_token_symbol: new_vault.collateral_token_symbol,
+            max_total_nasset: new_vault.vault.max_total_nasset,
+            max_nasset_per_user: new_vault.vault.max_nasset_per_user,
+        }),
+        router: update.router.map(|router_update| RouterUpdate {
+            router_addr: router_update.router.map(|addr| addr.to_string()),
+        }),
+        execute_after: update.execute_after,
+    }))
+}
+
+fn withdrawal_fee_to_response(withdrawal_fee: WithdrawalFee) -> WithdrawalFeeConfig {
+    WithdrawalFeeConfig {
+        max_fee: withdrawal_fee.max_fee,
+        decay_period: withdrawal_fee.decay_period,
+        treasury_addr: withdrawal_fee.treasury.map(|addr| addr.to_string()),
+    }
+}
+
 pub fn query_auto_nasset_value(
     deps: Deps,
     env: Env,
     amount: Uint128,
+    nasset_token_addr: Option<String>,
 ) -> StdResult<AutoNassetValueResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+    let (nasset_balance, auto_nasset_supply) = query_vault_totals(deps, &env, &vault)?;
+
+    let nasset_amount = share_math::convert_to_assets(
+        nasset_balance,
+        auto_nasset_supply,
+        amount.into(),
+        Rounding::Down,
+    )?;
+
+    Ok(AutoNassetValueResponse {
+        nasset_amount: nasset_amount.into(),
+    })
+}
+
+pub fn query_nasset_value(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+    nasset_token_addr: Option<String>,
+) -> StdResult<NassetValueResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+    let (nasset_balance, auto_nasset_supply) = query_vault_totals(deps, &env, &vault)?;
+
+    let cnasset_amount = share_math::convert_to_shares(
+        nasset_balance,
+        auto_nasset_supply,
+        amount.into(),
+        Rounding::Down,
+    )?;
+
+    Ok(NassetValueResponse {
+        cnasset_amount: cnasset_amount.into(),
+    })
+}
+
+pub fn query_deposit_capacity(
+    deps: Deps,
+    env: Env,
+    address: Option<String>,
+    nasset_token_addr: Option<String>,
+) -> StdResult<DepositCapacityResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+
+    let nasset_balance: Uint128 =
+        commands::query_token_balance(deps, &vault.nasset_token, &env.contract.address)?;
+    let remaining_total_nasset = vault.max_total_nasset.saturating_sub(nasset_balance);
+
+    let (user_nasset, remaining_user_nasset) = match (address, vault.max_nasset_per_user) {
+        (Some(address), Some(max_nasset_per_user)) => {
+            let user_addr = deps.api.addr_validate(&address)?;
+            let user_nasset =
+                commands::query_user_nasset(deps, &env, &vault, &user_addr, nasset_balance)?;
+            let remaining_user_nasset = std::cmp::min(
+                max_nasset_per_user.saturating_sub(user_nasset),
+                remaining_total_nasset,
+            );
+            (Some(user_nasset), Some(remaining_user_nasset))
+        }
+        _ => (None, None),
+    };
+
+    Ok(DepositCapacityResponse {
+        total_nasset: nasset_balance,
+        remaining_total_nasset,
+        user_nasset,
+        remaining_user_nasset,
+    })
+}
+
+pub fn query_unbondings(deps: Deps, address: String) -> StdResult<UnbondingsResponse> {
+    let user_addr = deps.api.addr_validate(&address)?;
+    let unbondings = load_unbondings(deps.storage, &user_addr)?;
+
+    Ok(UnbondingsResponse {
+        unbondings: unbondings
+            .into_iter()
+            .map(|unbonding| UnbondingResponse {
+                auto_nasset_amount: unbonding.auto_nasset_amount,
+                release_at: unbonding.release_at,
+            })
+            .collect(),
+    })
+}
+
+fn query_vault_or_primary(deps: Deps, nasset_token_addr: Option<String>) -> StdResult<Vault> {
     let config: Config = load_config(deps.storage)?;
+    commands::vault_or_primary(deps, &config, nasset_token_addr)
+}
 
+fn query_vault_totals(deps: Deps, env: &Env, vault: &Vault) -> StdResult<(Uint256, Uint256)> {
     let nasset_balance: Uint256 =
-        commands::query_token_balance(deps, &config.nasset_token, &env.contract.address).into();
+        commands::query_token_balance(deps, &vault.nasset_token, &env.contract.address)?.into();
 
     let auto_nasset_supply: Uint256 =
-        commands::query_supply(&deps.querier, &config.auto_nasset_token)?.into();
+        share_token::query_supply(deps, env, &vault.auto_nasset_token)?.into();
 
-    let nasset_amount: Uint256 =
-        nasset_balance * Uint256::from(amount) / Decimal256::from_uint256(auto_nasset_supply);
+    Ok((nasset_balance, auto_nasset_supply))
+}
 
-    Ok(AutoNassetValueResponse {
-        nasset_amount: nasset_amount.into(),
+pub fn query_convert_to_shares(
+    deps: Deps,
+    env: Env,
+    assets: Uint128,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultSharesResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+    let (total_assets, total_shares) = query_vault_totals(deps, &env, &vault)?;
+
+    let shares =
+        share_math::convert_to_shares(total_assets, total_shares, assets.into(), Rounding::Down)?;
+
+    Ok(VaultSharesResponse {
+        shares: shares.into(),
     })
 }
 
-pub fn query_nasset_value(deps: Deps, env: Env, amount: Uint128) -> StdResult<NassetValueResponse> {
+pub fn query_convert_to_assets(
+    deps: Deps,
+    env: Env,
+    shares: Uint128,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultAssetsResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+    let (total_assets, total_shares) = query_vault_totals(deps, &env, &vault)?;
+
+    let assets =
+        share_math::convert_to_assets(total_assets, total_shares, shares.into(), Rounding::Down)?;
+
+    Ok(VaultAssetsResponse {
+        assets: assets.into(),
+    })
+}
+
+/// Includes withdrawal fee like 'MaxWithdraw', so burning returned anAsset amount
+/// gives at least 'assets' nAsset
+pub fn query_preview_withdraw(
+    deps: Deps,
+    env: Env,
+    assets: Uint128,
+    owner: Option<String>,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultSharesResponse> {
     let config: Config = load_config(deps.storage)?;
+    let vault = commands::vault_or_primary(deps, &config, nasset_token_addr)?;
+    let owner_addr = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?;
+    let (total_assets, total_shares) = query_vault_totals(deps, &env, &vault)?;
 
-    let nasset_balance: Uint256 =
-        commands::query_token_balance(deps, &config.nasset_token, &env.contract.address).into();
+    let (fee_nom, fee_denom) = commands::withdrawal_fee_ratio(
+        deps,
+        &env,
+        &config.vault_withdrawal_fee(&vault),
+        owner_addr.as_ref(),
+    )?;
+    let assets_before_fee = share_math::assets_before_fee(assets.into(), fee_nom, fee_denom)?;
 
-    let auto_nasset_supply: Uint256 =
-        commands::query_supply(&deps.querier, &config.auto_nasset_token)?.into();
+    // user should burn enough anAsset to cover requested nAsset
+    let shares =
+        share_math::convert_to_shares(total_assets, total_shares, assets_before_fee, Rounding::Up)?;
 
-    let cnasset_amount: Uint256 =
-        auto_nasset_supply * Uint256::from(amount) / Decimal256::from_uint256(nasset_balance);
+    Ok(VaultSharesResponse {
+        shares: shares.into(),
+    })
+}
 
-    Ok(NassetValueResponse {
-        cnasset_amount: cnasset_amount.into(),
+pub fn query_max_deposit(
+    deps: Deps,
+    env: Env,
+    receiver: Option<String>,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultAssetsResponse> {
+    let capacity = query_deposit_capacity(deps, env, receiver, nasset_token_addr)?;
+
+    Ok(VaultAssetsResponse {
+        assets: capacity
+            .remaining_user_nasset
+            .unwrap_or(capacity.remaining_total_nasset),
+    })
+}
+
+pub fn query_max_withdraw(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultAssetsResponse> {
+    let config: Config = load_config(deps.storage)?;
+    let vault = commands::vault_or_primary(deps, &config, nasset_token_addr)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let (total_assets, total_shares) = query_vault_totals(deps, &env, &vault)?;
+
+    let owner_shares: Uint256 =
+        share_token::query_balance(deps, &env, &vault.auto_nasset_token, &owner_addr)?.into();
+    let assets: Uint128 =
+        share_math::convert_to_assets(total_assets, total_shares, owner_shares, Rounding::Down)?
+            .into();
+
+    let withdrawal_fee = commands::calculate_withdrawal_fee(
+        deps,
+        &env,
+        &config.vault_withdrawal_fee(&vault),
+        &owner_addr,
+        assets,
+    )?;
+
+    Ok(VaultAssetsResponse {
+        assets: assets - withdrawal_fee,
     })
 }
 
-#[entry_point]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn query_total_assets(
+    deps: Deps,
+    env: Env,
+    nasset_token_addr: Option<String>,
+) -> StdResult<VaultAssetsResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+
+    Ok(VaultAssetsResponse {
+        assets: commands::query_token_balance(deps, &vault.nasset_token, &env.contract.address)?,
+    })
+}
+
+pub fn query_vault(deps: Deps, env: Env, nasset_token_addr: String) -> StdResult<VaultResponse> {
+    let vault = load_vault(deps.storage, &deps.api.addr_validate(&nasset_token_addr)?)?;
+    vault_to_response(deps, &env, vault)
+}
+
+pub fn query_vaults(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VaultsResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let vaults = load_vaults(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|vault| vault_to_response(deps, &env, vault))
+        .collect::<StdResult<Vec<VaultResponse>>>()?;
+
+    Ok(VaultsResponse { vaults })
+}
+
+fn vault_to_response(deps: Deps, env: &Env, vault: Vault) -> StdResult<VaultResponse> {
+    let total_nasset =
+        commands::query_token_balance(deps, &vault.nasset_token, &env.contract.address)?;
+    let auto_nasset_supply = share_token::query_supply(deps, env, &vault.auto_nasset_token)?;
+
+    Ok(VaultResponse {
+        nasset_token: vault.nasset_token.to_string(),
+        auto_nasset_token: vault.auto_nasset_token.to_string(),
+        psi_to_nasset_pair: vault.psi_to_nasset_pair.to_string(),
+        nasset_token_rewards: vault.nasset_token_rewards.to_string(),
+        max_total_nasset: vault.max_total_nasset,
+        max_nasset_per_user: vault.max_nasset_per_user,
+        total_nasset,
+        auto_nasset_supply,
+    })
+}
+
+pub fn query_pending_rewards(
+    deps: Deps,
+    env: Env,
+    nasset_token_addr: Option<String>,
+) -> StdResult<PendingRewardsResponse> {
+    let config: Config = load_config(deps.storage)?;
+    let vault = commands::vault_or_primary(deps, &config, nasset_token_addr)?;
+    commands::query_pending_rewards(deps, &config, &vault, &env)
+}
+
+pub fn query_simulate_compound(
+    deps: Deps,
+    env: Env,
+    nasset_token_addr: Option<String>,
+) -> StdResult<SimulateCompoundResponse> {
+    let config: Config = load_config(deps.storage)?;
+    let vault = commands::vault_or_primary(deps, &config, nasset_token_addr)?;
+    let (nasset_balance, auto_nasset_supply) = query_vault_totals(deps, &env, &vault)?;
+
+    let psi_to_sell = commands::query_psi_to_sell(deps, &config, &vault, &env)?;
+    let nasset_out = commands::simulate_psi_swap(deps, &config, &vault, psi_to_sell)?;
+
+    let nasset_balance: Uint128 = (nasset_balance + nasset_out.into()).into();
+    let auto_nasset_supply: Uint128 = auto_nasset_supply.into();
+    let nasset_per_auto_nasset = share_math::exchange_rate(nasset_balance, auto_nasset_supply);
+
+    Ok(SimulateCompoundResponse {
+        psi_to_sell,
+        nasset_out,
+        nasset_per_auto_nasset,
+    })
+}
+
+/// Mirrors PsiClaimed -> PsiSold -> execute_withdraw chain
+pub fn query_simulate_withdraw(
+    deps: Deps,
+    env: Env,
+    cnasset_amount: Uint128,
+    address: Option<String>,
+    nasset_token_addr: Option<String>,
+) -> StdResult<SimulateWithdrawResponse> {
+    let config: Config = load_config(deps.storage)?;
+    let vault = commands::vault_or_primary(deps, &config, nasset_token_addr)?;
+    let (nasset_balance, auto_nasset_supply) = query_vault_totals(deps, &env, &vault)?;
+
+    let psi_to_sell = commands::query_psi_to_sell(deps, &config, &vault, &env)?;
+    let nasset_out = commands::simulate_psi_swap(deps, &config, &vault, psi_to_sell)?;
+
+    let nasset_to_withdraw: Uint128 = share_math::assets_to_withdraw(
+        nasset_balance + nasset_out.into(),
+        auto_nasset_supply,
+        cnasset_amount.into(),
+    )?
+    .into();
+
+    let withdrawal_fee = match address {
+        Some(address) => commands::calculate_withdrawal_fee(
+            deps,
+            &env,
+            &config.vault_withdrawal_fee(&vault),
+            &deps.api.addr_validate(&address)?,
+            nasset_to_withdraw,
+        )?,
+        None => Uint128::zero(),
+    };
+
+    Ok(SimulateWithdrawResponse {
+        nasset_amount: nasset_to_withdraw - withdrawal_fee,
+        withdrawal_fee,
+    })
+}
+
+pub fn query_token_query_mode(deps: Deps, token_addr: String) -> StdResult<TokenQueryModeResponse> {
+    let token = deps.api.addr_validate(&token_addr)?;
+    Ok(TokenQueryModeResponse {
+        mode: load_token_query_mode(deps.storage, &token)?,
+    })
+}
+
+pub fn query_stats(deps: Deps, nasset_token_addr: Option<String>) -> StdResult<StatsResponse> {
+    let vault = query_vault_or_primary(deps, nasset_token_addr)?;
+    let stats = load_stats(deps.storage, &vault.nasset_token)?;
+    Ok(StatsResponse {
+        psi_claimed: stats.psi_claimed,
+        psi_swapped: stats.psi_swapped,
+        nasset_acquired: stats.nasset_acquired,
+        compounds_count: stats.compounds_count,
+        last_compound_time: stats.last_compound_time,
+        withdrawal_fees: stats.withdrawal_fees,
+        nasset_deposited: stats.nasset_deposited,
+        nasset_withdrawn: stats.nasset_withdrawn,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    if load_config(deps.storage).is_ok() {
+        return Ok(Response::default());
+    }
+
+    // config is stored in legacy layout
+    let legacy_config = load_legacy_config(deps.storage)?;
+    let config_update_delay = msg.config_update_delay.ok_or_else(|| {
+        StdError::generic_err("config_update_delay is required to migrate legacy config")
+    })?;
+    store_config(deps.storage, &legacy_config.migrate(config_update_delay))?;
+
+    Ok(Response::new().add_attribute("action", "migrate_config"))
 }
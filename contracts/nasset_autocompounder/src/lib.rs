@@ -4,8 +4,11 @@ use std::convert::TryFrom;
 mod commands;
 pub mod contract;
 pub mod msg;
-mod reply_response;
+pub mod share_token;
 pub mod state;
+// unit test harness for dependent contracts
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 #[cfg(test)]
 #[allow(dead_code)]
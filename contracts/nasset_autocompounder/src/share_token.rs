@@ -0,0 +1,188 @@
+//! anAsset (cnAsset) share token helpers.
+//!
+//! Share token is either separate cw20-base contract or embedded one, when autocompounder
+//! implements cw20 interface itself using cw20-base as a library. Embedded token address
+//! is autocompounder address, its supply and balances are read from own storage.
+
+use crate::{commands, state::load_config};
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+};
+use cw20::{Cw20ExecuteMsg, MinterResponse};
+use cw20_base::msg::{InstantiateMsg as Cw20InstantiateMsg, QueryMsg as Cw20QueryMsg};
+use cw20_base::state::{MinterData, TokenInfo, BALANCES, TOKEN_INFO};
+use cw20_base::ContractError;
+
+pub fn instantiate_msg(env: &Env, collateral_token_symbol: &str) -> Cw20InstantiateMsg {
+    Cw20InstantiateMsg {
+        name: format!(
+            "n{} autocompounder share representation",
+            collateral_token_symbol
+        ),
+        symbol: format!("cn{}", collateral_token_symbol),
+        decimals: 6,
+        initial_balances: vec![],
+        mint: Some(MinterResponse {
+            minter: env.contract.address.to_string(),
+            cap: None,
+        }),
+        marketing: None,
+    }
+}
+
+/// Initializes embedded share token, returns its address
+pub fn instantiate_embedded(
+    deps: DepsMut,
+    env: &Env,
+    collateral_token_symbol: &str,
+) -> StdResult<Addr> {
+    let msg = instantiate_msg(env, collateral_token_symbol);
+    msg.validate()?;
+
+    TOKEN_INFO.save(
+        deps.storage,
+        &TokenInfo {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            total_supply: Uint128::zero(),
+            mint: Some(MinterData {
+                minter: env.contract.address.clone(),
+                cap: None,
+            }),
+        },
+    )?;
+
+    Ok(env.contract.address.clone())
+}
+
+/// Checks that autocompounder can mint existing share token, returns its address
+pub fn validate_existing(deps: Deps, env: &Env, token_addr: &str) -> StdResult<Addr> {
+    let token = deps.api.addr_validate(token_addr)?;
+    let minter: Option<MinterResponse> = deps
+        .querier
+        .query_wasm_smart(token.to_string(), &Cw20QueryMsg::Minter {})?;
+
+    match minter {
+        Some(minter) if minter.minter == env.contract.address.as_str() => Ok(token),
+        _ => Err(StdError::generic_err(
+            "autocompounder is not minter of share token",
+        )),
+    }
+}
+
+pub fn is_embedded(env: &Env, share_token: &Addr) -> bool {
+    share_token == &env.contract.address
+}
+
+pub fn query_supply(deps: Deps, env: &Env, share_token: &Addr) -> StdResult<Uint128> {
+    if is_embedded(env, share_token) {
+        return Ok(TOKEN_INFO.load(deps.storage)?.total_supply);
+    }
+
+    commands::query_supply(deps, share_token)
+}
+
+pub fn query_balance(
+    deps: Deps,
+    env: &Env,
+    share_token: &Addr,
+    account: &Addr,
+) -> StdResult<Uint128> {
+    if is_embedded(env, share_token) {
+        return Ok(BALANCES
+            .may_load(deps.storage, account)?
+            .unwrap_or_default());
+    }
+
+    commands::query_token_balance(deps, share_token, account)
+}
+
+// mint and burn are executed by autocompounder as a minter of embedded token
+
+pub fn mint_embedded(deps: DepsMut, env: &Env, recipient: &Addr, amount: Uint128) -> StdResult<()> {
+    cw20_base::contract::execute_mint(
+        deps,
+        env.clone(),
+        self_info(env),
+        recipient.to_string(),
+        amount,
+    )
+    .map_err(to_std_err)?;
+    Ok(())
+}
+
+// burns anAsset from autocompounder balance
+pub fn burn_embedded(deps: DepsMut, env: &Env, amount: Uint128) -> StdResult<()> {
+    cw20_base::contract::execute_burn(deps, env.clone(), self_info(env), amount)
+        .map_err(to_std_err)?;
+    Ok(())
+}
+
+/// cw20 execute messages of embedded share token
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    assert_embedded(deps.as_ref(), &env)?;
+
+    // withdrawal fee timer moves with anAsset
+    let transfer = match &msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            Some((info.sender.to_string(), recipient, *amount))
+        }
+        Cw20ExecuteMsg::Send {
+            contract, amount, ..
+        } => Some((info.sender.to_string(), contract, *amount)),
+        Cw20ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => Some((owner.clone(), recipient, *amount)),
+        Cw20ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            ..
+        } => Some((owner.clone(), contract, *amount)),
+        _ => None,
+    };
+    if let Some((from, to, amount)) = transfer {
+        let from = deps.api.addr_validate(&from)?;
+        let to = deps.api.addr_validate(to)?;
+        commands::transfer_last_deposit_time(deps.branch(), &env, &from, &to, amount)?;
+    }
+
+    cw20_base::contract::execute(deps, env, info, msg).map_err(to_std_err)
+}
+
+/// cw20 queries of embedded share token
+pub fn query(deps: Deps, env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    assert_embedded(deps, &env)?;
+    cw20_base::contract::query(deps, env, msg)
+}
+
+fn assert_embedded(deps: Deps, env: &Env) -> StdResult<()> {
+    let config = load_config(deps.storage)?;
+    if !is_embedded(env, &config.auto_nasset_token) {
+        return Err(StdError::generic_err("share token is not embedded"));
+    }
+
+    Ok(())
+}
+
+fn self_info(env: &Env) -> MessageInfo {
+    MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    }
+}
+
+fn to_std_err(err: ContractError) -> StdError {
+    match err {
+        ContractError::Std(err) => err,
+        err => StdError::generic_err(err.to_string()),
+    }
+}
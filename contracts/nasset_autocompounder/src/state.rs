@@ -1,7 +1,9 @@
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Item, Map};
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, StdError, StdResult, Storage, Uint128};
+use crate::msg::{DepositCaps, TokenQueryMode, WithdrawalMode};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdError, StdResult, Storage, Uint128};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Config {
@@ -11,25 +13,236 @@ pub struct Config {
     pub psi_to_nasset_pair: Addr,
     pub governance_contract: Addr,
     pub nasset_token_rewards: Addr,
+    //how many seconds governance should wait before queued config update can be executed
+    pub config_update_delay: u64,
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+    pub withdrawal_fee: WithdrawalFee,
+    pub withdrawal_mode: WithdrawalMode,
+    //trusted to deposit and withdraw on behalf of cw20 hook 'recipient'
+    pub router: Option<Addr>,
+    //deposits and compounding are stopped, withdrawals still work
+    pub paused: bool,
+}
+
+impl Config {
+    pub fn primary_vault(&self) -> Vault {
+        Vault {
+            nasset_token: self.nasset_token.clone(),
+            auto_nasset_token: self.auto_nasset_token.clone(),
+            psi_to_nasset_pair: self.psi_to_nasset_pair.clone(),
+            nasset_token_rewards: self.nasset_token_rewards.clone(),
+            max_total_nasset: self.max_total_nasset,
+            max_nasset_per_user: self.max_nasset_per_user,
+        }
+    }
+
+    pub fn is_primary_vault(&self, vault: &Vault) -> bool {
+        vault.nasset_token == self.nasset_token
+    }
+
+    pub fn vault_withdrawal_fee(&self, vault: &Vault) -> WithdrawalFee {
+        if self.is_primary_vault(vault) {
+            self.withdrawal_fee.clone()
+        } else {
+            WithdrawalFee::none()
+        }
+    }
+
+    // features which additional vaults don't support
+    pub fn has_primary_only_features(&self) -> bool {
+        !self.withdrawal_fee.max_fee.is_zero()
+            || matches!(self.withdrawal_mode, WithdrawalMode::Unbonding { .. })
+    }
+
+    // address whose position is changed by cw20 hook: router acts on behalf of
+    // 'recipient', anyone else on behalf of itself
+    pub fn position_owner<'a>(&self, cw20_sender: &'a Addr, recipient: &'a Addr) -> &'a Addr {
+        match &self.router {
+            Some(router) if router == cw20_sender => recipient,
+            _ => cw20_sender,
+        }
+    }
+}
+
+// primary vault lives in Config, additional vaults are keyed by nAsset token.
+// withdrawal fee and unbonding apply to primary vault only
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Vault {
+    pub nasset_token: Addr,
+    pub auto_nasset_token: Addr,
+    pub psi_to_nasset_pair: Addr,
+    pub nasset_token_rewards: Addr,
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
 }
 
+// Config stored by versions before config update timelock, deposit caps,
+// withdrawal fee and unbonding were added
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct GovernanceUpdateState {
-    pub new_governance_contract_addr: Addr,
-    pub wait_approve_until: u64,
+pub struct LegacyConfig {
+    pub nasset_token: Addr,
+    pub auto_nasset_token: Addr,
+    pub psi_token: Addr,
+    pub psi_to_nasset_pair: Addr,
+    pub governance_contract: Addr,
+    pub nasset_token_rewards: Addr,
+}
+
+impl LegacyConfig {
+    // keeps behaviour of legacy version: no caps, no withdrawal fee, instant withdrawals
+    pub fn migrate(self, config_update_delay: u64) -> Config {
+        Config {
+            nasset_token: self.nasset_token,
+            auto_nasset_token: self.auto_nasset_token,
+            psi_token: self.psi_token,
+            psi_to_nasset_pair: self.psi_to_nasset_pair,
+            governance_contract: self.governance_contract,
+            nasset_token_rewards: self.nasset_token_rewards,
+            config_update_delay,
+            max_total_nasset: Uint128::MAX,
+            max_nasset_per_user: None,
+            withdrawal_fee: WithdrawalFee::none(),
+            withdrawal_mode: WithdrawalMode::Instant {},
+            router: None,
+            paused: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawalFee {
+    //fee right after deposit, decays linearly to zero over 'decay_period' seconds
+    pub max_fee: Decimal,
+    pub decay_period: u64,
+    //fee stays in the vault (goes to remaining holders) when treasury is not set
+    pub treasury: Option<Addr>,
+}
+
+impl WithdrawalFee {
+    pub fn none() -> Self {
+        WithdrawalFee {
+            max_fee: Decimal::zero(),
+            decay_period: 0,
+            treasury: None,
+        }
+    }
+}
+
+// all timelocked governance changes, only one update can be queued at a time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ConfigUpdate {
+    pub psi_token: Option<Addr>,
+    pub psi_to_nasset_pair: Option<Addr>,
+    pub nasset_token_rewards: Option<Addr>,
+    pub config_update_delay: Option<u64>,
+    pub withdrawal_fee: Option<WithdrawalFee>,
+    pub withdrawal_mode: Option<WithdrawalMode>,
+    pub deposit_caps: Option<DepositCaps>,
+    pub token_query_mode: Option<TokenQueryModeUpdate>,
+    pub new_vault: Option<NewVault>,
+    pub router: Option<RouterUpdate>,
+    pub execute_after: u64,
+}
+
+impl ConfigUpdate {
+    // see 'Config::has_primary_only_features'
+    pub fn enables_primary_only_features(&self) -> bool {
+        let enables_fee = matches!(&self.withdrawal_fee, Some(fee) if !fee.max_fee.is_zero());
+        let enables_unbonding =
+            matches!(self.withdrawal_mode, Some(WithdrawalMode::Unbonding { .. }));
+        enables_fee || enables_unbonding
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouterUpdate {
+    pub router: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TokenQueryModeUpdate {
+    pub token: Addr,
+    pub mode: TokenQueryMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NewVault {
+    // 'auto_nasset_token' is empty until anAsset token is instantiated
+    pub vault: Vault,
+    pub cw20_token_code_id: u64,
+    pub collateral_token_symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Unbonding {
+    pub auto_nasset_amount: Uint128,
+    pub release_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UserState {
+    pub last_deposit_time: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct WithdrawAction {
+    pub nasset_token: Addr,
+    //anAsset owner
+    pub sender: Addr,
+    //nAsset recipient
     pub farmer: Addr,
     pub auto_nasset_amount: Uint128,
 }
 
+// tracks PSI and nAsset balances through claim -> sell chain
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompoundState {
+    pub nasset_token: Addr,
+    pub sender: Addr,
+    pub psi_balance_before_claim: Uint128,
+    pub psi_claimed: Uint128,
+    pub psi_sold: Uint128,
+    pub nasset_balance_before_swap: Uint128,
+}
+
+// lifetime totals
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Stats {
+    pub psi_claimed: Uint128,
+    pub psi_swapped: Uint128,
+    pub nasset_acquired: Uint128,
+    pub compounds_count: u64,
+    //missing in stats stored before it was added
+    #[serde(default)]
+    pub last_compound_time: u64,
+    pub withdrawal_fees: Uint128,
+    pub nasset_deposited: Uint128,
+    pub nasset_withdrawn: Uint128,
+}
+
 static KEY_CONFIG: Item<Config> = Item::new("config");
+static KEY_LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
 static KEY_WITHDRAW_ACTION: Item<Option<WithdrawAction>> = Item::new("withdraw_action");
-static USERS_SHARE: Map<&Addr, Uint128> = Map::new("shares");
+static KEY_COMPOUND_STATE: Item<CompoundState> = Item::new("compound_state");
+static KEY_STATS: Item<Stats> = Item::new("stats");
+// nAsset -> stats of additional vault
+static VAULT_STATS: Map<&Addr, Stats> = Map::new("vault_stats");
+// nAsset -> additional vault
+static VAULTS: Map<&Addr, Vault> = Map::new("vaults");
+// anAsset -> nAsset of additional vault
+static VAULT_SHARES: Map<&Addr, Addr> = Map::new("vault_shares");
+// vault waiting for anAsset token to be instantiated
+static KEY_PENDING_VAULT: Item<Vault> = Item::new("pending_vault");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+static USERS_STATE: Map<&Addr, UserState> = Map::new("users");
+static UNBONDINGS: Map<&Addr, Vec<Unbonding>> = Map::new("unbondings");
 
-static KEY_GOVERNANCE_UPDATE: Item<GovernanceUpdateState> = Item::new("gov_update");
+static KEY_CONFIG_UPDATE: Item<ConfigUpdate> = Item::new("config_update");
+// cw20 token -> how to query its balances and supply, smart queries by default
+static TOKEN_QUERY_MODES: Map<&Addr, TokenQueryMode> = Map::new("token_query_modes");
 
 pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
     KEY_CONFIG.load(storage)
@@ -39,6 +252,10 @@ pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()>
     KEY_CONFIG.save(storage, config)
 }
 
+pub fn load_legacy_config(storage: &dyn Storage) -> StdResult<LegacyConfig> {
+    KEY_LEGACY_CONFIG.load(storage)
+}
+
 pub fn load_withdraw_action(storage: &dyn Storage) -> StdResult<Option<WithdrawAction>> {
     KEY_WITHDRAW_ACTION.load(storage)
 }
@@ -61,6 +278,137 @@ pub fn remove_withdraw_action(storage: &mut dyn Storage) -> StdResult<()> {
     KEY_WITHDRAW_ACTION.save(storage, &None)
 }
 
+pub fn may_load_compound_state(storage: &dyn Storage) -> StdResult<Option<CompoundState>> {
+    KEY_COMPOUND_STATE.may_load(storage)
+}
+
+pub fn store_compound_state(
+    storage: &mut dyn Storage,
+    compound_state: &CompoundState,
+) -> StdResult<()> {
+    KEY_COMPOUND_STATE.save(storage, compound_state)
+}
+
+pub fn remove_compound_state(storage: &mut dyn Storage) {
+    KEY_COMPOUND_STATE.remove(storage)
+}
+
+// stats are empty for contracts deployed before they were introduced
+pub fn load_stats(storage: &dyn Storage, nasset_token: &Addr) -> StdResult<Stats> {
+    let stats = if is_primary_nasset(storage, nasset_token)? {
+        KEY_STATS.may_load(storage)?
+    } else {
+        VAULT_STATS.may_load(storage, nasset_token)?
+    };
+    Ok(stats.unwrap_or_default())
+}
+
+pub fn update_stats<A>(
+    storage: &mut dyn Storage,
+    nasset_token: &Addr,
+    action: A,
+) -> StdResult<Stats>
+where
+    A: FnOnce(&mut Stats),
+{
+    let mut stats = load_stats(storage, nasset_token)?;
+    action(&mut stats);
+    if is_primary_nasset(storage, nasset_token)? {
+        KEY_STATS.save(storage, &stats)?;
+    } else {
+        VAULT_STATS.save(storage, nasset_token, &stats)?;
+    }
+    Ok(stats)
+}
+
+fn is_primary_nasset(storage: &dyn Storage, nasset_token: &Addr) -> StdResult<bool> {
+    Ok(&load_config(storage)?.nasset_token == nasset_token)
+}
+
+pub fn load_token_query_mode(storage: &dyn Storage, token: &Addr) -> StdResult<TokenQueryMode> {
+    Ok(TOKEN_QUERY_MODES
+        .may_load(storage, token)?
+        .unwrap_or(TokenQueryMode::Smart {}))
+}
+
+pub fn store_token_query_mode(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    mode: &TokenQueryMode,
+) -> StdResult<()> {
+    match mode {
+        TokenQueryMode::Smart {} => {
+            TOKEN_QUERY_MODES.remove(storage, token);
+            Ok(())
+        }
+        TokenQueryMode::Raw { .. } => TOKEN_QUERY_MODES.save(storage, token, mode),
+    }
+}
+
+pub fn load_vault(storage: &dyn Storage, nasset_token: &Addr) -> StdResult<Vault> {
+    may_load_vault(storage, nasset_token)?
+        .ok_or_else(|| StdError::generic_err(format!("no vault for nAsset {}", nasset_token)))
+}
+
+/// Primary or additional vault by nAsset token
+pub fn may_load_vault(storage: &dyn Storage, nasset_token: &Addr) -> StdResult<Option<Vault>> {
+    let config = load_config(storage)?;
+    if &config.nasset_token == nasset_token {
+        return Ok(Some(config.primary_vault()));
+    }
+
+    VAULTS.may_load(storage, nasset_token)
+}
+
+/// Primary or additional vault by anAsset token
+pub fn may_load_vault_by_auto_nasset(
+    storage: &dyn Storage,
+    auto_nasset_token: &Addr,
+) -> StdResult<Option<Vault>> {
+    let config = load_config(storage)?;
+    if &config.auto_nasset_token == auto_nasset_token {
+        return Ok(Some(config.primary_vault()));
+    }
+
+    match VAULT_SHARES.may_load(storage, auto_nasset_token)? {
+        Some(nasset_token) => VAULTS.may_load(storage, &nasset_token),
+        None => Ok(None),
+    }
+}
+
+pub fn store_vault(storage: &mut dyn Storage, vault: &Vault) -> StdResult<()> {
+    VAULT_SHARES.save(storage, &vault.auto_nasset_token, &vault.nasset_token)?;
+    VAULTS.save(storage, &vault.nasset_token, vault)
+}
+
+/// Additional vaults ordered by nAsset address
+pub fn load_vaults(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Vault>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    VAULTS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, vault)| vault))
+        .collect()
+}
+
+pub fn may_load_pending_vault(storage: &dyn Storage) -> StdResult<Option<Vault>> {
+    KEY_PENDING_VAULT.may_load(storage)
+}
+
+pub fn store_pending_vault(storage: &mut dyn Storage, vault: &Vault) -> StdResult<()> {
+    KEY_PENDING_VAULT.save(storage, vault)
+}
+
+pub fn remove_pending_vault(storage: &mut dyn Storage) {
+    KEY_PENDING_VAULT.remove(storage)
+}
+
 pub fn config_set_nasset_token(storage: &mut dyn Storage, nasset_token: Addr) -> StdResult<Config> {
     KEY_CONFIG.update(storage, |mut config: Config| -> StdResult<_> {
         config.nasset_token = nasset_token;
@@ -78,29 +426,48 @@ pub fn set_auto_nasset_token_addr(
     })
 }
 
-pub fn may_load_bank(storage: &dyn Storage, addr: &Addr) -> StdResult<Option<Uint128>> {
-    USERS_SHARE.may_load(storage, addr)
+pub fn may_load_user_state(storage: &dyn Storage, addr: &Addr) -> StdResult<Option<UserState>> {
+    USERS_STATE.may_load(storage, addr)
 }
 
-pub fn load_bank(storage: &dyn Storage, addr: &Addr) -> StdResult<Uint128> {
-    may_load_bank(storage, addr).map(|res| res.unwrap_or_default())
+pub fn store_user_state(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    user_state: &UserState,
+) -> StdResult<()> {
+    USERS_STATE.save(storage, addr, user_state)
+}
+
+pub fn load_unbondings(storage: &dyn Storage, addr: &Addr) -> StdResult<Vec<Unbonding>> {
+    UNBONDINGS
+        .may_load(storage, addr)
+        .map(|res| res.unwrap_or_default())
 }
 
-pub fn store_bank(storage: &mut dyn Storage, addr: &Addr, share: &Uint128) -> StdResult<()> {
-    USERS_SHARE.save(storage, addr, share)
+pub fn store_unbondings(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    unbondings: &[Unbonding],
+) -> StdResult<()> {
+    if unbondings.is_empty() {
+        UNBONDINGS.remove(storage, addr);
+        Ok(())
+    } else {
+        UNBONDINGS.save(storage, addr, &unbondings.to_vec())
+    }
 }
 
-pub fn load_gov_update(storage: &dyn Storage) -> StdResult<GovernanceUpdateState> {
-    KEY_GOVERNANCE_UPDATE.load(storage)
+pub fn may_load_config_update(storage: &dyn Storage) -> StdResult<Option<ConfigUpdate>> {
+    KEY_CONFIG_UPDATE.may_load(storage)
 }
 
-pub fn store_gov_update(
+pub fn store_config_update(
     storage: &mut dyn Storage,
-    gov_update: &GovernanceUpdateState,
+    config_update: &ConfigUpdate,
 ) -> StdResult<()> {
-    KEY_GOVERNANCE_UPDATE.save(storage, gov_update)
+    KEY_CONFIG_UPDATE.save(storage, config_update)
 }
 
-pub fn remove_gov_update(storage: &mut dyn Storage) {
-    KEY_GOVERNANCE_UPDATE.remove(storage)
+pub fn remove_config_update(storage: &mut dyn Storage) {
+    KEY_CONFIG_UPDATE.remove(storage)
 }
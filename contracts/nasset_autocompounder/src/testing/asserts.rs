@@ -0,0 +1,112 @@
+use super::sdk::{
+    AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR, NASSET_TOKEN_REWARDS_ADDR, PSI_TOKEN_ADDR,
+    PSI_TO_NASSET_PAIR_ADDR,
+};
+use crate::msg::{AstroportCw20HookMsg, NAssetTokenRewardsAnyoneMsg, NAssetTokenRewardsExecuteMsg};
+use crate::SubmsgIds;
+use cosmwasm_std::{to_binary, CosmosMsg, ReplyOn, Response, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+/// PSI claim which starts compound ('ReplyOn::Success') or withdraw ('ReplyOn::Always')
+pub fn claim_psi_msg(nasset_token_rewards: &str, reply_on: ReplyOn) -> SubMsg {
+    SubMsg {
+        id: SubmsgIds::PsiClaimed.id(),
+        msg: CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: nasset_token_rewards.to_string(),
+            msg: to_binary(&NAssetTokenRewardsExecuteMsg::Anyone {
+                anyone_msg: NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient: None },
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        gas_limit: None,
+        reply_on,
+    }
+}
+
+pub fn sell_psi_msg(psi_token: &str, psi_to_nasset_pair: &str, psi_amount: Uint128) -> SubMsg {
+    SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: psi_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                amount: psi_amount,
+                contract: psi_to_nasset_pair.to_string(),
+                msg: to_binary(&AstroportCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: None,
+                    to: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        SubmsgIds::PsiSold.id(),
+    )
+}
+
+// assertions below use 'Sdk' addresses
+
+/// Response contains only PSI claim
+pub fn assert_claim_psi(response: &Response, reply_on: ReplyOn) {
+    assert_eq!(
+        response.messages,
+        vec![claim_psi_msg(NASSET_TOKEN_REWARDS_ADDR, reply_on)]
+    );
+}
+
+/// Response contains only PSI sell
+pub fn assert_sell_psi(response: &Response, psi_amount: Uint128) {
+    assert_eq!(
+        response.messages,
+        vec![sell_psi_msg(
+            PSI_TOKEN_ADDR,
+            PSI_TO_NASSET_PAIR_ADDR,
+            psi_amount
+        )]
+    );
+}
+
+pub fn assert_nasset_sent(response: &Response, recipient: &str, amount: Uint128) {
+    assert_contains_cw20_msg(
+        response,
+        NASSET_TOKEN_ADDR,
+        Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        },
+    );
+}
+
+pub fn assert_auto_nasset_minted(response: &Response, recipient: &str, amount: Uint128) {
+    assert_contains_cw20_msg(
+        response,
+        AUTO_NASSET_TOKEN_ADDR,
+        Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        },
+    );
+}
+
+pub fn assert_auto_nasset_burned(response: &Response, amount: Uint128) {
+    assert_contains_cw20_msg(
+        response,
+        AUTO_NASSET_TOKEN_ADDR,
+        Cw20ExecuteMsg::Burn { amount },
+    );
+}
+
+fn assert_contains_cw20_msg(response: &Response, token: &str, msg: Cw20ExecuteMsg) {
+    let expected = SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    }));
+    assert!(
+        response.messages.contains(&expected),
+        "{:?} not found in {:?}",
+        msg,
+        response.messages
+    );
+}
@@ -0,0 +1,299 @@
+use crate::msg::{
+    AstroportQueryMsg, AstroportSimulationResponse, NAssetTokenRewardsAccruedRewardsResponse,
+    NAssetTokenRewardsQueryMsg,
+};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cosmwasm_storage::to_length_prefixed;
+use std::collections::HashMap;
+use std::hash::Hash;
+use terra_cosmwasm::TerraQueryWrapper;
+
+use cw20::{BalanceResponse, Cw20QueryMsg, MinterResponse, TokenInfoResponse};
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_querier: TokenQuerier,
+    rewards_querier: RewardsQuerier,
+    pair_querier: PairQuerier,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Raw { contract_addr, key }) => {
+                let key: &[u8] = key.as_slice();
+
+                let prefix_token_info = b"token_info";
+                let prefix_token_info_legacy = to_length_prefixed(b"token_info");
+                let prefix_balance = to_length_prefixed(b"balance");
+
+                if key.to_vec() == prefix_token_info || key.to_vec() == prefix_token_info_legacy {
+                    let token_supply = match self.token_querier.supplies.get(contract_addr) {
+                        Some(supply) => supply,
+                        None => {
+                            return SystemResult::Err(SystemError::InvalidRequest {
+                                error: format!(
+                                    "No supply info exists for the contract {}",
+                                    contract_addr
+                                ),
+                                request: key.into(),
+                            })
+                        }
+                    };
+
+                    SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                        name: "some_token_name".to_string(),
+                        symbol: "some_token_symbol".to_string(),
+                        decimals: 6,
+                        total_supply: *token_supply,
+                    })))
+                } else if key.starts_with(&prefix_balance) {
+                    let key_address: &[u8] = &key[prefix_balance.len()..];
+                    let address: Addr = Addr::unchecked(std::str::from_utf8(key_address).unwrap());
+
+                    let balances: &HashMap<String, Uint128> =
+                        match self.token_querier.balances.get(contract_addr) {
+                            Some(balances) => balances,
+                            None => {
+                                return SystemResult::Err(SystemError::InvalidRequest {
+                                    error: format!(
+                                        "No balance info exists for the contract {}",
+                                        contract_addr
+                                    ),
+                                    request: key.into(),
+                                })
+                            }
+                        };
+
+                    let balance = match balances.get(&address.to_string()) {
+                        Some(v) => v,
+                        None => {
+                            return SystemResult::Err(SystemError::InvalidRequest {
+                                error: "Balance not found".to_string(),
+                                request: key.into(),
+                            })
+                        }
+                    };
+
+                    SystemResult::Ok(ContractResult::from(to_binary(&balance)))
+                } else {
+                    SystemResult::Err(SystemError::InvalidRequest {
+                        error: format!("Unknown raw query to {}", contract_addr),
+                        request: key.into(),
+                    })
+                }
+            }
+
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Ok(cw20_query) = from_binary::<Cw20QueryMsg>(msg) {
+                    return self.token_querier.handle_query(contract_addr, cw20_query);
+                }
+
+                if let Ok(NAssetTokenRewardsQueryMsg::AccruedRewards { .. }) = from_binary(msg) {
+                    return SystemResult::Ok(ContractResult::from(to_binary(
+                        &NAssetTokenRewardsAccruedRewardsResponse {
+                            rewards: self.rewards_querier.accrued_rewards(contract_addr),
+                        },
+                    )));
+                }
+
+                if let Ok(AstroportQueryMsg::Simulation { offer_asset }) = from_binary(msg) {
+                    return SystemResult::Ok(ContractResult::from(to_binary(
+                        &AstroportSimulationResponse {
+                            return_amount: offer_asset.amount
+                                * self.pair_querier.price(contract_addr),
+                            spread_amount: Uint128::zero(),
+                            commission_amount: Uint128::zero(),
+                        },
+                    )));
+                }
+
+                SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Unknown query to {}", contract_addr),
+                    request: msg.clone(),
+                })
+            }
+
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_querier.balances = array_to_hashmap(balances);
+    }
+
+    pub fn with_token_supplies(&mut self, supplies: HashMap<String, Uint128>) {
+        self.token_querier.supplies = supplies;
+    }
+
+    pub fn with_token_minter(&mut self, token: &str, minter: &str) {
+        self.token_querier
+            .minters
+            .insert(token.to_string(), minter.to_string());
+    }
+
+    pub fn with_accrued_rewards(&mut self, accrued_rewards: Uint128) {
+        self.rewards_querier.accrued_rewards = accrued_rewards;
+    }
+
+    pub fn with_vault_accrued_rewards(&mut self, rewards_contract: &str, accrued_rewards: Uint128) {
+        self.rewards_querier
+            .vault_accrued_rewards
+            .insert(rewards_contract.to_string(), accrued_rewards);
+    }
+
+    // nAsset amount for one PSI
+    pub fn with_psi_to_nasset_price(&mut self, price: Decimal) {
+        self.pair_querier.price = price;
+    }
+
+    pub fn with_vault_psi_to_nasset_price(&mut self, pair: &str, price: Decimal) {
+        self.pair_querier
+            .vault_prices
+            .insert(pair.to_string(), price);
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_querier: TokenQuerier::default(),
+            rewards_querier: RewardsQuerier::default(),
+            pair_querier: PairQuerier::default(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct TokenQuerier {
+    // this lets us iterate over all pairs that match the first string
+    balances: HashMap<String, HashMap<String, Uint128>>,
+    supplies: HashMap<String, Uint128>,
+    minters: HashMap<String, String>,
+}
+
+impl TokenQuerier {
+    fn handle_query(&self, contract_addr: &str, msg: Cw20QueryMsg) -> QuerierResult {
+        match msg {
+            Cw20QueryMsg::TokenInfo {} => match self.supplies.get(contract_addr) {
+                Some(supply) => {
+                    SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                        name: "some_token_name".to_string(),
+                        symbol: "some_token_symbol".to_string(),
+                        decimals: 6,
+                        total_supply: *supply,
+                    })))
+                }
+                None => SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.to_string(),
+                }),
+            },
+            // cw20 returns zero for unknown accounts
+            Cw20QueryMsg::Balance { address } => {
+                let balance = self
+                    .balances
+                    .get(contract_addr)
+                    .and_then(|balances| balances.get(&address))
+                    .cloned()
+                    .unwrap_or_default();
+                SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                    balance,
+                })))
+            }
+            // no minter is set for unknown tokens
+            Cw20QueryMsg::Minter {} => {
+                let minter = self
+                    .minters
+                    .get(contract_addr)
+                    .map(|minter| MinterResponse {
+                        minter: minter.clone(),
+                        cap: None,
+                    });
+                SystemResult::Ok(ContractResult::from(to_binary(&minter)))
+            }
+            msg => SystemResult::Err(SystemError::InvalidRequest {
+                error: format!("Unsupported cw20 query to {}", contract_addr),
+                request: to_binary(&msg).unwrap(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct RewardsQuerier {
+    accrued_rewards: Uint128,
+    //rewards contracts of additional vaults
+    vault_accrued_rewards: HashMap<String, Uint128>,
+}
+
+impl RewardsQuerier {
+    fn accrued_rewards(&self, rewards_contract: &str) -> Uint128 {
+        self.vault_accrued_rewards
+            .get(rewards_contract)
+            .copied()
+            .unwrap_or(self.accrued_rewards)
+    }
+}
+
+#[derive(Clone, Default)]
+struct PairQuerier {
+    price: Decimal,
+    //pairs of additional vaults
+    vault_prices: HashMap<String, Decimal>,
+}
+
+impl PairQuerier {
+    fn price(&self, pair: &str) -> Decimal {
+        self.vault_prices.get(pair).copied().unwrap_or(self.price)
+    }
+}
+
+fn array_to_hashmap<K, V>(balances: &[(&String, &[(&K, &V)])]) -> HashMap<String, HashMap<K, V>>
+where
+    V: Clone,
+    K: Clone + Eq + Hash,
+{
+    let mut result_map: HashMap<String, HashMap<K, V>> = HashMap::new();
+    for (contract_addr, map_values) in balances.iter() {
+        let mut contract_balances_map: HashMap<K, V> = HashMap::new();
+        for (key, value) in map_values.iter() {
+            contract_balances_map.insert((**key).clone(), (**value).clone());
+        }
+
+        result_map.insert(contract_addr.to_string(), contract_balances_map);
+    }
+    result_map
+}
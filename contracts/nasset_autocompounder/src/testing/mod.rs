@@ -0,0 +1,17 @@
+//! Unit test harness for contracts which deposit into the autocompounder.
+//! 'Sdk' calls contract entry points with mocked nAsset, PSI, anAsset, rewards and pair,
+//! replies are simulated by hand. Enabled with 'testing' feature.
+mod asserts;
+mod mock_querier;
+mod sdk;
+
+pub use asserts::{
+    assert_auto_nasset_burned, assert_auto_nasset_minted, assert_claim_psi, assert_nasset_sent,
+    assert_sell_psi, claim_psi_msg, sell_psi_msg,
+};
+pub use mock_querier::{mock_dependencies, WasmMockQuerier};
+pub use sdk::{
+    Sdk, SdkBuilder, AUTO_NASSET_TOKEN_ADDR, COLLATERAL_TOKEN_SYMBOL, CONFIG_UPDATE_DELAY,
+    CW20_TOKEN_CODE_ID, GOVERNANCE_CONTRACT_ADDR, MAX_TOTAL_NASSET, NASSET_TOKEN_ADDR,
+    NASSET_TOKEN_REWARDS_ADDR, PSI_TOKEN_ADDR, PSI_TO_NASSET_PAIR_ADDR,
+};
@@ -0,0 +1,637 @@
+use crate::{
+    msg::{Cw20HookMsg, ExecuteMsg, GovernanceMsg, InstantiateMsg, ShareTokenMode},
+    state::{load_config, load_withdraw_action, Config},
+    SubmsgIds,
+};
+
+use super::asserts::{assert_claim_psi, assert_sell_psi};
+use super::mock_querier::{mock_dependencies, WasmMockQuerier};
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    to_binary, Api, ContractResult, Decimal, Empty, Env, OwnedDeps, Querier, Reply, ReplyOn,
+    Response, StdResult, Storage, SubMsg, SubMsgExecutionResponse, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use nasset_autocompounder_interface::reply_response::MsgInstantiateContractResponse;
+use std::collections::HashMap;
+
+pub const NASSET_TOKEN_ADDR: &str = "addr0001";
+pub const PSI_TOKEN_ADDR: &str = "addr0002";
+pub const PSI_TO_NASSET_PAIR_ADDR: &str = "addr0003";
+pub const GOVERNANCE_CONTRACT_ADDR: &str = "addr0004";
+pub const CW20_TOKEN_CODE_ID: u64 = 256;
+pub const NASSET_TOKEN_REWARDS_ADDR: &str = "addr0005";
+pub const COLLATERAL_TOKEN_SYMBOL: &str = "AVAX";
+pub const AUTO_NASSET_TOKEN_ADDR: &str = "addr0006";
+pub const CONFIG_UPDATE_DELAY: u64 = 86_400;
+pub const MAX_TOTAL_NASSET: u128 = 1_000_000_000_000_000;
+
+pub struct Sdk {
+    pub deps: OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    pub env: Env,
+    nasset_balance: Uint128,
+    psi_balance: Uint128,
+    auto_nasset_supply: Uint128,
+    auto_nasset_balances: HashMap<String, Uint128>,
+    //contract balances and supplies of additional vaults tokens
+    vault_balances: HashMap<String, Uint128>,
+    vault_supplies: HashMap<String, Uint128>,
+}
+
+/// Builds instantiated 'Sdk' with initial token balances, pending PSI and pair price.
+pub struct SdkBuilder {
+    instantiate_msg: InstantiateMsg,
+    nasset_balance: Uint128,
+    psi_balance: Uint128,
+    auto_nasset_supply: Uint128,
+    auto_nasset_balances: Vec<(String, Uint128)>,
+    accrued_rewards: Uint128,
+    psi_to_nasset_price: Decimal,
+}
+
+impl Default for SdkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SdkBuilder {
+    pub fn new() -> Self {
+        SdkBuilder {
+            instantiate_msg: Sdk::instantiate_msg(),
+            nasset_balance: Uint128::zero(),
+            psi_balance: Uint128::zero(),
+            auto_nasset_supply: Uint128::zero(),
+            auto_nasset_balances: vec![],
+            accrued_rewards: Uint128::zero(),
+            psi_to_nasset_price: Decimal::zero(),
+        }
+    }
+
+    //'Sdk::instantiate_msg' by default
+    pub fn instantiate_msg(mut self, instantiate_msg: InstantiateMsg) -> Self {
+        self.instantiate_msg = instantiate_msg;
+        self
+    }
+
+    pub fn share_token_mode(mut self, share_token_mode: ShareTokenMode) -> Self {
+        self.instantiate_msg.share_token_mode = Some(share_token_mode);
+        self
+    }
+
+    //autocompounder nAsset balance
+    pub fn nasset_balance(mut self, amount: Uint128) -> Self {
+        self.nasset_balance = amount;
+        self
+    }
+
+    //autocompounder PSI balance
+    pub fn psi_balance(mut self, amount: Uint128) -> Self {
+        self.psi_balance = amount;
+        self
+    }
+
+    pub fn auto_nasset_supply(mut self, amount: Uint128) -> Self {
+        self.auto_nasset_supply = amount;
+        self
+    }
+
+    pub fn auto_nasset_balance(mut self, address: &str, amount: Uint128) -> Self {
+        self.auto_nasset_balances
+            .push((address.to_string(), amount));
+        self
+    }
+
+    //PSI which autocompounder can claim from nAsset rewards contract
+    pub fn accrued_rewards(mut self, amount: Uint128) -> Self {
+        self.accrued_rewards = amount;
+        self
+    }
+
+    //nAsset amount for one PSI
+    pub fn psi_to_nasset_price(mut self, price: Decimal) -> Self {
+        self.psi_to_nasset_price = price;
+        self
+    }
+
+    pub fn build(self) -> Sdk {
+        let mut deps = mock_dependencies(&[]);
+        let share_token_mode = self.instantiate_msg.share_token_mode.clone();
+        let is_embedded = share_token_mode == Some(ShareTokenMode::Embedded {});
+        match share_token_mode {
+            Some(ShareTokenMode::External {}) | None => {
+                Sdk::instantiate_nasset_autocompounder(&mut deps, self.instantiate_msg);
+            }
+            // anAsset is embedded or minted by autocompounder already, there is no instantiate reply
+            Some(ShareTokenMode::Embedded {}) | Some(ShareTokenMode::Existing { .. }) => {
+                deps.querier
+                    .with_token_minter(AUTO_NASSET_TOKEN_ADDR, MOCK_CONTRACT_ADDR);
+                crate::contract::instantiate(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("addr9999", &[]),
+                    self.instantiate_msg,
+                )
+                .unwrap();
+            }
+        }
+
+        let mut sdk = Sdk::new(deps);
+        sdk.set_nasset_balance(self.nasset_balance.into());
+        sdk.set_psi_balance(self.psi_balance.into());
+        if !is_embedded {
+            sdk.set_auto_nasset_supply(self.auto_nasset_supply.into());
+            for (address, amount) in self.auto_nasset_balances {
+                sdk.set_auto_nasset_balance(&address, amount.into());
+            }
+        }
+        sdk.set_accrued_rewards(self.accrued_rewards.into());
+        sdk.set_psi_to_nasset_price(self.psi_to_nasset_price);
+        sdk
+    }
+}
+
+impl Sdk {
+    pub fn builder() -> SdkBuilder {
+        SdkBuilder::new()
+    }
+
+    pub fn init() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn init_with_embedded_share_token() -> Self {
+        Self::builder()
+            .share_token_mode(ShareTokenMode::Embedded {})
+            .build()
+    }
+
+    // previously deployed anAsset at 'AUTO_NASSET_TOKEN_ADDR', minted by autocompounder
+    pub fn init_with_existing_share_token() -> Self {
+        Self::builder()
+            .share_token_mode(ShareTokenMode::Existing {
+                token_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+            })
+            .build()
+    }
+
+    pub fn instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            nasset_token_addr: NASSET_TOKEN_ADDR.to_string(),
+            psi_token_addr: PSI_TOKEN_ADDR.to_string(),
+            psi_to_nasset_pair_addr: PSI_TO_NASSET_PAIR_ADDR.to_string(),
+            governance_contract_addr: GOVERNANCE_CONTRACT_ADDR.to_string(),
+            cw20_token_code_id: CW20_TOKEN_CODE_ID,
+            nasset_token_rewards_addr: NASSET_TOKEN_REWARDS_ADDR.to_string(),
+            collateral_token_symbol: COLLATERAL_TOKEN_SYMBOL.to_string(),
+            config_update_delay: CONFIG_UPDATE_DELAY,
+            max_total_nasset: Uint128::from(MAX_TOTAL_NASSET),
+            max_nasset_per_user: None,
+            withdrawal_fee: None,
+            withdrawal_mode: None,
+            share_token_mode: None,
+        }
+    }
+
+    fn new(deps: OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> Self {
+        Sdk {
+            deps,
+            env: mock_env(),
+            nasset_balance: Uint128::zero(),
+            auto_nasset_supply: Uint128::zero(),
+            psi_balance: Uint128::zero(),
+            auto_nasset_balances: HashMap::new(),
+            vault_balances: HashMap::new(),
+            vault_supplies: HashMap::new(),
+        }
+    }
+
+    pub fn instantiate_nasset_autocompounder<A: Storage, B: Api, C: Querier>(
+        deps: &mut OwnedDeps<A, B, C>,
+        init_msg: InstantiateMsg,
+    ) {
+        let info = mock_info("addr9999", &[]);
+        crate::contract::instantiate(deps.as_mut(), mock_env(), info, init_msg.clone()).unwrap();
+
+        // it worked, let's query the state
+        let config: Config = load_config(&deps.storage).unwrap();
+        assert_eq!(
+            init_msg.governance_contract_addr,
+            config.governance_contract.to_string()
+        );
+        assert_eq!(init_msg.nasset_token_addr, config.nasset_token.to_string());
+        assert_eq!(config.auto_nasset_token.to_string(), "");
+        assert_eq!(init_msg.psi_token_addr, config.psi_token.to_string());
+        assert_eq!(
+            init_msg.psi_to_nasset_pair_addr,
+            config.psi_to_nasset_pair.to_string()
+        );
+        assert_eq!(
+            init_msg.nasset_token_rewards_addr,
+            config.nasset_token_rewards.to_string()
+        );
+        assert_eq!(
+            init_msg.nasset_token_rewards_addr,
+            config.nasset_token_rewards.to_string()
+        );
+
+        let withdraw_action = load_withdraw_action(&deps.storage).unwrap();
+        assert!(withdraw_action.is_none());
+
+        // ==========================================================
+        // ================ Init AUTO_NASSET_TOKEN ==================
+        // ==========================================================
+
+        {
+            let res = crate::contract::reply(
+                deps.as_mut(),
+                mock_env(),
+                Self::auto_nasset_token_instantiated_reply(AUTO_NASSET_TOKEN_ADDR),
+            )
+            .unwrap();
+
+            assert_eq!(
+                res.attributes,
+                vec![
+                    ("action", "auto_nasset_token_initialized"),
+                    ("auto_nasset_token_addr", AUTO_NASSET_TOKEN_ADDR),
+                ]
+            );
+
+            let config: Config = load_config(&deps.storage).unwrap();
+            assert_eq!(config.auto_nasset_token.to_string(), AUTO_NASSET_TOKEN_ADDR);
+        }
+    }
+
+    pub fn user_deposit(&mut self, address: &str, amount: Uint128) -> StdResult<Response<Empty>> {
+        self.user_deposit_to(address, None, amount)
+    }
+
+    pub fn user_deposit_to(
+        &mut self,
+        address: &str,
+        recipient: Option<String>,
+        amount: Uint128,
+    ) -> StdResult<Response<Empty>> {
+        let cw20_deposit_msg = Cw20ReceiveMsg {
+            sender: address.to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Deposit { recipient }).unwrap(),
+        };
+
+        let info = mock_info(NASSET_TOKEN_ADDR, &[]);
+        crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            info,
+            ExecuteMsg::Receive(cw20_deposit_msg),
+        )
+    }
+
+    pub fn user_withdraw(
+        &mut self,
+        address: &str,
+        amount: Uint128,
+        nasset_profit: Uint256,
+    ) -> StdResult<Response<Empty>> {
+        let response = self.user_send_withdraw(address, amount).unwrap();
+        self.process_withdraw(response, nasset_profit)
+    }
+
+    pub fn user_withdraw_to(
+        &mut self,
+        address: &str,
+        recipient: &str,
+        amount: Uint128,
+        nasset_profit: Uint256,
+    ) -> StdResult<Response<Empty>> {
+        let response = self
+            .user_send_withdraw_to(address, Some(recipient.to_string()), amount)
+            .unwrap();
+        self.process_withdraw(response, nasset_profit)
+    }
+
+    pub fn user_send_withdraw(
+        &mut self,
+        address: &str,
+        amount: Uint128,
+    ) -> StdResult<Response<Empty>> {
+        self.user_send_withdraw_to(address, None, amount)
+    }
+
+    pub fn user_send_withdraw_to(
+        &mut self,
+        address: &str,
+        recipient: Option<String>,
+        amount: Uint128,
+    ) -> StdResult<Response<Empty>> {
+        let cw20_withdraw_msg = Cw20ReceiveMsg {
+            sender: address.to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Withdraw { recipient }).unwrap(),
+        };
+
+        let info = mock_info(AUTO_NASSET_TOKEN_ADDR, &[]);
+        crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            info,
+            ExecuteMsg::Receive(cw20_withdraw_msg),
+        )
+    }
+
+    // anAsset is sent to autocompounder with embedded token 'Send',
+    // which calls autocompounder 'Receive' on its own behalf
+    pub fn user_withdraw_embedded(
+        &mut self,
+        address: &str,
+        amount: Uint128,
+        nasset_profit: Uint256,
+    ) -> StdResult<Response<Empty>> {
+        let response = self.user_send_withdraw_embedded(address, amount)?;
+        self.process_withdraw(response, nasset_profit)
+    }
+
+    // response of autocompounder 'Receive', withdraw chain is not processed
+    pub fn user_send_withdraw_embedded(
+        &mut self,
+        address: &str,
+        amount: Uint128,
+    ) -> StdResult<Response<Empty>> {
+        let withdraw_msg = to_binary(&Cw20HookMsg::Withdraw { recipient: None }).unwrap();
+        let response = crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            mock_info(address, &[]),
+            ExecuteMsg::Send {
+                contract: MOCK_CONTRACT_ADDR.to_string(),
+                amount,
+                msg: withdraw_msg.clone(),
+            },
+        )?;
+
+        let cw20_withdraw_msg = Cw20ReceiveMsg {
+            sender: address.to_string(),
+            amount,
+            msg: withdraw_msg,
+        };
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(
+                cw20_withdraw_msg
+                    .clone()
+                    .into_cosmos_msg(MOCK_CONTRACT_ADDR)
+                    .unwrap()
+            )]
+        );
+
+        crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            ExecuteMsg::Receive(cw20_withdraw_msg),
+        )
+    }
+
+    pub fn user_claim(
+        &mut self,
+        address: &str,
+        nasset_profit: Uint256,
+    ) -> StdResult<Response<Empty>> {
+        let info = mock_info(address, &[]);
+        let response = crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            info,
+            ExecuteMsg::Claim {},
+        )?;
+        self.process_withdraw(response, nasset_profit)
+    }
+
+    // go through claim rewards -> sell psi -> withdraw chain
+    fn process_withdraw(
+        &mut self,
+        response: Response<Empty>,
+        nasset_profit: Uint256,
+    ) -> StdResult<Response<Empty>> {
+        //this number means nothing
+        //because we manually set nasset_profit
+        let psi_claimed = Uint256::from(256_000_000u128);
+
+        assert_claim_psi(&response, ReplyOn::Always);
+        let res = self.reply_psi_claimed(psi_claimed).unwrap();
+        assert_sell_psi(&res, psi_claimed.into());
+
+        let response = self.reply_psi_sold(nasset_profit);
+        assert!(load_withdraw_action(&self.deps.storage).unwrap().is_none());
+        response
+    }
+
+    pub fn user_withdraw_without_nasset_rewards(
+        &mut self,
+        address: &str,
+        amount: Uint128,
+    ) -> StdResult<Response<Empty>> {
+        let response = self.user_send_withdraw(address, amount).unwrap();
+        assert_claim_psi(&response, ReplyOn::Always);
+
+        self.set_psi_balance(Uint256::zero());
+        let response = self.reply_psi_claim_failed("No rewards have accrued yet");
+        assert!(load_withdraw_action(&self.deps.storage).unwrap().is_none());
+        response
+    }
+
+    pub fn user_send_compound(&mut self, nasset_profit: Uint256) -> StdResult<Response<Empty>> {
+        //this number means nothing
+        //because we manually set nasset_profit
+        let psi_claimed = Uint256::from(256_000_000u128);
+
+        let env = self.env.clone();
+        let info = mock_info("addr9999", &[]);
+        let response = crate::contract::execute(
+            self.deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::Compound {
+                nasset_token_addr: None,
+            },
+        )
+        .unwrap();
+
+        assert_claim_psi(&response, ReplyOn::Success);
+        let res = self.reply_psi_claimed(psi_claimed).unwrap();
+        assert_sell_psi(&res, psi_claimed.into());
+
+        let response = self.reply_psi_sold(nasset_profit);
+        assert!(load_withdraw_action(&self.deps.storage).unwrap().is_none());
+        response
+    }
+
+    pub fn auto_nasset_token_instantiated_reply(auto_nasset_token_addr: &str) -> Reply {
+        Reply {
+            id: SubmsgIds::InitANAsset.id(),
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: Some(MsgInstantiateContractResponse::new(auto_nasset_token_addr).to_binary()),
+            }),
+        }
+    }
+
+    // InitANAsset reply, for vaults added after instantiate
+    pub fn reply_auto_nasset_token_instantiated(
+        &mut self,
+        auto_nasset_token_addr: &str,
+    ) -> StdResult<Response<Empty>> {
+        let reply_msg = Self::auto_nasset_token_instantiated_reply(auto_nasset_token_addr);
+        crate::contract::reply(self.deps.as_mut(), self.env.clone(), reply_msg)
+    }
+
+    // PsiClaimed reply, 'psi_balance' is autocompounder PSI balance after claim
+    pub fn reply_psi_claimed(&mut self, psi_balance: Uint256) -> StdResult<Response<Empty>> {
+        self.set_psi_balance(psi_balance);
+        self.reply_ok(SubmsgIds::PsiClaimed)
+    }
+
+    // PsiClaimed reply when nAsset rewards contract fails, only withdraw replies on error
+    pub fn reply_psi_claim_failed(&mut self, error: &str) -> StdResult<Response<Empty>> {
+        let reply_msg = Reply {
+            id: SubmsgIds::PsiClaimed.id(),
+            result: ContractResult::Err(error.to_string()),
+        };
+        crate::contract::reply(self.deps.as_mut(), self.env.clone(), reply_msg)
+    }
+
+    // PsiSold reply, all PSI is sold and 'nasset_bought' is added to autocompounder balance
+    pub fn reply_psi_sold(&mut self, nasset_bought: Uint256) -> StdResult<Response<Empty>> {
+        self.set_psi_balance(Uint256::zero());
+        self.increase_nasset_balance(nasset_bought);
+        self.reply_ok(SubmsgIds::PsiSold)
+    }
+
+    fn reply_ok(&mut self, submsg_id: SubmsgIds) -> StdResult<Response<Empty>> {
+        let reply_msg = Reply {
+            id: submsg_id.id(),
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        crate::contract::reply(self.deps.as_mut(), self.env.clone(), reply_msg)
+    }
+
+    pub fn governance(&mut self, governance_msg: GovernanceMsg) -> StdResult<Response<Empty>> {
+        crate::contract::execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            mock_info(GOVERNANCE_CONTRACT_ADDR, &[]),
+            ExecuteMsg::Governance { governance_msg },
+        )
+    }
+
+    // queue timelocked governance update and execute it after 'config_update_delay'
+    pub fn governance_update(
+        &mut self,
+        governance_msg: GovernanceMsg,
+    ) -> StdResult<Response<Empty>> {
+        self.governance(governance_msg)?;
+        self.increase_block_time(CONFIG_UPDATE_DELAY);
+        self.governance(GovernanceMsg::ExecuteConfigUpdate {})
+    }
+
+    pub fn set_accrued_rewards(&mut self, value: Uint256) {
+        self.deps.querier.with_accrued_rewards(value.into());
+    }
+
+    pub fn set_psi_to_nasset_price(&mut self, price: Decimal) {
+        self.deps.querier.with_psi_to_nasset_price(price);
+    }
+
+    pub fn set_vault_accrued_rewards(&mut self, rewards_contract: &str, value: Uint256) {
+        self.deps
+            .querier
+            .with_vault_accrued_rewards(rewards_contract, value.into());
+    }
+
+    pub fn set_vault_psi_to_nasset_price(&mut self, pair: &str, price: Decimal) {
+        self.deps
+            .querier
+            .with_vault_psi_to_nasset_price(pair, price);
+    }
+
+    pub fn increase_block_time(&mut self, seconds: u64) {
+        self.env.block.time = self.env.block.time.plus_seconds(seconds);
+    }
+
+    pub fn set_auto_nasset_supply(&mut self, value: Uint256) {
+        self.auto_nasset_supply = value.into();
+        self.set_token_supplies();
+    }
+
+    pub fn set_nasset_balance(&mut self, value: Uint256) {
+        self.nasset_balance = value.into();
+        self.set_token_balances();
+    }
+
+    pub fn increase_nasset_balance(&mut self, value: Uint256) {
+        self.nasset_balance = (Uint256::from(self.nasset_balance) + value).into();
+        self.set_token_balances();
+    }
+
+    pub fn set_psi_balance(&mut self, value: Uint256) {
+        self.psi_balance = value.into();
+        self.set_token_balances();
+    }
+
+    pub fn set_vault_nasset_balance(&mut self, nasset_token: &str, value: Uint256) {
+        self.vault_balances
+            .insert(nasset_token.to_string(), value.into());
+        self.set_token_balances();
+    }
+
+    pub fn set_vault_auto_nasset_supply(&mut self, auto_nasset_token: &str, value: Uint256) {
+        self.vault_supplies
+            .insert(auto_nasset_token.to_string(), value.into());
+        self.set_token_supplies();
+    }
+
+    pub fn set_auto_nasset_balance(&mut self, address: &str, value: Uint256) {
+        self.auto_nasset_balances
+            .insert(address.to_string(), value.into());
+        self.set_token_balances();
+    }
+
+    fn set_token_supplies(&mut self) {
+        let mut supplies = self.vault_supplies.clone();
+        supplies.insert(AUTO_NASSET_TOKEN_ADDR.to_string(), self.auto_nasset_supply);
+        self.deps.querier.with_token_supplies(supplies)
+    }
+
+    fn set_token_balances(&mut self) {
+        let auto_nasset_balances: Vec<(&String, &Uint128)> =
+            self.auto_nasset_balances.iter().collect();
+        let contract_addr = MOCK_CONTRACT_ADDR.to_string();
+        let vault_balances: Vec<(&String, [(&String, &Uint128); 1])> = self
+            .vault_balances
+            .iter()
+            .map(|(token, balance)| (token, [(&contract_addr, balance)]))
+            .collect();
+
+        let nasset_token = NASSET_TOKEN_ADDR.to_string();
+        let psi_token = PSI_TOKEN_ADDR.to_string();
+        let auto_nasset_token = AUTO_NASSET_TOKEN_ADDR.to_string();
+        let nasset_balances = [(&contract_addr, &self.nasset_balance)];
+        let psi_balances = [(&contract_addr, &self.psi_balance)];
+        let mut balances: Vec<(&String, &[(&String, &Uint128)])> = vec![
+            (&nasset_token, &nasset_balances),
+            (&psi_token, &psi_balances),
+            (&auto_nasset_token, &auto_nasset_balances),
+        ];
+        balances.extend(
+            vault_balances
+                .iter()
+                .map(|(token, balances)| (*token, &balances[..])),
+        );
+        self.deps.querier.with_token_balances(&balances);
+    }
+}
@@ -1,11 +1,17 @@
 use crate::{
-    msg::{ExecuteMsg, GovernanceMsg},
-    state::load_config,
+    msg::{
+        ExecuteMsg, GovernanceMsg, PendingConfigUpdateResponse, QueryMsg, TokenQueryMode,
+        WithdrawalFeeConfig,
+    },
+    state::{load_config, may_load_config_update, WithdrawalFee},
 };
 
-use super::sdk::{Sdk, GOVERNANCE_CONTRACT_ADDR};
+use crate::testing::{
+    Sdk, CONFIG_UPDATE_DELAY, GOVERNANCE_CONTRACT_ADDR, NASSET_TOKEN_REWARDS_ADDR, PSI_TOKEN_ADDR,
+    PSI_TO_NASSET_PAIR_ADDR,
+};
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::StdError;
+use cosmwasm_std::{from_binary, Addr, Decimal, StdError, Uint128};
 
 #[test]
 fn fail_to_change_config_if_sender_is_not_governance() {
@@ -16,6 +22,9 @@ fn fail_to_change_config_if_sender_is_not_governance() {
             psi_token_addr: None,
             psi_to_nasset_pair_addr: None,
             nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: None,
+            withdrawal_mode: None,
         },
     };
 
@@ -31,18 +40,89 @@ fn fail_to_change_config_if_sender_is_not_governance() {
 }
 
 #[test]
-fn success_to_change_config_if_sender_governance() {
+fn success_to_change_config_if_sender_governance_after_delay() {
     let mut sdk = Sdk::init();
 
     let new_psi_token_addr = "addr9992".to_string();
     let new_psi_to_nasset_pair_addr = "addr9991".to_string();
     let new_nasset_token_rewards_addr = "addr9990".to_string();
+    let new_config_update_delay = 3_600;
+    let new_withdrawal_fee = WithdrawalFeeConfig {
+        max_fee: Decimal::percent(1),
+        decay_period: 86_400,
+        treasury_addr: Some("addr9989".to_string()),
+    };
+
+    // Queue config update
+    {
+        let change_config_msg = ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::UpdateConfig {
+                psi_token_addr: Some(new_psi_token_addr.clone()),
+                psi_to_nasset_pair_addr: Some(new_psi_to_nasset_pair_addr.clone()),
+                nasset_token_rewards_addr: Some(new_nasset_token_rewards_addr.clone()),
+                config_update_delay: Some(new_config_update_delay),
+                withdrawal_fee: Some(new_withdrawal_fee.clone()),
+                withdrawal_mode: None,
+            },
+        };
+
+        let env = mock_env();
+        let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+        crate::contract::execute(sdk.deps.as_mut(), env, info, change_config_msg).unwrap();
+
+        // nothing changed yet
+        let config = load_config(&sdk.deps.storage).unwrap();
+        assert_eq!(PSI_TOKEN_ADDR, config.psi_token);
+        assert_eq!(PSI_TO_NASSET_PAIR_ADDR, config.psi_to_nasset_pair);
+        assert_eq!(NASSET_TOKEN_REWARDS_ADDR, config.nasset_token_rewards);
+        assert_eq!(CONFIG_UPDATE_DELAY, config.config_update_delay);
+        assert_eq!(WithdrawalFee::none(), config.withdrawal_fee);
+    }
+
+    // Execute config update
+    {
+        let execute_update_msg = ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+        };
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(CONFIG_UPDATE_DELAY);
+        let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+        crate::contract::execute(sdk.deps.as_mut(), env, info, execute_update_msg).unwrap();
+
+        let config = load_config(&sdk.deps.storage).unwrap();
+        assert_eq!(new_psi_token_addr, config.psi_token);
+        assert_eq!(new_psi_to_nasset_pair_addr, config.psi_to_nasset_pair);
+        assert_eq!(new_nasset_token_rewards_addr, config.nasset_token_rewards);
+        assert_eq!(new_config_update_delay, config.config_update_delay);
+        assert_eq!(
+            WithdrawalFee {
+                max_fee: new_withdrawal_fee.max_fee,
+                decay_period: new_withdrawal_fee.decay_period,
+                treasury: Some(Addr::unchecked("addr9989")),
+            },
+            config.withdrawal_fee
+        );
+
+        let config_update = may_load_config_update(&sdk.deps.storage).unwrap();
+        assert!(config_update.is_none());
+    }
+}
+
+#[test]
+fn fail_to_execute_config_update_before_delay() {
+    let mut sdk = Sdk::init();
+
+    let new_psi_to_nasset_pair_addr = "addr9991".to_string();
 
     let change_config_msg = ExecuteMsg::Governance {
         governance_msg: GovernanceMsg::UpdateConfig {
-            psi_token_addr: Some(new_psi_token_addr.clone()),
-            psi_to_nasset_pair_addr: Some(new_psi_to_nasset_pair_addr.clone()),
-            nasset_token_rewards_addr: Some(new_nasset_token_rewards_addr.clone()),
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: Some(new_psi_to_nasset_pair_addr),
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: None,
+            withdrawal_mode: None,
         },
     };
 
@@ -50,8 +130,176 @@ fn success_to_change_config_if_sender_governance() {
     let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
     crate::contract::execute(sdk.deps.as_mut(), env, info, change_config_msg).unwrap();
 
+    let execute_update_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+    };
+
+    let mut env = mock_env();
+    let execute_after = env.block.time.seconds() + CONFIG_UPDATE_DELAY;
+    env.block.time = env.block.time.plus_seconds(CONFIG_UPDATE_DELAY - 1);
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), env, info, execute_update_msg);
+    assert_eq!(
+        StdError::generic_err(format!(
+            "config update is timelocked until {}",
+            execute_after
+        )),
+        res.err().unwrap()
+    );
+
     let config = load_config(&sdk.deps.storage).unwrap();
-    assert_eq!(new_psi_token_addr, config.psi_token);
-    assert_eq!(new_psi_to_nasset_pair_addr, config.psi_to_nasset_pair);
-    assert_eq!(new_nasset_token_rewards_addr, config.nasset_token_rewards);
+    assert_eq!(PSI_TO_NASSET_PAIR_ADDR, config.psi_to_nasset_pair);
+}
+
+#[test]
+fn cancel_config_update() {
+    let mut sdk = Sdk::init();
+
+    let change_config_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: Some("addr9991".to_string()),
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: None,
+            withdrawal_mode: None,
+        },
+    };
+
+    let env = mock_env();
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), env.clone(), info, change_config_msg).unwrap();
+
+    let query_res =
+        crate::contract::query(sdk.deps.as_ref(), env, QueryMsg::PendingConfigUpdate {}).unwrap();
+    let pending: Option<PendingConfigUpdateResponse> = from_binary(&query_res).unwrap();
+    assert_eq!(
+        Some(PendingConfigUpdateResponse {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: Some("addr9991".to_string()),
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: None,
+            withdrawal_mode: None,
+            deposit_caps: None,
+            token_query_mode: None,
+            new_vault: None,
+            router: None,
+            execute_after: mock_env().block.time.seconds() + CONFIG_UPDATE_DELAY,
+        }),
+        pending
+    );
+
+    let cancel_update_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::CancelConfigUpdate {},
+    };
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, cancel_update_msg).unwrap();
+
+    let execute_update_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+    };
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_UPDATE_DELAY);
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), env, info, execute_update_msg);
+    assert_eq!(
+        StdError::generic_err("no pending config update"),
+        res.err().unwrap()
+    );
+
+    let config = load_config(&sdk.deps.storage).unwrap();
+    assert_eq!(PSI_TO_NASSET_PAIR_ADDR, config.psi_to_nasset_pair);
+}
+
+#[test]
+fn fail_to_queue_withdrawal_fee_above_100_percent() {
+    let mut sdk = Sdk::init();
+
+    let change_config_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: None,
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: Some(WithdrawalFeeConfig {
+                max_fee: Decimal::percent(101),
+                decay_period: 86_400,
+                treasury_addr: None,
+            }),
+            withdrawal_mode: None,
+        },
+    };
+
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, change_config_msg);
+    assert_eq!(
+        StdError::generic_err("withdrawal fee can't be greater than 100%"),
+        res.err().unwrap()
+    );
+}
+
+#[test]
+fn fail_to_queue_config_update_while_one_is_pending() {
+    let mut sdk = Sdk::init();
+
+    let update_config_msg = |psi_to_nasset_pair_addr: &str| GovernanceMsg::UpdateConfig {
+        psi_token_addr: None,
+        psi_to_nasset_pair_addr: Some(psi_to_nasset_pair_addr.to_string()),
+        nasset_token_rewards_addr: None,
+        config_update_delay: None,
+        withdrawal_fee: None,
+        withdrawal_mode: None,
+    };
+    sdk.governance(update_config_msg("addr9991")).unwrap();
+    let pending = may_load_config_update(&sdk.deps.storage).unwrap();
+
+    // neither replacing pending update nor queueing other governance change
+    // restarts the timer
+    sdk.increase_block_time(CONFIG_UPDATE_DELAY / 2);
+    let governance_msgs = vec![
+        update_config_msg("addr9992"),
+        GovernanceMsg::UpdateDepositCaps {
+            max_total_nasset: Uint128::zero(),
+            max_nasset_per_user: None,
+        },
+        GovernanceMsg::UpdateTokenQueryMode {
+            token_addr: PSI_TOKEN_ADDR.to_string(),
+            mode: TokenQueryMode::Smart {},
+        },
+    ];
+    for governance_msg in governance_msgs {
+        assert_eq!(
+            StdError::generic_err("config update is already pending, cancel it first"),
+            sdk.governance(governance_msg).unwrap_err()
+        );
+    }
+    assert_eq!(pending, may_load_config_update(&sdk.deps.storage).unwrap());
+
+    // new update can be queued after cancelling pending one
+    sdk.governance(GovernanceMsg::CancelConfigUpdate {})
+        .unwrap();
+    sdk.governance_update(update_config_msg("addr9992"))
+        .unwrap();
+    let config = load_config(&sdk.deps.storage).unwrap();
+    assert_eq!(Addr::unchecked("addr9992"), config.psi_to_nasset_pair);
+}
+
+#[test]
+fn fail_to_queue_empty_config_update() {
+    let mut sdk = Sdk::init();
+
+    let res = sdk.governance(GovernanceMsg::UpdateConfig {
+        psi_token_addr: None,
+        psi_to_nasset_pair_addr: None,
+        nasset_token_rewards_addr: None,
+        config_update_delay: None,
+        withdrawal_fee: None,
+        withdrawal_mode: None,
+    });
+    assert_eq!(
+        StdError::generic_err("config update is empty"),
+        res.unwrap_err()
+    );
+    assert_eq!(None, may_load_config_update(&sdk.deps.storage).unwrap());
 }
@@ -1,12 +1,13 @@
 use crate::{
     msg::{ExecuteMsg, GovernanceMsg},
-    state::{load_config, load_gov_update},
+    state::load_config,
 };
 
-use super::sdk::{Sdk, GOVERNANCE_CONTRACT_ADDR};
+use crate::testing::{Sdk, GOVERNANCE_CONTRACT_ADDR};
 
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{Addr, BlockInfo, StdError};
+use nasset_autocompounder_governance::load_gov_update;
 
 #[test]
 fn fail_to_change_governance_if_sender_is_not_governance() {
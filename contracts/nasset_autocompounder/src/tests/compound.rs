@@ -1,4 +1,4 @@
-use super::sdk::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
+use crate::testing::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
 
 use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::{to_binary, CosmosMsg, SubMsg, Uint128, WasmMsg};
@@ -1,6 +1,6 @@
 use crate::msg::{Cw20HookMsg, ExecuteMsg};
 
-use super::sdk::{Sdk, AUTO_NASSET_TOKEN_ADDR};
+use crate::testing::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
 
 use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::testing::{mock_env, mock_info};
@@ -15,7 +15,7 @@ fn fail_to_deposit_wrong_cw20() {
     let cw20_deposit_msg = Cw20ReceiveMsg {
         sender: sender_addr.to_string(),
         amount: Uint128::from(256u64),
-        msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
     };
 
     let info = mock_info("some_random_addr", &[]);
@@ -119,3 +119,44 @@ fn deposit_nasset_after_someone_transfer_some_nassets_directly_to_contract() {
         );
     }
 }
+
+#[test]
+fn deposit_nasset_for_recipient() {
+    let mut sdk = Sdk::init();
+    let sender_addr = "addr9999";
+    let recipient_addr = "addr6666";
+    let deposit_amount = Uint128::from(1_000u128);
+
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(deposit_amount.into());
+
+    let cw20_deposit_msg = Cw20ReceiveMsg {
+        sender: sender_addr.to_string(),
+        amount: deposit_amount,
+        msg: to_binary(&Cw20HookMsg::Deposit {
+            recipient: Some(recipient_addr.to_string()),
+        })
+        .unwrap(),
+    };
+    let info = mock_info(NASSET_TOKEN_ADDR, &[]);
+    let response = crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        info,
+        ExecuteMsg::Receive(cw20_deposit_msg),
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: recipient_addr.to_string(),
+                amount: deposit_amount,
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
@@ -0,0 +1,157 @@
+use crate::msg::{DepositCapacityResponse, ExecuteMsg, GovernanceMsg, QueryMsg};
+
+use crate::testing::{Sdk, CONFIG_UPDATE_DELAY};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, StdError, Uint128};
+
+fn set_deposit_caps(sdk: &mut Sdk, max_total_nasset: u128, max_nasset_per_user: Option<u128>) {
+    sdk.governance_update(GovernanceMsg::UpdateDepositCaps {
+        max_total_nasset: Uint128::from(max_total_nasset),
+        max_nasset_per_user: max_nasset_per_user.map(Uint128::from),
+    })
+    .unwrap();
+}
+
+fn query_deposit_capacity(sdk: &Sdk, address: Option<&str>) -> DepositCapacityResponse {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        mock_env(),
+        QueryMsg::DepositCapacity {
+            address: address.map(|addr| addr.to_string()),
+            nasset_token_addr: None,
+        },
+    )
+    .unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+#[test]
+fn fail_to_update_deposit_caps_if_sender_is_not_governance() {
+    let mut sdk = Sdk::init();
+
+    let update_caps_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateDepositCaps {
+            max_total_nasset: Uint128::from(100u128),
+            max_nasset_per_user: None,
+        },
+    };
+
+    let info = mock_info("addr0010", &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, update_caps_msg);
+    assert_eq!(StdError::generic_err("unauthorized"), res.err().unwrap());
+}
+
+#[test]
+fn fail_to_deposit_above_total_cap() {
+    let mut sdk = Sdk::init();
+    set_deposit_caps(&mut sdk, 1_000, None);
+
+    let user_address = "addr9999";
+    let deposit_amount: Uint256 = 1_001u128.into();
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(deposit_amount);
+
+    let res = sdk.user_deposit(user_address, deposit_amount.into());
+    assert_eq!(
+        StdError::generic_err("deposit exceeds vault capacity"),
+        res.err().unwrap()
+    );
+
+    // exactly at cap is fine
+    let deposit_amount: Uint256 = 1_000u128.into();
+    sdk.set_nasset_balance(deposit_amount);
+    sdk.user_deposit(user_address, deposit_amount.into())
+        .unwrap();
+}
+
+#[test]
+fn fail_to_deposit_above_user_cap() {
+    let mut sdk = Sdk::init();
+    set_deposit_caps(&mut sdk, 1_000_000, Some(1_000));
+
+    // user already holds 300 anAsset out of 1000, vault has 2000 nAsset,
+    // so user position is worth 600 nAsset
+    let user_address = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_auto_nasset_balance(user_address, Uint256::from(300u128));
+
+    let deposit_amount: Uint256 = 401u128.into();
+    sdk.set_nasset_balance(Uint256::from(2_000u128) + deposit_amount);
+    let res = sdk.user_deposit(user_address, deposit_amount.into());
+    assert_eq!(
+        StdError::generic_err("deposit exceeds user capacity"),
+        res.err().unwrap()
+    );
+
+    let deposit_amount: Uint256 = 400u128.into();
+    sdk.set_nasset_balance(Uint256::from(2_000u128) + deposit_amount);
+    sdk.user_deposit(user_address, deposit_amount.into())
+        .unwrap();
+
+    // other user is not affected by first user position
+    let deposit_amount: Uint256 = 1_000u128.into();
+    sdk.set_nasset_balance(Uint256::from(2_000u128) + deposit_amount);
+    sdk.user_deposit("addr6666", deposit_amount.into()).unwrap();
+}
+
+#[test]
+fn query_remaining_capacity() {
+    let mut sdk = Sdk::init();
+    set_deposit_caps(&mut sdk, 10_000, Some(1_000));
+
+    let user_address = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_auto_nasset_balance(user_address, Uint256::from(300u128));
+    sdk.set_nasset_balance(Uint256::from(2_000u128));
+
+    assert_eq!(
+        DepositCapacityResponse {
+            total_nasset: Uint128::from(2_000u128),
+            remaining_total_nasset: Uint128::from(8_000u128),
+            user_nasset: Some(Uint128::from(600u128)),
+            remaining_user_nasset: Some(Uint128::from(400u128)),
+        },
+        query_deposit_capacity(&sdk, Some(user_address))
+    );
+
+    // remaining user capacity is limited by total capacity
+    sdk.set_nasset_balance(Uint256::from(9_900u128));
+    let capacity = query_deposit_capacity(&sdk, Some("addr6666"));
+    assert_eq!(Some(Uint128::zero()), capacity.user_nasset);
+    assert_eq!(Some(Uint128::from(100u128)), capacity.remaining_user_nasset);
+
+    // without per user cap there is nothing to report for user
+    set_deposit_caps(&mut sdk, 10_000, None);
+    let capacity = query_deposit_capacity(&sdk, Some(user_address));
+    assert_eq!(Uint128::from(100u128), capacity.remaining_total_nasset);
+    assert_eq!(None, capacity.user_nasset);
+    assert_eq!(None, capacity.remaining_user_nasset);
+}
+
+#[test]
+fn deposit_caps_are_timelocked() {
+    let mut sdk = Sdk::init();
+    let capacity = query_deposit_capacity(&sdk, None);
+
+    sdk.governance(GovernanceMsg::UpdateDepositCaps {
+        max_total_nasset: Uint128::zero(),
+        max_nasset_per_user: None,
+    })
+    .unwrap();
+    assert_eq!(capacity, query_deposit_capacity(&sdk, None));
+
+    sdk.increase_block_time(CONFIG_UPDATE_DELAY - 1);
+    assert!(sdk
+        .governance(GovernanceMsg::ExecuteConfigUpdate {})
+        .is_err());
+
+    sdk.increase_block_time(1);
+    sdk.governance(GovernanceMsg::ExecuteConfigUpdate {})
+        .unwrap();
+    assert_eq!(
+        Uint128::zero(),
+        query_deposit_capacity(&sdk, None).remaining_total_nasset
+    );
+}
@@ -0,0 +1,188 @@
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::load_config;
+
+use crate::testing::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, CosmosMsg, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, MinterResponse, TokenInfoResponse};
+use serde::de::DeserializeOwned;
+
+fn query<T: DeserializeOwned>(sdk: &Sdk, msg: QueryMsg) -> StdResult<T> {
+    from_binary(&crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        msg,
+    )?)
+}
+
+fn query_balance(sdk: &Sdk, address: &str) -> Uint128 {
+    let response: BalanceResponse = query(
+        sdk,
+        QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    response.balance
+}
+
+fn query_total_supply(sdk: &Sdk) -> Uint128 {
+    let response: TokenInfoResponse = query(sdk, QueryMsg::TokenInfo {}).unwrap();
+    response.total_supply
+}
+
+#[test]
+fn instantiate_with_embedded_share_token() {
+    let sdk = Sdk::init_with_embedded_share_token();
+
+    let config = load_config(&sdk.deps.storage).unwrap();
+    assert_eq!(MOCK_CONTRACT_ADDR, config.auto_nasset_token.as_str());
+
+    let token_info: TokenInfoResponse = query(&sdk, QueryMsg::TokenInfo {}).unwrap();
+    assert_eq!(
+        TokenInfoResponse {
+            name: "nAVAX autocompounder share representation".to_string(),
+            symbol: "cnAVAX".to_string(),
+            decimals: 6,
+            total_supply: Uint128::zero(),
+        },
+        token_info
+    );
+
+    let minter: Option<MinterResponse> = query(&sdk, QueryMsg::Minter {}).unwrap();
+    assert_eq!(
+        Some(MinterResponse {
+            minter: MOCK_CONTRACT_ADDR.to_string(),
+            cap: None,
+        }),
+        minter
+    );
+}
+
+#[test]
+fn deposit_mints_embedded_share_token() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+    let user_address = "addr9999";
+
+    sdk.set_nasset_balance(Uint256::from(100u64));
+    let response = sdk.user_deposit(user_address, Uint128::new(100)).unwrap();
+    //minted in place, no messages to token contract
+    assert!(response.messages.is_empty());
+    assert_eq!(Uint128::new(100), query_balance(&sdk, user_address));
+    assert_eq!(Uint128::new(100), query_total_supply(&sdk));
+
+    //nAsset price doubled
+    sdk.set_nasset_balance(Uint256::from(300u64));
+    sdk.user_deposit("addr6666", Uint128::new(100)).unwrap();
+    assert_eq!(Uint128::new(50), query_balance(&sdk, "addr6666"));
+    assert_eq!(Uint128::new(150), query_total_supply(&sdk));
+}
+
+#[test]
+fn withdraw_burns_embedded_share_token() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+    let user_address = "addr9999";
+
+    sdk.set_nasset_balance(Uint256::from(100u64));
+    sdk.user_deposit(user_address, Uint128::new(100)).unwrap();
+
+    let response = sdk
+        .user_withdraw_embedded(user_address, Uint128::new(40), Uint256::from(20u64))
+        .unwrap();
+    //only nAsset transfer, anAsset is burned in place
+    assert_eq!(
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: NASSET_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: user_address.to_string(),
+                amount: Uint128::new(48),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))],
+        response.messages
+    );
+
+    assert_eq!(Uint128::new(60), query_balance(&sdk, user_address));
+    assert_eq!(Uint128::zero(), query_balance(&sdk, MOCK_CONTRACT_ADDR));
+    assert_eq!(Uint128::new(60), query_total_supply(&sdk));
+}
+
+#[test]
+fn transfer_embedded_share_token() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+    let user_address = "addr9999";
+
+    sdk.set_nasset_balance(Uint256::from(100u64));
+    sdk.user_deposit(user_address, Uint128::new(100)).unwrap();
+
+    crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info(user_address, &[]),
+        ExecuteMsg::Transfer {
+            recipient: "addr6666".to_string(),
+            amount: Uint128::new(30),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(Uint128::new(70), query_balance(&sdk, user_address));
+    assert_eq!(Uint128::new(30), query_balance(&sdk, "addr6666"));
+    assert_eq!(Uint128::new(100), query_total_supply(&sdk));
+}
+
+#[test]
+fn fail_to_withdraw_from_other_cw20_in_embedded_mode() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+
+    sdk.set_nasset_balance(Uint256::from(100u64));
+    sdk.user_deposit("addr9999", Uint128::new(100)).unwrap();
+
+    //there is no separate anAsset token
+    let res = crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info(AUTO_NASSET_TOKEN_ADDR, &[]),
+        ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+            sender: "addr9999".to_string(),
+            amount: Uint128::new(100),
+            msg: to_binary(&crate::msg::Cw20HookMsg::Withdraw { recipient: None }).unwrap(),
+        }),
+    );
+    assert_eq!(Err(StdError::generic_err("unauthorized")), res);
+}
+
+#[test]
+fn fail_to_use_cw20_interface_with_external_share_token() {
+    let mut sdk = Sdk::init();
+
+    let res = crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info("addr9999", &[]),
+        ExecuteMsg::Transfer {
+            recipient: "addr6666".to_string(),
+            amount: Uint128::new(30),
+        },
+    );
+    assert_eq!(
+        Err(StdError::generic_err("share token is not embedded")),
+        res
+    );
+
+    let res: StdResult<BalanceResponse> = query(
+        &sdk,
+        QueryMsg::Balance {
+            address: "addr9999".to_string(),
+        },
+    );
+    assert_eq!(
+        Err(StdError::generic_err("share token is not embedded")),
+        res
+    );
+}
@@ -0,0 +1,91 @@
+use nasset_autocompounder_interface::events::{
+    AutocompounderEvent, CompoundEvent, DepositEvent, WithdrawEvent,
+};
+
+use crate::testing::{Sdk, NASSET_TOKEN_ADDR};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+#[test]
+fn deposit_event() {
+    let mut sdk = Sdk::init();
+    let user_address = "addr9999";
+
+    sdk.set_auto_nasset_supply(Uint256::from(100u128));
+    //200 in vault + 50 deposited
+    sdk.set_nasset_balance(Uint256::from(250u128));
+    let response = sdk
+        .user_deposit(user_address, Uint128::from(50u128))
+        .unwrap();
+
+    assert_eq!(
+        AutocompounderEvent::parse_all(&response.events).unwrap(),
+        vec![AutocompounderEvent::Deposit(DepositEvent {
+            nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+            sender: Addr::unchecked(user_address),
+            recipient: Addr::unchecked(user_address),
+            nasset_amount: Uint128::from(50u128),
+            shares_minted: Uint128::from(25u128),
+            exchange_rate: Decimal::from_ratio(2u128, 1u128),
+        })]
+    );
+}
+
+#[test]
+fn withdraw_emits_compound_and_withdraw_events() {
+    let mut sdk = Sdk::init();
+    let user_address = "addr9999";
+
+    sdk.set_auto_nasset_supply(Uint256::from(100u128));
+    sdk.set_nasset_balance(Uint256::from(100u128));
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(10u128), Uint256::from(100u128))
+        .unwrap();
+
+    assert_eq!(
+        AutocompounderEvent::parse_all(&response.events).unwrap(),
+        vec![
+            AutocompounderEvent::Compound(CompoundEvent {
+                nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+                sender: Addr::unchecked(user_address),
+                psi_claimed: Uint128::from(256_000_000u128),
+                psi_sold: Uint128::from(256_000_000u128),
+                nasset_bought: Uint128::from(100u128),
+                exchange_rate: Decimal::from_ratio(2u128, 1u128),
+            }),
+            AutocompounderEvent::Withdraw(WithdrawEvent {
+                nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+                sender: Addr::unchecked(user_address),
+                recipient: Addr::unchecked(user_address),
+                shares_burned: Uint128::from(10u128),
+                nasset_amount: Uint128::from(20u128),
+                withdrawal_fee: Uint128::zero(),
+                exchange_rate: Decimal::from_ratio(2u128, 1u128),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn compound_event_counts_psi_from_balance() {
+    let mut sdk = Sdk::init();
+
+    sdk.set_auto_nasset_supply(Uint256::from(100u128));
+    sdk.set_nasset_balance(Uint256::from(100u128));
+    //PSI left on contract balance before compound
+    sdk.set_psi_balance(Uint256::from(1_000u128));
+    let response = sdk.user_send_compound(Uint256::from(50u128)).unwrap();
+
+    assert_eq!(
+        AutocompounderEvent::parse_all(&response.events).unwrap(),
+        vec![AutocompounderEvent::Compound(CompoundEvent {
+            nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+            sender: Addr::unchecked("addr9999"),
+            psi_claimed: Uint128::from(255_999_000u128),
+            psi_sold: Uint128::from(256_000_000u128),
+            nasset_bought: Uint128::from(50u128),
+            exchange_rate: Decimal::from_ratio(3u128, 2u128),
+        })]
+    );
+}
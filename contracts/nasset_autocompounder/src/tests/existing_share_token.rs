@@ -0,0 +1,86 @@
+use crate::msg::ShareTokenMode;
+use crate::state::load_config;
+
+use crate::testing::{mock_dependencies, Sdk, AUTO_NASSET_TOKEN_ADDR};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+fn instantiate_with_minter(minter: Option<&str>) -> Result<usize, StdError> {
+    let mut deps = mock_dependencies(&[]);
+    if let Some(minter) = minter {
+        deps.querier
+            .with_token_minter(AUTO_NASSET_TOKEN_ADDR, minter);
+    }
+
+    let mut instantiate_msg = Sdk::instantiate_msg();
+    instantiate_msg.share_token_mode = Some(ShareTokenMode::Existing {
+        token_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+    });
+    let response = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr9999", &[]),
+        instantiate_msg,
+    )?;
+    Ok(response.messages.len())
+}
+
+#[test]
+fn instantiate_with_existing_share_token() {
+    let sdk = Sdk::init_with_existing_share_token();
+
+    let config = load_config(&sdk.deps.storage).unwrap();
+    assert_eq!(AUTO_NASSET_TOKEN_ADDR, config.auto_nasset_token.as_str());
+
+    //no new share token is instantiated
+    assert_eq!(Ok(0), instantiate_with_minter(Some(MOCK_CONTRACT_ADDR)));
+}
+
+#[test]
+fn fail_to_instantiate_when_autocompounder_is_not_minter() {
+    assert_eq!(
+        Err(StdError::generic_err(
+            "autocompounder is not minter of share token"
+        )),
+        instantiate_with_minter(Some("old_autocompounder"))
+    );
+    assert_eq!(
+        Err(StdError::generic_err(
+            "autocompounder is not minter of share token"
+        )),
+        instantiate_with_minter(None)
+    );
+}
+
+#[test]
+fn holders_keep_share_value_after_redeploy() {
+    //100 anAsset of previous deployment, its 200 nAsset is moved to new vault
+    let mut sdk = Sdk::builder()
+        .share_token_mode(ShareTokenMode::Existing {
+            token_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+        })
+        .nasset_balance(Uint128::from(200u128))
+        .auto_nasset_supply(Uint128::from(100u128))
+        .auto_nasset_balance("old_holder", Uint128::from(100u128))
+        .build();
+
+    let deposit_amount = Uint128::from(100u128);
+    sdk.increase_nasset_balance(Uint256::from(deposit_amount));
+    let response = sdk.user_deposit("new_holder", deposit_amount).unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: "new_holder".to_string(),
+                amount: Uint128::from(50u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
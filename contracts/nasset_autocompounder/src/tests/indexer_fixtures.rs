@@ -0,0 +1,117 @@
+use crate::testing::Sdk;
+use nasset_autocompounder_interface::events::CONTRACT_ADDRESS_ATTRIBUTE;
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Event, Response, Uint128};
+use serde::Serialize;
+use std::path::PathBuf;
+
+// transactions which 'nasset_autocompounder_indexer' tests are run against.
+// Rewrite fixture after events change with 'UPDATE_INDEXER_FIXTURES=1 cargo test indexer_fixtures'
+const FIXTURE_PATH: &str =
+    "../../packages/nasset_autocompounder_indexer/src/tests/fixtures/history.json";
+const USER_A: &str = "addr0100";
+const USER_B: &str = "addr0101";
+const BLOCK_TIME: u64 = 3_600;
+
+#[derive(Serialize)]
+struct FixtureTx {
+    height: u64,
+    timestamp: u64,
+    events: Vec<Event>,
+}
+
+struct History {
+    sdk: Sdk,
+    txs: Vec<FixtureTx>,
+}
+
+impl History {
+    fn new() -> Self {
+        History {
+            sdk: Sdk::init(),
+            txs: vec![],
+        }
+    }
+
+    // every transaction is in its own block, events are prefixed and tagged with
+    // contract address as chain does it
+    fn record(&mut self, response: Response) {
+        self.sdk.env.block.height += 1;
+        self.sdk.increase_block_time(BLOCK_TIME);
+        let contract_addr = self.sdk.env.contract.address.to_string();
+        let events = response
+            .events
+            .into_iter()
+            .map(|event| {
+                let mut chain_event = Event::new(format!("wasm-{}", event.ty))
+                    .add_attribute(CONTRACT_ADDRESS_ATTRIBUTE, &contract_addr);
+                chain_event.attributes.extend(event.attributes);
+                chain_event
+            })
+            .collect();
+
+        self.txs.push(FixtureTx {
+            height: self.sdk.env.block.height,
+            timestamp: self.sdk.env.block.time.seconds(),
+            events,
+        });
+    }
+}
+
+fn history_txs() -> Vec<FixtureTx> {
+    let mut history = History::new();
+
+    //A deposits 100 nAsset into empty vault, rate 1
+    history.sdk.set_nasset_balance(Uint256::from(100u128));
+    let response = history
+        .sdk
+        .user_deposit(USER_A, Uint128::from(100u128))
+        .unwrap();
+    history.record(response);
+    history.sdk.set_auto_nasset_supply(Uint256::from(100u128));
+
+    //compound buys 100 nAsset, rate 2
+    let response = history
+        .sdk
+        .user_send_compound(Uint256::from(100u128))
+        .unwrap();
+    history.record(response);
+
+    //B deposits 50 nAsset for 25 anAsset
+    history.sdk.set_nasset_balance(Uint256::from(250u128));
+    let response = history
+        .sdk
+        .user_deposit(USER_B, Uint128::from(50u128))
+        .unwrap();
+    history.record(response);
+    history.sdk.set_auto_nasset_supply(Uint256::from(125u128));
+
+    //A withdraws 50 anAsset after compound which buys 25 nAsset, rate 2.2
+    let response = history
+        .sdk
+        .user_withdraw(USER_A, Uint128::from(50u128), Uint256::from(25u128))
+        .unwrap();
+    history.record(response);
+
+    history.txs
+}
+
+#[test]
+fn indexer_fixtures_are_up_to_date() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(FIXTURE_PATH);
+    let txs = serde_json::to_value(history_txs()).unwrap();
+
+    if std::env::var("UPDATE_INDEXER_FIXTURES").is_ok() {
+        let content = serde_json::to_string_pretty(&txs).unwrap();
+        std::fs::write(&path, content + "\n").unwrap();
+        return;
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let fixture: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(
+        fixture, txs,
+        "indexer fixtures are outdated, rerun with UPDATE_INDEXER_FIXTURES=1"
+    );
+}
@@ -1,4 +1,4 @@
-use super::sdk::Sdk;
+use crate::testing::Sdk;
 
 #[test]
 fn proper_initialization() {
@@ -0,0 +1,141 @@
+use crate::msg::{
+    AstroportAssetInfo, AstroportCw20HookMsg, AstroportQueryMsg, AstroportSimulationResponse,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+//constant product PSI -> nAsset pair with 0.3% commission,
+//reserves are pair balances of both tokens
+pub const COMMISSION_PERMILLE: u128 = 3;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub psi_token: String,
+    pub nasset_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Config {
+    psi_token: Addr,
+    nasset_token: Addr,
+}
+
+const CONFIG: Item<Config> = Item::new("config");
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            psi_token: deps.api.addr_validate(&msg.psi_token)?,
+            nasset_token: deps.api.addr_validate(&msg.nasset_token)?,
+        },
+    )?;
+    Ok(Response::new())
+}
+
+fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    let ExecuteMsg::Receive(cw20_msg) = msg;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.psi_token {
+        return Err(StdError::generic_err("only PSI can be offered"));
+    }
+
+    let to = match from_binary(&cw20_msg.msg)? {
+        AstroportCw20HookMsg::Swap { to, .. } => to.unwrap_or(cw20_msg.sender),
+        AstroportCw20HookMsg::WithdrawLiquidity {} => {
+            return Err(StdError::generic_err("liquidity is not supported"))
+        }
+    };
+
+    // offered PSI is already on pair balance
+    let psi_reserve = token_balance(deps.as_ref(), &config.psi_token, &env.contract.address)?
+        .checked_sub(cw20_msg.amount)?;
+    let nasset_reserve = token_balance(deps.as_ref(), &config.nasset_token, &env.contract.address)?;
+    let simulation = compute_swap(psi_reserve, nasset_reserve, cw20_msg.amount);
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.nasset_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to,
+                amount: simulation.return_amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "swap"),
+            ("offer_amount", &cw20_msg.amount.to_string()),
+            ("return_amount", &simulation.return_amount.to_string()),
+        ]))
+}
+
+fn query(deps: Deps, env: Env, msg: AstroportQueryMsg) -> StdResult<Binary> {
+    match msg {
+        AstroportQueryMsg::Simulation { offer_asset } => {
+            let config = CONFIG.load(deps.storage)?;
+            match offer_asset.info {
+                AstroportAssetInfo::Token { contract_addr }
+                    if contract_addr == config.psi_token.as_str() => {}
+                _ => return Err(StdError::generic_err("only PSI can be offered")),
+            }
+
+            let psi_reserve = token_balance(deps, &config.psi_token, &env.contract.address)?;
+            let nasset_reserve = token_balance(deps, &config.nasset_token, &env.contract.address)?;
+            to_binary(&compute_swap(
+                psi_reserve,
+                nasset_reserve,
+                offer_asset.amount,
+            ))
+        }
+    }
+}
+
+pub fn compute_swap(
+    offer_reserve: Uint128,
+    ask_reserve: Uint128,
+    offer_amount: Uint128,
+) -> AstroportSimulationResponse {
+    // ask_reserve * offer_amount / (offer_reserve + offer_amount)
+    let return_amount = ask_reserve.multiply_ratio(offer_amount, offer_reserve + offer_amount);
+    // spread is the loss against the spot price
+    let spot_return_amount = ask_reserve.multiply_ratio(offer_amount, offer_reserve);
+    let commission_amount = return_amount.multiply_ratio(COMMISSION_PERMILLE, 1000u128);
+
+    AstroportSimulationResponse {
+        return_amount: return_amount - commission_amount,
+        spread_amount: spot_return_amount.saturating_sub(return_amount),
+        commission_amount,
+    }
+}
+
+fn token_balance(deps: Deps, token: &Addr, account: &Addr) -> StdResult<Uint128> {
+    let response: BalanceResponse = deps.querier.query_wasm_smart(
+        token.to_string(),
+        &Cw20QueryMsg::Balance {
+            address: account.to_string(),
+        },
+    )?;
+    Ok(response.balance)
+}
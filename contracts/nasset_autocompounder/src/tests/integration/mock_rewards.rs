@@ -0,0 +1,127 @@
+use crate::msg::{
+    NAssetTokenRewardsAccruedRewardsResponse, NAssetTokenRewardsAnyoneMsg,
+    NAssetTokenRewardsExecuteMsg, NAssetTokenRewardsQueryMsg,
+};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+//nAsset rewards contract which accrues 'psi_per_nasset' PSI per second
+//for each nAsset on holder balance (balance at claim time)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub psi_token: String,
+    pub nasset_token: String,
+    pub psi_per_nasset: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Config {
+    psi_token: Addr,
+    nasset_token: Addr,
+    psi_per_nasset: Decimal,
+    start_time: u64,
+}
+
+const CONFIG: Item<Config> = Item::new("config");
+const LAST_CLAIM_TIME: Map<&Addr, u64> = Map::new("last_claim_time");
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            psi_token: deps.api.addr_validate(&msg.psi_token)?,
+            nasset_token: deps.api.addr_validate(&msg.nasset_token)?,
+            psi_per_nasset: msg.psi_per_nasset,
+            start_time: env.block.time.seconds(),
+        },
+    )?;
+    Ok(Response::new())
+}
+
+fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: NAssetTokenRewardsExecuteMsg,
+) -> StdResult<Response> {
+    let NAssetTokenRewardsExecuteMsg::Anyone { anyone_msg } = msg;
+    let (holder, recipient) = match anyone_msg {
+        NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient } => {
+            let recipient = match recipient {
+                Some(recipient) => deps.api.addr_validate(&recipient)?,
+                None => info.sender.clone(),
+            };
+            (info.sender, recipient)
+        }
+        NAssetTokenRewardsAnyoneMsg::ClaimRewardsForSomeone { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            (address.clone(), address)
+        }
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let rewards = accrued_rewards(deps.as_ref(), &env, &config, &holder)?;
+    if rewards.is_zero() {
+        return Err(StdError::generic_err("No rewards have accrued yet"));
+    }
+    LAST_CLAIM_TIME.save(deps.storage, &holder, &env.block.time.seconds())?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.psi_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: rewards,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "claim_rewards"),
+            ("holder", holder.as_str()),
+            ("rewards", &rewards.to_string()),
+        ]))
+}
+
+fn query(deps: Deps, env: Env, msg: NAssetTokenRewardsQueryMsg) -> StdResult<Binary> {
+    match msg {
+        NAssetTokenRewardsQueryMsg::AccruedRewards { address } => {
+            let config = CONFIG.load(deps.storage)?;
+            let holder = deps.api.addr_validate(&address)?;
+            to_binary(&NAssetTokenRewardsAccruedRewardsResponse {
+                rewards: accrued_rewards(deps, &env, &config, &holder)?,
+            })
+        }
+    }
+}
+
+fn accrued_rewards(deps: Deps, env: &Env, config: &Config, holder: &Addr) -> StdResult<Uint128> {
+    let last_claim_time = LAST_CLAIM_TIME
+        .may_load(deps.storage, holder)?
+        .unwrap_or(config.start_time);
+    let elapsed = env.block.time.seconds().saturating_sub(last_claim_time);
+
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        config.nasset_token.to_string(),
+        &Cw20QueryMsg::Balance {
+            address: holder.to_string(),
+        },
+    )?;
+
+    Ok(balance.balance * config.psi_per_nasset * Uint128::from(elapsed))
+}
@@ -0,0 +1,308 @@
+mod mock_pair;
+mod mock_rewards;
+mod scenarios;
+
+use crate::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ShareTokenMode,
+    SimulateCompoundResponse, StatsResponse,
+};
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
+use cw20::{
+    BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse, TokenInfoResponse,
+};
+use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+
+pub const ADMIN: &str = "admin";
+pub const GOVERNANCE: &str = "governance";
+pub const KEEPER: &str = "keeper";
+pub const COLLATERAL_TOKEN_SYMBOL: &str = "Luna";
+
+//pair reserves, PSI price is 0.1 nAsset
+pub const PAIR_PSI_RESERVE: u128 = 10_000_000_000_000;
+pub const PAIR_NASSET_RESERVE: u128 = 1_000_000_000_000;
+pub const REWARDS_PSI_BALANCE: u128 = 1_000_000_000_000_000;
+
+fn autocompounder_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply),
+    )
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
+//autocompounder with real cw20-base nAsset, PSI and anAsset tokens,
+//mock nAsset rewards contract and constant product PSI -> nAsset pair
+pub struct Suite {
+    pub app: App,
+    pub nasset_token: Addr,
+    pub psi_token: Addr,
+    pub auto_nasset_token: Addr,
+    pub nasset_token_rewards: Addr,
+    pub psi_to_nasset_pair: Addr,
+    pub autocompounder: Addr,
+}
+
+impl Suite {
+    pub fn init() -> Self {
+        Self::init_with(ShareTokenMode::External {})
+    }
+
+    pub fn init_with_embedded_share_token() -> Self {
+        Self::init_with(ShareTokenMode::Embedded {})
+    }
+
+    fn init_with(share_token_mode: ShareTokenMode) -> Self {
+        let mut app = AppBuilder::new().build();
+        let admin = Addr::unchecked(ADMIN);
+        let cw20_code_id = app.store_code(cw20_contract());
+        let rewards_code_id = app.store_code(mock_rewards::contract());
+        let pair_code_id = app.store_code(mock_pair::contract());
+        let autocompounder_code_id = app.store_code(autocompounder_contract());
+
+        let nasset_token = instantiate_token(&mut app, cw20_code_id, "nLuna token", "nLuna");
+        let psi_token = instantiate_token(&mut app, cw20_code_id, "Nexus PSI token", "PSI");
+
+        let nasset_token_rewards = app
+            .instantiate_contract(
+                rewards_code_id,
+                admin.clone(),
+                &mock_rewards::InstantiateMsg {
+                    psi_token: psi_token.to_string(),
+                    nasset_token: nasset_token.to_string(),
+                    // 0.864 PSI per nAsset per day
+                    psi_per_nasset: Decimal::from_ratio(1u128, 100_000u128),
+                },
+                &[],
+                "nasset rewards",
+                None,
+            )
+            .unwrap();
+
+        let psi_to_nasset_pair = app
+            .instantiate_contract(
+                pair_code_id,
+                admin.clone(),
+                &mock_pair::InstantiateMsg {
+                    psi_token: psi_token.to_string(),
+                    nasset_token: nasset_token.to_string(),
+                },
+                &[],
+                "psi to nasset pair",
+                None,
+            )
+            .unwrap();
+
+        let autocompounder = app
+            .instantiate_contract(
+                autocompounder_code_id,
+                admin,
+                &InstantiateMsg {
+                    nasset_token_addr: nasset_token.to_string(),
+                    psi_token_addr: psi_token.to_string(),
+                    psi_to_nasset_pair_addr: psi_to_nasset_pair.to_string(),
+                    governance_contract_addr: GOVERNANCE.to_string(),
+                    cw20_token_code_id: cw20_code_id,
+                    nasset_token_rewards_addr: nasset_token_rewards.to_string(),
+                    collateral_token_symbol: COLLATERAL_TOKEN_SYMBOL.to_string(),
+                    config_update_delay: 0,
+                    max_total_nasset: Uint128::MAX,
+                    max_nasset_per_user: None,
+                    withdrawal_fee: None,
+                    withdrawal_mode: None,
+                    share_token_mode: Some(share_token_mode),
+                },
+                &[],
+                "nasset autocompounder",
+                None,
+            )
+            .unwrap();
+
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(autocompounder.clone(), &QueryMsg::Config {})
+            .unwrap();
+
+        let mut suite = Suite {
+            app,
+            nasset_token,
+            psi_token,
+            auto_nasset_token: Addr::unchecked(config.auto_nasset_token_addr),
+            nasset_token_rewards,
+            psi_to_nasset_pair,
+            autocompounder,
+        };
+
+        let pair = suite.psi_to_nasset_pair.to_string();
+        let rewards = suite.nasset_token_rewards.to_string();
+        suite.mint_nasset(&pair, PAIR_NASSET_RESERVE);
+        suite.mint_psi(&pair, PAIR_PSI_RESERVE);
+        suite.mint_psi(&rewards, REWARDS_PSI_BALANCE);
+        suite
+    }
+
+    pub fn mint_nasset(&mut self, recipient: &str, amount: u128) {
+        let token = self.nasset_token.clone();
+        self.mint(token, recipient, amount);
+    }
+
+    pub fn mint_psi(&mut self, recipient: &str, amount: u128) {
+        let token = self.psi_token.clone();
+        self.mint(token, recipient, amount);
+    }
+
+    fn mint(&mut self, token: Addr, recipient: &str, amount: u128) {
+        self.app
+            .execute_contract(
+                Addr::unchecked(ADMIN),
+                token,
+                &Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::from(amount),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    pub fn deposit(&mut self, user: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(user),
+            self.nasset_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.autocompounder.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
+            },
+            &[],
+        )
+    }
+
+    //anAsset 'Send' to autocompounder, cw20 interface is the same for both share token modes
+    pub fn withdraw(&mut self, user: &str, auto_nasset_amount: u128) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(user),
+            self.auto_nasset_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.autocompounder.to_string(),
+                amount: Uint128::from(auto_nasset_amount),
+                msg: to_binary(&Cw20HookMsg::Withdraw { recipient: None }).unwrap(),
+            },
+            &[],
+        )
+    }
+
+    pub fn withdraw_all(&mut self, user: &str) -> AnyResult<AppResponse> {
+        let auto_nasset_balance = self.auto_nasset_balance(user);
+        self.withdraw(user, auto_nasset_balance)
+    }
+
+    pub fn compound(&mut self) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(KEEPER),
+            self.autocompounder.clone(),
+            &ExecuteMsg::Compound {
+                nasset_token_addr: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn nasset_balance(&self, address: &str) -> u128 {
+        self.token_balance(&self.nasset_token, address)
+    }
+
+    pub fn psi_balance(&self, address: &str) -> u128 {
+        self.token_balance(&self.psi_token, address)
+    }
+
+    pub fn auto_nasset_balance(&self, address: &str) -> u128 {
+        self.token_balance(&self.auto_nasset_token, address)
+    }
+
+    pub fn auto_nasset_supply(&self) -> u128 {
+        let response: TokenInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.auto_nasset_token.clone(), &Cw20QueryMsg::TokenInfo {})
+            .unwrap();
+        response.total_supply.u128()
+    }
+
+    fn token_balance(&self, token: &Addr, address: &str) -> u128 {
+        let response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        response.balance.u128()
+    }
+
+    pub fn vault_nasset(&self) -> u128 {
+        self.nasset_balance(self.autocompounder.as_str())
+    }
+
+    pub fn stats(&self) -> StatsResponse {
+        self.query(&QueryMsg::Stats {
+            nasset_token_addr: None,
+        })
+    }
+
+    pub fn simulate_compound(&self) -> SimulateCompoundResponse {
+        self.query(&QueryMsg::SimulateCompound {
+            nasset_token_addr: None,
+        })
+    }
+
+    pub fn query<T: serde::de::DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.autocompounder.clone(), msg)
+            .unwrap()
+    }
+}
+
+fn instantiate_token(app: &mut App, code_id: u64, name: &str, symbol: &str) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(ADMIN),
+        &cw20_base::msg::InstantiateMsg {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: Vec::<Cw20Coin>::new(),
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+    )
+    .unwrap()
+}
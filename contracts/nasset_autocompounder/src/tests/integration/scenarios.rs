@@ -0,0 +1,237 @@
+use super::mock_pair::compute_swap;
+use super::{Suite, PAIR_NASSET_RESERVE};
+use cosmwasm_std::Uint128;
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn deposit_and_withdraw_in_same_block_returns_deposit() {
+    let mut suite = Suite::init();
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.mint_nasset("user2", 500_000_000);
+
+    suite.deposit("user1", 1_000_000_000).unwrap();
+    suite.deposit("user2", 500_000_000).unwrap();
+    assert_eq!(suite.auto_nasset_balance("user1"), 1_000_000_000);
+    assert_eq!(suite.auto_nasset_balance("user2"), 500_000_000);
+    assert_eq!(suite.vault_nasset(), 1_500_000_000);
+
+    //no rewards accrued, failed claim does not block withdraw
+    suite.withdraw_all("user2").unwrap();
+    suite.withdraw_all("user1").unwrap();
+
+    assert_eq!(suite.nasset_balance("user1"), 1_000_000_000);
+    assert_eq!(suite.nasset_balance("user2"), 500_000_000);
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+    assert_eq!(suite.stats().compounds_count, 0);
+}
+
+#[test]
+fn compound_sells_claimed_psi_for_nasset() {
+    let mut suite = Suite::init();
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.deposit("user1", 1_000_000_000).unwrap();
+
+    suite.advance_time(DAY);
+    let simulation = suite.simulate_compound();
+    // 0.864 PSI per nAsset per day
+    assert_eq!(simulation.psi_to_sell.u128(), 864_000_000);
+
+    let pair = suite.psi_to_nasset_pair.to_string();
+    let pair_psi_before = suite.psi_balance(&pair);
+    suite.compound().unwrap();
+
+    let autocompounder = suite.autocompounder.to_string();
+    assert_eq!(suite.psi_balance(&autocompounder), 0);
+    assert_eq!(suite.psi_balance(&pair), pair_psi_before + 864_000_000);
+    assert_eq!(
+        suite.vault_nasset(),
+        1_000_000_000 + simulation.nasset_out.u128()
+    );
+    assert_eq!(suite.auto_nasset_supply(), 1_000_000_000);
+
+    let stats = suite.stats();
+    assert_eq!(stats.psi_claimed.u128(), 864_000_000);
+    assert_eq!(stats.psi_swapped.u128(), 864_000_000);
+    assert_eq!(stats.nasset_acquired, simulation.nasset_out);
+    assert_eq!(stats.compounds_count, 1);
+}
+
+#[test]
+fn compound_without_accrued_rewards_fails() {
+    let mut suite = Suite::init();
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.deposit("user1", 1_000_000_000).unwrap();
+
+    suite.compound().unwrap_err();
+    assert_eq!(suite.vault_nasset(), 1_000_000_000);
+    assert_eq!(suite.stats().compounds_count, 0);
+}
+
+#[test]
+fn withdraw_compounds_before_burning() {
+    let mut suite = Suite::init();
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.deposit("user1", 1_000_000_000).unwrap();
+
+    suite.advance_time(DAY);
+    let simulation = suite.simulate_compound();
+    suite.withdraw_all("user1").unwrap();
+
+    assert_eq!(
+        suite.nasset_balance("user1"),
+        1_000_000_000 + simulation.nasset_out.u128()
+    );
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+    assert_eq!(suite.stats().compounds_count, 1);
+}
+
+#[test]
+fn late_depositor_does_not_share_earlier_rewards() {
+    let mut suite = Suite::init();
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.mint_nasset("user2", 1_000_000_000);
+    suite.deposit("user1", 1_000_000_000).unwrap();
+
+    suite.advance_time(DAY);
+    suite.compound().unwrap();
+    let nasset_bought = suite.vault_nasset() - 1_000_000_000;
+
+    suite.deposit("user2", 1_000_000_000).unwrap();
+    assert!(suite.auto_nasset_balance("user2") < suite.auto_nasset_balance("user1"));
+
+    suite.withdraw_all("user2").unwrap();
+    suite.withdraw_all("user1").unwrap();
+
+    //rounding is in favor of the vault
+    let user2_nasset = suite.nasset_balance("user2");
+    assert!((1_000_000_000 - 1..=1_000_000_000).contains(&user2_nasset));
+    assert_eq!(
+        suite.nasset_balance("user1"),
+        2_000_000_000 + nasset_bought - user2_nasset
+    );
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+}
+
+#[test]
+fn rewards_are_split_by_share_between_many_users() {
+    let mut suite = Suite::init();
+    let users = ["user1", "user2", "user3", "user4", "user5"];
+    let deposits: [u128; 5] = [
+        1_000_000_000,
+        2_500_000_000,
+        300_000_000,
+        7_000_000_000,
+        50_000_000,
+    ];
+    for (user, deposit) in users.iter().zip(deposits.iter()) {
+        suite.mint_nasset(user, *deposit);
+        suite.deposit(user, *deposit).unwrap();
+    }
+
+    suite.advance_time(7 * DAY);
+    suite.compound().unwrap();
+    let vault_nasset = suite.vault_nasset();
+    let total_deposit: u128 = deposits.iter().sum();
+
+    for (user, deposit) in users.iter().zip(deposits.iter()) {
+        suite.withdraw_all(user).unwrap();
+        //everyone got the same yield on deposit
+        let expected = vault_nasset * deposit / total_deposit;
+        let received = suite.nasset_balance(user);
+        assert!((expected - 1..=expected + 1).contains(&received));
+    }
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+}
+
+#[test]
+fn interleaved_deposits_compounds_and_withdrawals() {
+    let mut suite = Suite::init();
+    let users = ["user1", "user2", "user3", "user4"];
+    for user in users.iter() {
+        suite.mint_nasset(user, 10_000_000_000);
+    }
+
+    let mut deposited = [0u128; 4];
+    let mut withdrawn = [0u128; 4];
+    for day in 0..12usize {
+        let user = day % users.len();
+        let amount = 100_000_000 * (day as u128 + 1);
+        suite.deposit(users[user], amount).unwrap();
+        deposited[user] += amount;
+
+        suite.advance_time(DAY);
+        if day % 3 == 2 {
+            suite.compound().unwrap();
+        }
+
+        //partial withdraw of another user, it compounds first
+        let other = (day + 2) % users.len();
+        let auto_nasset_balance = suite.auto_nasset_balance(users[other]);
+        if auto_nasset_balance > 1 {
+            let nasset_before = suite.nasset_balance(users[other]);
+            suite
+                .withdraw(users[other], auto_nasset_balance / 2)
+                .unwrap();
+            withdrawn[other] += suite.nasset_balance(users[other]) - nasset_before;
+        }
+        suite.advance_time(DAY / 2);
+    }
+
+    for (i, user) in users.iter().enumerate() {
+        let nasset_before = suite.nasset_balance(user);
+        suite.withdraw_all(user).unwrap();
+        withdrawn[i] += suite.nasset_balance(user) - nasset_before;
+        //no withdrawal fee, every farmer earned something
+        assert!(withdrawn[i] > deposited[i]);
+    }
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+
+    //all bought nAsset came from the pair and ended up with farmers
+    let pair = suite.psi_to_nasset_pair.to_string();
+    let nasset_bought = PAIR_NASSET_RESERVE - suite.nasset_balance(&pair);
+    let total_withdrawn: u128 = withdrawn.iter().sum();
+    let total_deposited: u128 = deposited.iter().sum();
+    assert_eq!(total_withdrawn, total_deposited + nasset_bought);
+    assert_eq!(suite.stats().nasset_acquired.u128(), nasset_bought);
+}
+
+#[test]
+fn embedded_share_token_lifecycle() {
+    let mut suite = Suite::init_with_embedded_share_token();
+    assert_eq!(suite.auto_nasset_token, suite.autocompounder);
+    suite.mint_nasset("user1", 1_000_000_000);
+    suite.mint_nasset("user2", 3_000_000_000);
+    suite.deposit("user1", 1_000_000_000).unwrap();
+    suite.deposit("user2", 3_000_000_000).unwrap();
+
+    suite.advance_time(DAY);
+    let pair = suite.psi_to_nasset_pair.to_string();
+    let expected_bought = compute_swap(
+        suite.psi_balance(&pair).into(),
+        suite.nasset_balance(&pair).into(),
+        Uint128::from(4_000 * 864_000u128),
+    )
+    .return_amount
+    .u128();
+    suite.compound().unwrap();
+    assert_eq!(suite.vault_nasset(), 4_000_000_000 + expected_bought);
+
+    suite.withdraw_all("user1").unwrap();
+    suite.withdraw_all("user2").unwrap();
+    assert_eq!(
+        suite.nasset_balance("user1") + suite.nasset_balance("user2"),
+        4_000_000_000 + expected_bought
+    );
+    assert_eq!(
+        suite.nasset_balance("user1"),
+        1_000_000_000 + expected_bought / 4
+    );
+    assert_eq!(suite.vault_nasset(), 0);
+    assert_eq!(suite.auto_nasset_supply(), 0);
+}
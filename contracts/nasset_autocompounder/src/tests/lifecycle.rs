@@ -0,0 +1,502 @@
+use crate::msg::{QueryMsg, WithdrawalFeeConfig, WithdrawalMode};
+use crate::testing::{Sdk, MAX_TOTAL_NASSET, NASSET_TOKEN_ADDR};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::{from_binary, CosmosMsg, Decimal, Response, Uint128, WasmMsg};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, TokenInfoResponse};
+use proptest::prelude::*;
+use proptest::test_runner::{Config, RngAlgorithm, TestCaseError, TestRng, TestRunner};
+use serde::de::DeserializeOwned;
+
+// random sequences of contract calls on randomly configured vault: deposit caps,
+// withdrawal fee, instant or unbonding withdrawals and compounding between actions.
+// anAsset is embedded so supply and balances are real contract state and only
+// nAsset balance is mocked.
+// Failed run prints its seed, rerun it with 'LIFECYCLE_SEED=<seed> cargo test lifecycle'
+const CASES: u32 = 64;
+const DEFAULT_SEED: u64 = 0x6e61_7373_6574;
+const USERS: [&str; 4] = ["addr0100", "addr0101", "addr0102", "addr0103"];
+const TREASURY_ADDR: &str = "addr0200";
+const DECAY_PERIOD: u64 = 1_000;
+const UNBONDING_PERIOD: u64 = 500;
+
+#[derive(Clone, Debug)]
+struct Setup {
+    max_total_nasset: u128,
+    max_nasset_per_user: Option<u128>,
+    max_fee_percent: u64,
+    // withdrawal fee stays in the vault otherwise
+    fee_to_treasury: bool,
+    unbonding: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Action {
+    Deposit {
+        user: usize,
+        amount: u128,
+    },
+    // withdraw (or unbond) given percent of user's anAsset,
+    // 'profit' is bought on compound before withdraw
+    Withdraw {
+        user: usize,
+        percent: u128,
+        profit: u128,
+    },
+    // claim released unbondings, 'profit' is bought on compound before withdraw
+    Claim {
+        user: usize,
+        profit: u128,
+    },
+    Compound {
+        profit: u128,
+    },
+    // nAsset sent directly to autocompounder
+    Donate {
+        amount: u128,
+    },
+    // withdrawal fee decays and unbondings are released
+    Wait {
+        seconds: u64,
+    },
+}
+
+fn setup_strategy() -> impl Strategy<Value = Setup> {
+    (
+        prop_oneof![
+            Just(MAX_TOTAL_NASSET),
+            1_000_000_000_000..5_000_000_000_000u128
+        ],
+        proptest::option::of(500_000_000_000..2_000_000_000_000u128),
+        0..=10u64,
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(
+                max_total_nasset,
+                max_nasset_per_user,
+                max_fee_percent,
+                fee_to_treasury,
+                unbonding,
+            )| {
+                Setup {
+                    max_total_nasset,
+                    max_nasset_per_user,
+                    max_fee_percent,
+                    fee_to_treasury,
+                    unbonding,
+                }
+            },
+        )
+}
+
+fn action_strategy() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => (0..USERS.len(), 1..1_000_000_000_000u128)
+            .prop_map(|(user, amount)| Action::Deposit { user, amount }),
+        3 => (0..USERS.len(), 1..=100u128, 0..10_000_000_000u128)
+            .prop_map(|(user, percent, profit)| Action::Withdraw { user, percent, profit }),
+        1 => (0..USERS.len(), 0..10_000_000_000u128)
+            .prop_map(|(user, profit)| Action::Claim { user, profit }),
+        1 => (0..10_000_000_000u128).prop_map(|profit| Action::Compound { profit }),
+        1 => (1..10_000_000_000u128).prop_map(|amount| Action::Donate { amount }),
+        1 => (0..=600u64).prop_map(|seconds| Action::Wait { seconds }),
+    ]
+}
+
+fn lifecycle_seed() -> u64 {
+    match std::env::var("LIFECYCLE_SEED") {
+        Ok(seed) => seed.parse().expect("LIFECYCLE_SEED must be u64"),
+        Err(_) => DEFAULT_SEED,
+    }
+}
+
+fn run_with_seed(seed: u64) {
+    let mut seed_bytes = [0u8; 32];
+    for chunk in seed_bytes.chunks_mut(8) {
+        chunk.copy_from_slice(&seed.to_le_bytes());
+    }
+
+    let config = Config {
+        cases: CASES,
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let mut runner = TestRunner::new_with_rng(
+        config,
+        TestRng::from_seed(RngAlgorithm::ChaCha, &seed_bytes),
+    );
+    let strategy = (
+        setup_strategy(),
+        proptest::collection::vec(action_strategy(), 1..48),
+    );
+    if let Err(err) = runner.run(&strategy, |(setup, actions)| {
+        check_lifecycle(&setup, &actions)
+    }) {
+        panic!("{}\nreproduce with LIFECYCLE_SEED={}", err, seed);
+    }
+}
+
+#[test]
+fn lifecycle_keeps_share_accounting_invariants() {
+    run_with_seed(lifecycle_seed());
+}
+
+struct Model {
+    sdk: Sdk,
+    setup: Setup,
+    vault_nasset: u128,
+    shares: [u128; 4],
+    // (anAsset amount, release time)
+    unbondings: [Vec<(u128, u64)>; 4],
+    last_deposit_time: [Option<u64>; 4],
+}
+
+impl Model {
+    fn new(setup: &Setup) -> Self {
+        let mut instantiate_msg = Sdk::instantiate_msg();
+        instantiate_msg.max_total_nasset = Uint128::from(setup.max_total_nasset);
+        instantiate_msg.max_nasset_per_user = setup.max_nasset_per_user.map(Uint128::from);
+        instantiate_msg.withdrawal_fee = Some(WithdrawalFeeConfig {
+            max_fee: Decimal::percent(setup.max_fee_percent),
+            decay_period: DECAY_PERIOD,
+            treasury_addr: setup.fee_to_treasury.then(|| TREASURY_ADDR.to_string()),
+        });
+        if setup.unbonding {
+            instantiate_msg.withdrawal_mode = Some(WithdrawalMode::Unbonding {
+                unbonding_period: UNBONDING_PERIOD,
+            });
+        }
+
+        let sdk = Sdk::builder()
+            .instantiate_msg(instantiate_msg)
+            .share_token_mode(crate::msg::ShareTokenMode::Embedded {})
+            .build();
+
+        Model {
+            sdk,
+            setup: setup.clone(),
+            vault_nasset: 0,
+            shares: [0; 4],
+            unbondings: Default::default(),
+            last_deposit_time: [None; 4],
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.sdk.env.block.time.seconds()
+    }
+
+    fn supply(&self) -> u128 {
+        let response: TokenInfoResponse = query(&self.sdk, QueryMsg::TokenInfo {});
+        response.total_supply.u128()
+    }
+
+    fn balance(&self, address: &str) -> u128 {
+        let response: BalanceResponse = query(
+            &self.sdk,
+            QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        );
+        response.balance.u128()
+    }
+
+    fn locked(&self) -> u128 {
+        self.unbondings
+            .iter()
+            .flatten()
+            .map(|(amount, _)| amount)
+            .sum()
+    }
+
+    fn set_vault_nasset(&mut self, amount: u128) {
+        self.vault_nasset = amount;
+        self.sdk.set_nasset_balance(Uint256::from(amount));
+    }
+
+    // fee = amount * max_fee * (decay_period - seconds_since_deposit) / decay_period
+    fn expected_fee(&self, user: usize, amount: u128) -> u128 {
+        let decay_left = match self.last_deposit_time[user] {
+            Some(last_deposit_time) => DECAY_PERIOD.saturating_sub(self.now() - last_deposit_time),
+            None => DECAY_PERIOD,
+        };
+        (Uint128::from(amount) * Decimal::percent(self.setup.max_fee_percent))
+            .multiply_ratio(decay_left, DECAY_PERIOD)
+            .u128()
+    }
+
+    // whether contract has to reject deposit
+    fn deposit_rejected(&self, user: usize, amount: u128, supply: u128) -> bool {
+        let nasset_before = self.vault_nasset;
+        if nasset_before + amount > self.setup.max_total_nasset {
+            return true;
+        }
+
+        let user_nasset = if supply == 0 {
+            0
+        } else {
+            Uint128::from(nasset_before)
+                .multiply_ratio(self.shares[user], supply)
+                .u128()
+        };
+        if let Some(max_nasset_per_user) = self.setup.max_nasset_per_user {
+            if user_nasset + amount > max_nasset_per_user {
+                return true;
+            }
+        }
+
+        // too small deposit to mint at least one anAsset
+        supply != 0
+            && Uint128::from(amount)
+                .multiply_ratio(supply, nasset_before)
+                .is_zero()
+    }
+
+    // withdraws 'to_burn' anAsset of 'user' from vault holding 'nasset_before' nAsset,
+    // withdrawal chain is processed by 'withdraw'
+    fn check_withdraw(
+        &mut self,
+        user: usize,
+        to_burn: u128,
+        profit: u128,
+        supply_before: u128,
+        withdraw: impl FnOnce(&mut Sdk) -> Result<Response, TestCaseError>,
+    ) -> Result<(), TestCaseError> {
+        let response = withdraw(&mut self.sdk)?;
+        let sent_to_user = nasset_sent(&response, USERS[user]);
+        let sent_to_treasury = nasset_sent(&response, TREASURY_ADDR);
+        let fee: u128 = attribute(&response, "withdrawal_fee").parse().unwrap();
+        let withdrawn = sent_to_user + fee;
+
+        // compound happens before withdraw, nobody gets more than his part
+        let nasset_after_compound = self.vault_nasset + profit;
+        let due = Uint128::from(nasset_after_compound)
+            .multiply_ratio(to_burn, supply_before)
+            .u128();
+        prop_assert!(withdrawn <= due, "withdrawn {} but due {}", withdrawn, due);
+        prop_assert!(
+            due - withdrawn <= 1,
+            "withdrawn {} but due {}",
+            withdrawn,
+            due
+        );
+
+        // fee is charged by user's own last deposit
+        prop_assert_eq!(self.expected_fee(user, withdrawn), fee);
+        if self.setup.fee_to_treasury {
+            prop_assert_eq!(fee, sent_to_treasury);
+        } else {
+            prop_assert_eq!(0, sent_to_treasury);
+        }
+
+        self.set_vault_nasset(nasset_after_compound - sent_to_user - sent_to_treasury);
+        Ok(())
+    }
+}
+
+fn query<T: DeserializeOwned>(sdk: &Sdk, msg: QueryMsg) -> T {
+    from_binary(&crate::contract::query(sdk.deps.as_ref(), sdk.env.clone(), msg).unwrap()).unwrap()
+}
+
+fn nasset_sent(response: &Response, recipient_addr: &str) -> u128 {
+    response
+        .messages
+        .iter()
+        .filter_map(|sub_msg| match &sub_msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == NASSET_TOKEN_ADDR => match from_binary(msg).unwrap() {
+                Cw20ExecuteMsg::Transfer { recipient, amount } if recipient == recipient_addr => {
+                    Some(amount.u128())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .sum()
+}
+
+fn attribute(response: &Response, key: &str) -> String {
+    response
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.clone())
+        .unwrap()
+}
+
+fn to_test_err<E: ToString>(err: E) -> TestCaseError {
+    TestCaseError::fail(err.to_string())
+}
+
+fn check_lifecycle(setup: &Setup, actions: &[Action]) -> Result<(), TestCaseError> {
+    let mut model = Model::new(setup);
+
+    for action in actions {
+        let (nasset_before, supply_before) = (model.vault_nasset, model.supply());
+
+        match *action {
+            Action::Deposit { user, amount } => {
+                let rejected = model.deposit_rejected(user, amount, supply_before);
+                // cw20 'Send' moves nAsset before autocompounder 'Receive'
+                model.set_vault_nasset(nasset_before + amount);
+                let res = model.sdk.user_deposit(USERS[user], Uint128::from(amount));
+                prop_assert_eq!(rejected, res.is_err(), "deposit result: {:?}", res);
+                match res {
+                    Ok(_) => {
+                        let minted = model.balance(USERS[user]) - model.shares[user];
+                        // depositor never gets more than his part of the vault
+                        if supply_before != 0 {
+                            prop_assert!(
+                                Uint256::from(minted) * Uint256::from(nasset_before)
+                                    <= Uint256::from(amount) * Uint256::from(supply_before)
+                            );
+                        }
+                        model.shares[user] += minted;
+                        model.last_deposit_time[user] = Some(model.now());
+                    }
+                    // transaction is reverted
+                    Err(_) => model.set_vault_nasset(nasset_before),
+                }
+            }
+
+            Action::Withdraw {
+                user,
+                percent,
+                profit,
+            } => {
+                let to_burn = model.shares[user] * percent / 100;
+                if to_burn == 0 {
+                    continue;
+                }
+
+                if model.setup.unbonding {
+                    // anAsset is locked on autocompounder until claim
+                    let response = model
+                        .sdk
+                        .user_send_withdraw_embedded(USERS[user], Uint128::from(to_burn))
+                        .map_err(to_test_err)?;
+                    prop_assert!(response.messages.is_empty());
+                    let release_at = model.now() + UNBONDING_PERIOD;
+                    model.unbondings[user].push((to_burn, release_at));
+                } else {
+                    model.check_withdraw(user, to_burn, profit, supply_before, |sdk| {
+                        sdk.user_withdraw_embedded(
+                            USERS[user],
+                            Uint128::from(to_burn),
+                            Uint256::from(profit),
+                        )
+                        .map_err(to_test_err)
+                    })?;
+                }
+                model.shares[user] -= to_burn;
+            }
+
+            Action::Claim { user, profit } => {
+                let now = model.now();
+                let (released, unbonding): (Vec<_>, Vec<_>) = model.unbondings[user]
+                    .iter()
+                    .partition(|(_, release_at)| *release_at <= now);
+                let to_burn: u128 = released.iter().map(|(amount, _)| amount).sum();
+                if to_burn == 0 {
+                    prop_assert!(model.sdk.user_claim(USERS[user], Uint256::zero()).is_err());
+                    continue;
+                }
+
+                model.check_withdraw(user, to_burn, profit, supply_before, |sdk| {
+                    sdk.user_claim(USERS[user], Uint256::from(profit))
+                        .map_err(to_test_err)
+                })?;
+                model.unbondings[user] = unbonding;
+            }
+
+            Action::Compound { profit } => {
+                model
+                    .sdk
+                    .user_send_compound(Uint256::from(profit))
+                    .map_err(to_test_err)?;
+                model.vault_nasset += profit;
+            }
+
+            Action::Donate { amount } => model.set_vault_nasset(nasset_before + amount),
+
+            Action::Wait { seconds } => model.sdk.increase_block_time(seconds),
+        }
+
+        // cnAsset supply is the sum of balances, unbonding anAsset is held by autocompounder
+        let supply = model.supply();
+        for (user, address) in USERS.iter().enumerate() {
+            prop_assert_eq!(model.balance(address), model.shares[user]);
+        }
+        prop_assert_eq!(model.balance(MOCK_CONTRACT_ADDR), model.locked());
+        prop_assert_eq!(supply, model.shares.iter().sum::<u128>() + model.locked());
+
+        // anAsset starts at 1 nAsset and never gets cheaper
+        prop_assert!(model.vault_nasset >= supply);
+        if supply_before != 0 && supply != 0 {
+            prop_assert!(
+                Uint256::from(model.vault_nasset) * Uint256::from(supply_before)
+                    >= Uint256::from(nasset_before) * Uint256::from(supply),
+                "exchange rate decreased after {:?}",
+                action
+            );
+        }
+    }
+
+    // everybody exits after fee decayed, last one takes the rest.
+    // nAsset which came while supply was zero belongs to the next depositor
+    let has_holders = model.supply() != 0;
+    for (user, address) in USERS.iter().enumerate() {
+        let shares = model.shares[user];
+        if shares == 0 {
+            continue;
+        }
+
+        if model.setup.unbonding {
+            model
+                .sdk
+                .user_send_withdraw_embedded(address, Uint128::from(shares))
+                .map_err(to_test_err)?;
+            let release_at = model.now() + UNBONDING_PERIOD;
+            model.unbondings[user].push((shares, release_at));
+        } else {
+            model.sdk.increase_block_time(DECAY_PERIOD);
+            let supply = model.supply();
+            model.check_withdraw(user, shares, 0, supply, |sdk| {
+                sdk.user_withdraw_embedded(address, Uint128::from(shares), Uint256::zero())
+                    .map_err(to_test_err)
+            })?;
+        }
+        model.shares[user] = 0;
+    }
+
+    model
+        .sdk
+        .increase_block_time(DECAY_PERIOD.max(UNBONDING_PERIOD));
+    for (user, address) in USERS.iter().enumerate() {
+        let to_burn: u128 = model.unbondings[user]
+            .iter()
+            .map(|(amount, _)| amount)
+            .sum();
+        if to_burn == 0 {
+            continue;
+        }
+
+        let supply = model.supply();
+        model.check_withdraw(user, to_burn, 0, supply, |sdk| {
+            sdk.user_claim(address, Uint256::zero())
+                .map_err(to_test_err)
+        })?;
+        model.unbondings[user].clear();
+    }
+
+    prop_assert_eq!(model.supply(), 0);
+    if has_holders {
+        prop_assert_eq!(model.vault_nasset, 0);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::msg::{MigrateMsg, WithdrawalMode};
+use crate::state::{load_config, Config, WithdrawalFee};
+
+use crate::testing::{
+    mock_dependencies, Sdk, AUTO_NASSET_TOKEN_ADDR, GOVERNANCE_CONTRACT_ADDR, NASSET_TOKEN_ADDR,
+    NASSET_TOKEN_REWARDS_ADDR, PSI_TOKEN_ADDR, PSI_TO_NASSET_PAIR_ADDR,
+};
+
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, StdError, Storage, Uint128};
+
+const CONFIG_UPDATE_DELAY: u64 = 86_400;
+
+// config as it is stored by version without timelock
+fn store_legacy_config(storage: &mut dyn Storage) {
+    storage.set(
+        b"config",
+        format!(
+            r#"{{"nasset_token":"{}","auto_nasset_token":"{}","psi_token":"{}","psi_to_nasset_pair":"{}","governance_contract":"{}","nasset_token_rewards":"{}"}}"#,
+            NASSET_TOKEN_ADDR,
+            AUTO_NASSET_TOKEN_ADDR,
+            PSI_TOKEN_ADDR,
+            PSI_TO_NASSET_PAIR_ADDR,
+            GOVERNANCE_CONTRACT_ADDR,
+            NASSET_TOKEN_REWARDS_ADDR
+        )
+        .as_bytes(),
+    );
+}
+
+#[test]
+fn migrate_legacy_config() {
+    let mut deps = mock_dependencies(&[]);
+    store_legacy_config(&mut deps.storage);
+    assert!(load_config(&deps.storage).is_err());
+
+    crate::contract::migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            config_update_delay: Some(CONFIG_UPDATE_DELAY),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        Config {
+            nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+            auto_nasset_token: Addr::unchecked(AUTO_NASSET_TOKEN_ADDR),
+            psi_token: Addr::unchecked(PSI_TOKEN_ADDR),
+            psi_to_nasset_pair: Addr::unchecked(PSI_TO_NASSET_PAIR_ADDR),
+            governance_contract: Addr::unchecked(GOVERNANCE_CONTRACT_ADDR),
+            nasset_token_rewards: Addr::unchecked(NASSET_TOKEN_REWARDS_ADDR),
+            config_update_delay: CONFIG_UPDATE_DELAY,
+            max_total_nasset: Uint128::MAX,
+            max_nasset_per_user: None,
+            withdrawal_fee: WithdrawalFee::none(),
+            withdrawal_mode: WithdrawalMode::Instant {},
+            router: None,
+            paused: false,
+        },
+        load_config(&deps.storage).unwrap()
+    );
+}
+
+#[test]
+fn fail_to_migrate_legacy_config_without_delay() {
+    let mut deps = mock_dependencies(&[]);
+    store_legacy_config(&mut deps.storage);
+
+    let result = crate::contract::migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            config_update_delay: None,
+        },
+    );
+    assert_eq!(
+        Err(StdError::generic_err(
+            "config_update_delay is required to migrate legacy config"
+        )),
+        result
+    );
+}
+
+#[test]
+fn migrate_current_config_keeps_it() {
+    let mut sdk = Sdk::init();
+    let config = load_config(&sdk.deps.storage).unwrap();
+
+    crate::contract::migrate(
+        sdk.deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            config_update_delay: Some(1),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(config, load_config(&sdk.deps.storage).unwrap());
+}
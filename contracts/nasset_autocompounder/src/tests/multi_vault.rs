@@ -0,0 +1,694 @@
+use crate::msg::{
+    AstroportCw20HookMsg, AutoNassetValueResponse, Cw20HookMsg, DepositCapacityResponse,
+    ExecuteMsg, GovernanceMsg, NAssetTokenRewardsAnyoneMsg, NAssetTokenRewardsExecuteMsg,
+    NassetValueResponse, NewVault, PendingConfigUpdateResponse, PendingRewardsResponse, QueryMsg,
+    SimulateCompoundResponse, SimulateWithdrawResponse, StatsResponse, VaultAssetsResponse,
+    VaultResponse, VaultSharesResponse, VaultsResponse, WithdrawalFeeConfig, WithdrawalMode,
+};
+use crate::SubmsgIds;
+
+use crate::testing::{
+    Sdk, AUTO_NASSET_TOKEN_ADDR, CONFIG_UPDATE_DELAY, CW20_TOKEN_CODE_ID, NASSET_TOKEN_ADDR,
+    PSI_TOKEN_ADDR,
+};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{
+    from_binary, to_binary, CosmosMsg, Decimal, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use nasset_autocompounder_interface::reply_response::MsgInstantiateContractResponse;
+use serde::de::DeserializeOwned;
+
+const VAULT_NASSET_TOKEN_ADDR: &str = "addr0011";
+const VAULT_PSI_TO_NASSET_PAIR_ADDR: &str = "addr0012";
+const VAULT_NASSET_TOKEN_REWARDS_ADDR: &str = "addr0013";
+const VAULT_AUTO_NASSET_TOKEN_ADDR: &str = "addr0014";
+const VAULT_MAX_TOTAL_NASSET: u128 = 1_000_000;
+
+fn send_add_vault(sdk: &mut Sdk, nasset_token_addr: &str) -> StdResult<Response> {
+    sdk.governance_update(GovernanceMsg::AddVault {
+        nasset_token_addr: nasset_token_addr.to_string(),
+        psi_to_nasset_pair_addr: VAULT_PSI_TO_NASSET_PAIR_ADDR.to_string(),
+        nasset_token_rewards_addr: VAULT_NASSET_TOKEN_REWARDS_ADDR.to_string(),
+        cw20_token_code_id: CW20_TOKEN_CODE_ID,
+        collateral_token_symbol: "ETH".to_string(),
+        max_total_nasset: Uint128::from(VAULT_MAX_TOTAL_NASSET),
+        max_nasset_per_user: None,
+    })
+}
+
+fn add_vault(sdk: &mut Sdk) {
+    let response = send_add_vault(sdk, VAULT_NASSET_TOKEN_ADDR).unwrap();
+    assert_eq!(1, response.messages.len());
+    assert_eq!(SubmsgIds::InitANAsset.id(), response.messages[0].id);
+
+    let reply_msg = Reply {
+        id: SubmsgIds::InitANAsset.id(),
+        result: cosmwasm_std::ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(
+                MsgInstantiateContractResponse::new(VAULT_AUTO_NASSET_TOKEN_ADDR).to_binary(),
+            ),
+        }),
+    };
+    crate::contract::reply(sdk.deps.as_mut(), sdk.env.clone(), reply_msg).unwrap();
+}
+
+fn receive_cw20(
+    sdk: &mut Sdk,
+    token: &str,
+    sender: &str,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> StdResult<Response> {
+    crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&msg).unwrap(),
+        }),
+    )
+}
+
+fn reply(sdk: &mut Sdk, id: SubmsgIds) -> Response {
+    let reply_msg = Reply {
+        id: id.id(),
+        result: cosmwasm_std::ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    crate::contract::reply(sdk.deps.as_mut(), sdk.env.clone(), reply_msg).unwrap()
+}
+
+fn claim_rewards_msg(rewards_contract: &str, reply_on_success: bool) -> SubMsg {
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: rewards_contract.to_string(),
+        msg: to_binary(&NAssetTokenRewardsExecuteMsg::Anyone {
+            anyone_msg: NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient: None },
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+
+    if reply_on_success {
+        SubMsg::reply_on_success(msg, SubmsgIds::PsiClaimed.id())
+    } else {
+        SubMsg::reply_always(msg, SubmsgIds::PsiClaimed.id())
+    }
+}
+
+fn sell_psi_msg(amount: u128) -> SubMsg {
+    SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: PSI_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                amount: Uint128::from(amount),
+                contract: VAULT_PSI_TO_NASSET_PAIR_ADDR.to_string(),
+                msg: to_binary(&AstroportCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: None,
+                    to: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        },
+        SubmsgIds::PsiSold.id(),
+    )
+}
+
+fn token_msg(token: &str, msg: Cw20ExecuteMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&msg).unwrap(),
+        funds: vec![],
+    }))
+}
+
+fn query<T: DeserializeOwned>(sdk: &Sdk, msg: QueryMsg) -> T {
+    let query_res = crate::contract::query(sdk.deps.as_ref(), sdk.env.clone(), msg).unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+fn update_config_msg(
+    withdrawal_fee: Option<WithdrawalFeeConfig>,
+    withdrawal_mode: Option<WithdrawalMode>,
+) -> GovernanceMsg {
+    GovernanceMsg::UpdateConfig {
+        psi_token_addr: None,
+        psi_to_nasset_pair_addr: None,
+        nasset_token_rewards_addr: None,
+        config_update_delay: None,
+        withdrawal_fee,
+        withdrawal_mode,
+    }
+}
+
+fn withdrawal_fee(max_fee: Decimal) -> Option<WithdrawalFeeConfig> {
+    Some(WithdrawalFeeConfig {
+        max_fee,
+        decay_period: 1_000,
+        treasury_addr: None,
+    })
+}
+
+fn unbonding() -> Option<WithdrawalMode> {
+    Some(WithdrawalMode::Unbonding {
+        unbonding_period: 1_000,
+    })
+}
+
+fn query_vault(sdk: &Sdk, nasset_token_addr: &str) -> StdResult<VaultResponse> {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::Vault {
+            nasset_token_addr: nasset_token_addr.to_string(),
+        },
+    )?;
+    from_binary(&query_res)
+}
+
+#[test]
+fn add_vault_registers_vault_after_anasset_instantiated() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+
+    let vault = VaultResponse {
+        nasset_token: VAULT_NASSET_TOKEN_ADDR.to_string(),
+        auto_nasset_token: VAULT_AUTO_NASSET_TOKEN_ADDR.to_string(),
+        psi_to_nasset_pair: VAULT_PSI_TO_NASSET_PAIR_ADDR.to_string(),
+        nasset_token_rewards: VAULT_NASSET_TOKEN_REWARDS_ADDR.to_string(),
+        max_total_nasset: Uint128::from(VAULT_MAX_TOTAL_NASSET),
+        max_nasset_per_user: None,
+        total_nasset: Uint128::from(300u128),
+        auto_nasset_supply: Uint128::from(100u128),
+    };
+    assert_eq!(vault, query_vault(&sdk, VAULT_NASSET_TOKEN_ADDR).unwrap());
+
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::Vaults {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let vaults: VaultsResponse = from_binary(&query_res).unwrap();
+    assert_eq!(
+        VaultsResponse {
+            vaults: vec![vault]
+        },
+        vaults
+    );
+
+    // primary vault is resolved as well
+    let primary_vault = query_vault(&sdk, NASSET_TOKEN_ADDR).unwrap();
+    assert_eq!(AUTO_NASSET_TOKEN_ADDR, primary_vault.auto_nasset_token);
+}
+
+#[test]
+fn fail_to_add_vault_twice() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+
+    for nasset_token_addr in [VAULT_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR] {
+        assert_eq!(
+            StdError::generic_err("vault for this nAsset already exists"),
+            send_add_vault(&mut sdk, nasset_token_addr).unwrap_err()
+        );
+    }
+}
+
+#[test]
+fn add_vault_is_timelocked() {
+    let mut sdk = Sdk::init();
+    let add_vault_msg = GovernanceMsg::AddVault {
+        nasset_token_addr: VAULT_NASSET_TOKEN_ADDR.to_string(),
+        psi_to_nasset_pair_addr: VAULT_PSI_TO_NASSET_PAIR_ADDR.to_string(),
+        nasset_token_rewards_addr: VAULT_NASSET_TOKEN_REWARDS_ADDR.to_string(),
+        cw20_token_code_id: CW20_TOKEN_CODE_ID,
+        collateral_token_symbol: "ETH".to_string(),
+        max_total_nasset: Uint128::from(VAULT_MAX_TOTAL_NASSET),
+        max_nasset_per_user: None,
+    };
+
+    let response = sdk.governance(add_vault_msg).unwrap();
+    assert!(response.messages.is_empty());
+
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::PendingConfigUpdate {},
+    )
+    .unwrap();
+    let pending: Option<PendingConfigUpdateResponse> = from_binary(&query_res).unwrap();
+    assert_eq!(
+        Some(NewVault {
+            nasset_token_addr: VAULT_NASSET_TOKEN_ADDR.to_string(),
+            psi_to_nasset_pair_addr: VAULT_PSI_TO_NASSET_PAIR_ADDR.to_string(),
+            nasset_token_rewards_addr: VAULT_NASSET_TOKEN_REWARDS_ADDR.to_string(),
+            cw20_token_code_id: CW20_TOKEN_CODE_ID,
+            collateral_token_symbol: "ETH".to_string(),
+            max_total_nasset: Uint128::from(VAULT_MAX_TOTAL_NASSET),
+            max_nasset_per_user: None,
+        }),
+        pending.unwrap().new_vault
+    );
+
+    // anAsset token is instantiated only when update is executed
+    sdk.increase_block_time(CONFIG_UPDATE_DELAY);
+    let response = sdk
+        .governance(GovernanceMsg::ExecuteConfigUpdate {})
+        .unwrap();
+    assert_eq!(1, response.messages.len());
+    assert_eq!(SubmsgIds::InitANAsset.id(), response.messages[0].id);
+}
+
+#[test]
+fn deposit_to_vault_mints_its_anasset() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    let user_address = "addr9999";
+
+    //200 in vault + 100 deposited
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+    let response = receive_cw20(
+        &mut sdk,
+        VAULT_NASSET_TOKEN_ADDR,
+        user_address,
+        100,
+        Cw20HookMsg::Deposit { recipient: None },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![token_msg(
+            VAULT_AUTO_NASSET_TOKEN_ADDR,
+            Cw20ExecuteMsg::Mint {
+                recipient: user_address.to_string(),
+                amount: Uint128::from(50u128),
+            }
+        )]
+    );
+}
+
+#[test]
+fn fail_to_deposit_vault_anasset() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+
+    let res = receive_cw20(
+        &mut sdk,
+        VAULT_AUTO_NASSET_TOKEN_ADDR,
+        "addr9999",
+        100,
+        Cw20HookMsg::Deposit { recipient: None },
+    );
+    assert_eq!(StdError::generic_err("unauthorized"), res.unwrap_err());
+}
+
+#[test]
+fn withdraw_from_vault_sells_only_claimed_psi() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    let user_address = "addr9999";
+
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(200u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+    //PSI which belongs to primary vault
+    sdk.set_psi_balance(Uint256::from(1_000u128));
+
+    let response = receive_cw20(
+        &mut sdk,
+        VAULT_AUTO_NASSET_TOKEN_ADDR,
+        user_address,
+        10,
+        Cw20HookMsg::Withdraw { recipient: None },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![claim_rewards_msg(VAULT_NASSET_TOKEN_REWARDS_ADDR, false)]
+    );
+
+    sdk.set_psi_balance(Uint256::from(1_500u128));
+    let response = reply(&mut sdk, SubmsgIds::PsiClaimed);
+    assert_eq!(response.messages, vec![sell_psi_msg(500)]);
+
+    sdk.set_psi_balance(Uint256::from(1_000u128));
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    let response = reply(&mut sdk, SubmsgIds::PsiSold);
+    assert_eq!(
+        response.messages,
+        vec![
+            token_msg(
+                VAULT_NASSET_TOKEN_ADDR,
+                Cw20ExecuteMsg::Transfer {
+                    recipient: user_address.to_string(),
+                    amount: Uint128::from(30u128),
+                }
+            ),
+            token_msg(
+                VAULT_AUTO_NASSET_TOKEN_ADDR,
+                Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(10u128),
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn compound_vault() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+
+    let response = crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info("addr9999", &[]),
+        ExecuteMsg::Compound {
+            nasset_token_addr: Some(VAULT_NASSET_TOKEN_ADDR.to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![claim_rewards_msg(VAULT_NASSET_TOKEN_REWARDS_ADDR, true)]
+    );
+
+    sdk.set_psi_balance(Uint256::from(700u128));
+    let response = reply(&mut sdk, SubmsgIds::PsiClaimed);
+    assert_eq!(response.messages, vec![sell_psi_msg(700)]);
+}
+
+#[test]
+fn fail_to_compound_unknown_vault() {
+    let mut sdk = Sdk::init();
+
+    let res = crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info("addr9999", &[]),
+        ExecuteMsg::Compound {
+            nasset_token_addr: Some(VAULT_NASSET_TOKEN_ADDR.to_string()),
+        },
+    );
+    assert_eq!(
+        StdError::generic_err(format!("no vault for nAsset {}", VAULT_NASSET_TOKEN_ADDR)),
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn fail_to_add_vault_with_primary_only_features() {
+    let add_vault_err = StdError::generic_err(
+        "additional vaults don't support withdrawal fee and unbonding, disable them first",
+    );
+
+    let mut sdk = Sdk::init();
+    sdk.governance_update(update_config_msg(
+        withdrawal_fee(Decimal::percent(10)),
+        None,
+    ))
+    .unwrap();
+    assert_eq!(
+        add_vault_err,
+        send_add_vault(&mut sdk, VAULT_NASSET_TOKEN_ADDR).unwrap_err()
+    );
+
+    let mut sdk = Sdk::init();
+    sdk.governance_update(update_config_msg(None, unbonding()))
+        .unwrap();
+    assert_eq!(
+        add_vault_err,
+        send_add_vault(&mut sdk, VAULT_NASSET_TOKEN_ADDR).unwrap_err()
+    );
+
+    // zero fee is the same as no fee
+    let mut sdk = Sdk::init();
+    sdk.governance_update(update_config_msg(withdrawal_fee(Decimal::zero()), None))
+        .unwrap();
+    add_vault(&mut sdk);
+}
+
+#[test]
+fn fail_to_enable_primary_only_features_with_additional_vault() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+
+    let update_err = StdError::generic_err(
+        "withdrawal fee and unbonding can't be enabled with additional vaults",
+    );
+    assert_eq!(
+        update_err,
+        sdk.governance(update_config_msg(
+            withdrawal_fee(Decimal::percent(10)),
+            None
+        ))
+        .unwrap_err()
+    );
+    assert_eq!(
+        update_err,
+        sdk.governance(update_config_msg(None, unbonding()))
+            .unwrap_err()
+    );
+
+    sdk.governance_update(update_config_msg(
+        withdrawal_fee(Decimal::zero()),
+        Some(WithdrawalMode::Instant {}),
+    ))
+    .unwrap();
+}
+
+#[test]
+fn vault_queries_select_additional_vault() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+
+    let vault = || Some(VAULT_NASSET_TOKEN_ADDR.to_string());
+    let amount = Uint128::from(30u128);
+    let shares: VaultSharesResponse = query(
+        &sdk,
+        QueryMsg::ConvertToShares {
+            assets: amount,
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(Uint128::from(10u128), shares.shares);
+    let shares: VaultSharesResponse = query(
+        &sdk,
+        QueryMsg::PreviewWithdraw {
+            assets: amount,
+            owner: Some("addr9999".to_string()),
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(Uint128::from(10u128), shares.shares);
+    let assets: VaultAssetsResponse = query(
+        &sdk,
+        QueryMsg::ConvertToAssets {
+            shares: Uint128::from(10u128),
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(amount, assets.assets);
+    let nasset_value: NassetValueResponse = query(
+        &sdk,
+        QueryMsg::NAssetValue {
+            amount,
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!("10", nasset_value.cnasset_amount);
+    let auto_nasset_value: AutoNassetValueResponse = query(
+        &sdk,
+        QueryMsg::AutoNassetValue {
+            amount: Uint128::from(10u128),
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!("30", auto_nasset_value.nasset_amount);
+
+    let total_assets: VaultAssetsResponse = query(
+        &sdk,
+        QueryMsg::TotalAssets {
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(Uint128::from(300u128), total_assets.assets);
+    let capacity: DepositCapacityResponse = query(
+        &sdk,
+        QueryMsg::DepositCapacity {
+            address: None,
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(
+        Uint128::from(VAULT_MAX_TOTAL_NASSET - 300),
+        capacity.remaining_total_nasset
+    );
+    let max_deposit: VaultAssetsResponse = query(
+        &sdk,
+        QueryMsg::MaxDeposit {
+            receiver: None,
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(capacity.remaining_total_nasset, max_deposit.assets);
+
+    // primary vault when not specified
+    let shares: VaultSharesResponse = query(
+        &sdk,
+        QueryMsg::ConvertToShares {
+            assets: amount,
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(amount, shares.shares);
+
+    let res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::TotalAssets {
+            nasset_token_addr: Some("addr0099".to_string()),
+        },
+    );
+    assert_eq!(
+        StdError::generic_err("no vault for nAsset addr0099"),
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn vault_rewards_and_simulations() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+    //PSI and rewards which belong to primary vault
+    sdk.set_psi_balance(Uint256::from(1_000u128));
+    sdk.set_accrued_rewards(Uint256::from(500u128));
+    sdk.set_vault_accrued_rewards(VAULT_NASSET_TOKEN_REWARDS_ADDR, Uint256::from(150u128));
+    sdk.set_vault_psi_to_nasset_price(VAULT_PSI_TO_NASSET_PAIR_ADDR, Decimal::percent(200));
+
+    let vault = || Some(VAULT_NASSET_TOKEN_ADDR.to_string());
+    let pending_rewards: PendingRewardsResponse = query(
+        &sdk,
+        QueryMsg::PendingRewards {
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(
+        PendingRewardsResponse {
+            accrued_psi: Uint128::from(150u128),
+            psi_balance: Uint128::zero(),
+            total_psi: Uint128::from(150u128),
+        },
+        pending_rewards
+    );
+
+    let simulation: SimulateCompoundResponse = query(
+        &sdk,
+        QueryMsg::SimulateCompound {
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(
+        SimulateCompoundResponse {
+            psi_to_sell: Uint128::from(150u128),
+            nasset_out: Uint128::from(300u128),
+            nasset_per_auto_nasset: Decimal::from_ratio(600u128, 100u128),
+        },
+        simulation
+    );
+
+    let simulation: SimulateWithdrawResponse = query(
+        &sdk,
+        QueryMsg::SimulateWithdraw {
+            cnasset_amount: Uint128::from(10u128),
+            address: Some("addr9999".to_string()),
+            nasset_token_addr: vault(),
+        },
+    );
+    assert_eq!(
+        SimulateWithdrawResponse {
+            nasset_amount: Uint128::from(60u128),
+            withdrawal_fee: Uint128::zero(),
+        },
+        simulation
+    );
+}
+
+#[test]
+fn vault_stats_are_kept_separately() {
+    let mut sdk = Sdk::init();
+    add_vault(&mut sdk);
+    let stats = |sdk: &Sdk, nasset_token_addr: Option<&str>| -> StatsResponse {
+        query(
+            sdk,
+            QueryMsg::Stats {
+                nasset_token_addr: nasset_token_addr.map(|addr| addr.to_string()),
+            },
+        )
+    };
+
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::from(50u128));
+    sdk.user_deposit("addr9999", Uint128::from(50u128)).unwrap();
+
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(300u128));
+    sdk.set_vault_auto_nasset_supply(VAULT_AUTO_NASSET_TOKEN_ADDR, Uint256::from(100u128));
+    receive_cw20(
+        &mut sdk,
+        VAULT_NASSET_TOKEN_ADDR,
+        "addr9999",
+        100,
+        Cw20HookMsg::Deposit { recipient: None },
+    )
+    .unwrap();
+
+    crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info("addr9999", &[]),
+        ExecuteMsg::Compound {
+            nasset_token_addr: Some(VAULT_NASSET_TOKEN_ADDR.to_string()),
+        },
+    )
+    .unwrap();
+    sdk.set_psi_balance(Uint256::from(700u128));
+    reply(&mut sdk, SubmsgIds::PsiClaimed);
+    sdk.set_psi_balance(Uint256::zero());
+    sdk.set_vault_nasset_balance(VAULT_NASSET_TOKEN_ADDR, Uint256::from(650u128));
+    reply(&mut sdk, SubmsgIds::PsiSold);
+
+    let vault_stats = stats(&sdk, Some(VAULT_NASSET_TOKEN_ADDR));
+    assert_eq!(Uint128::from(100u128), vault_stats.nasset_deposited);
+    assert_eq!(Uint128::from(700u128), vault_stats.psi_claimed);
+    assert_eq!(Uint128::from(700u128), vault_stats.psi_swapped);
+    assert_eq!(Uint128::from(350u128), vault_stats.nasset_acquired);
+    assert_eq!(1, vault_stats.compounds_count);
+
+    let primary_stats = stats(&sdk, None);
+    assert_eq!(Uint128::from(50u128), primary_stats.nasset_deposited);
+    assert_eq!(0, primary_stats.compounds_count);
+}
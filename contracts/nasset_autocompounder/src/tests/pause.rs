@@ -0,0 +1,88 @@
+use crate::msg::{ConfigResponse, ExecuteMsg, GovernanceMsg, QueryMsg};
+use crate::state::{load_config, may_load_config_update};
+
+use crate::testing::Sdk;
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, StdError, Uint128};
+
+#[test]
+fn fail_to_pause_if_sender_is_not_governance() {
+    let mut sdk = Sdk::init();
+
+    let pause_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::SetPaused { paused: true },
+    };
+    let info = mock_info("addr0010", &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, pause_msg);
+    assert_eq!(StdError::generic_err("unauthorized"), res.err().unwrap());
+    assert!(!load_config(&sdk.deps.storage).unwrap().paused);
+}
+
+#[test]
+fn pause_is_not_timelocked() {
+    let mut sdk = Sdk::init();
+
+    // pending config update neither blocks pause nor is affected by it
+    sdk.governance(GovernanceMsg::UpdateRouter {
+        router_addr: Some("addr0008".to_string()),
+    })
+    .unwrap();
+    let pending = may_load_config_update(&sdk.deps.storage).unwrap();
+
+    sdk.governance(GovernanceMsg::SetPaused { paused: true })
+        .unwrap();
+
+    let query_res =
+        crate::contract::query(sdk.deps.as_ref(), sdk.env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&query_res).unwrap();
+    assert!(config.paused);
+    assert_eq!(None, config.router_addr);
+    assert_eq!(pending, may_load_config_update(&sdk.deps.storage).unwrap());
+}
+
+#[test]
+fn paused_autocompounder_rejects_deposit_and_compound() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::from(100u64));
+    sdk.governance(GovernanceMsg::SetPaused { paused: true })
+        .unwrap();
+
+    let paused_err = StdError::generic_err("autocompounder is paused");
+    assert_eq!(
+        paused_err,
+        sdk.user_deposit("addr9999", Uint128::from(100u64))
+            .unwrap_err()
+    );
+
+    let compound_msg = ExecuteMsg::Compound {
+        nasset_token_addr: None,
+    };
+    let info = mock_info("addr9999", &[]);
+    let res = crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, compound_msg);
+    assert_eq!(paused_err, res.unwrap_err());
+
+    // unpause is instant too
+    sdk.governance(GovernanceMsg::SetPaused { paused: false })
+        .unwrap();
+    sdk.user_deposit("addr9999", Uint128::from(100u64)).unwrap();
+}
+
+#[test]
+fn withdraw_works_while_paused() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u64));
+    sdk.set_nasset_balance(Uint256::from(1_000u64));
+    sdk.governance(GovernanceMsg::SetPaused { paused: true })
+        .unwrap();
+
+    let response = sdk
+        .user_withdraw("addr9999", Uint128::from(100u64), Uint256::zero())
+        .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "action" && attribute.value == "withdraw"));
+}
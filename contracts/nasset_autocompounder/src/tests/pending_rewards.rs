@@ -0,0 +1,32 @@
+use crate::msg::{PendingRewardsResponse, QueryMsg};
+
+use crate::testing::Sdk;
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{from_binary, Uint128};
+
+#[test]
+fn query_pending_rewards() {
+    let mut sdk = Sdk::init();
+    sdk.set_psi_balance(Uint256::from(50u128));
+    sdk.set_accrued_rewards(Uint256::from(150u128));
+
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::PendingRewards {
+            nasset_token_addr: None,
+        },
+    )
+    .unwrap();
+    let pending_rewards: PendingRewardsResponse = from_binary(&query_res).unwrap();
+
+    assert_eq!(
+        PendingRewardsResponse {
+            accrued_psi: Uint128::from(150u128),
+            psi_balance: Uint128::from(50u128),
+            total_psi: Uint128::from(200u128),
+        },
+        pending_rewards
+    );
+}
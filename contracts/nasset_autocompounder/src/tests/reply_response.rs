@@ -0,0 +1,44 @@
+use crate::state::load_config;
+use crate::testing::{mock_dependencies, Sdk};
+use crate::SubmsgIds;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Binary, ContractResult, Reply, SubMsgExecutionResponse};
+
+fn reply(result: ContractResult<SubMsgExecutionResponse>) -> Reply {
+    Reply {
+        id: SubmsgIds::InitANAsset.id(),
+        result,
+    }
+}
+
+fn reply_with_data(data: Option<Binary>) -> Reply {
+    reply(ContractResult::Ok(SubMsgExecutionResponse {
+        events: vec![],
+        data,
+    }))
+}
+
+#[test]
+fn bad_init_token_reply_is_error() {
+    let mut deps = mock_dependencies(&[]);
+    crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr9999", &[]),
+        Sdk::instantiate_msg(),
+    )
+    .unwrap();
+
+    let bad_replies = vec![
+        reply_with_data(Some(Binary::from(vec![0x0a, 0x05]))),
+        reply_with_data(None),
+        reply(ContractResult::Err("out of gas".to_string())),
+    ];
+    for bad_reply in bad_replies {
+        assert!(crate::contract::reply(deps.as_mut(), mock_env(), bad_reply).is_err());
+    }
+
+    let config = load_config(&deps.storage).unwrap();
+    assert_eq!("", config.auto_nasset_token.as_str());
+}
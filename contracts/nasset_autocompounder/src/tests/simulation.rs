@@ -0,0 +1,95 @@
+use crate::msg::{QueryMsg, SimulateCompoundResponse, SimulateWithdrawResponse};
+
+use crate::testing::Sdk;
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{from_binary, Decimal, Uint128};
+
+fn simulate_compound(sdk: &Sdk) -> SimulateCompoundResponse {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::SimulateCompound {
+            nasset_token_addr: None,
+        },
+    )
+    .unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+fn simulate_withdraw(sdk: &Sdk, cnasset_amount: u128) -> SimulateWithdrawResponse {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::SimulateWithdraw {
+            cnasset_amount: Uint128::from(cnasset_amount),
+            address: None,
+            nasset_token_addr: None,
+        },
+    )
+    .unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+#[test]
+fn simulate_compound_sells_accrued_and_contract_psi() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+    sdk.set_psi_balance(Uint256::from(50u128));
+    sdk.set_accrued_rewards(Uint256::from(150u128));
+    sdk.set_psi_to_nasset_price(Decimal::percent(50));
+
+    assert_eq!(
+        SimulateCompoundResponse {
+            psi_to_sell: Uint128::from(200u128),
+            nasset_out: Uint128::from(100u128),
+            nasset_per_auto_nasset: Decimal::from_ratio(1_600u128, 1_000u128),
+        },
+        simulate_compound(&sdk)
+    );
+}
+
+#[test]
+fn simulate_compound_without_rewards() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+    sdk.set_psi_to_nasset_price(Decimal::percent(50));
+
+    assert_eq!(
+        SimulateCompoundResponse {
+            psi_to_sell: Uint128::zero(),
+            nasset_out: Uint128::zero(),
+            nasset_per_auto_nasset: Decimal::from_ratio(1_500u128, 1_000u128),
+        },
+        simulate_compound(&sdk)
+    );
+}
+
+#[test]
+fn simulate_withdraw_includes_harvest() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+    sdk.set_accrued_rewards(Uint256::from(200u128));
+    sdk.set_psi_to_nasset_price(Decimal::percent(50));
+
+    let simulation = simulate_withdraw(&sdk, 100);
+    assert_eq!(
+        SimulateWithdrawResponse {
+            nasset_amount: Uint128::from(160u128),
+            withdrawal_fee: Uint128::zero(),
+        },
+        simulation
+    );
+
+    // real withdraw gives the same amount
+    let response = sdk
+        .user_withdraw("addr9999", Uint128::from(100u128), Uint256::from(100u128))
+        .unwrap();
+    assert!(response
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "nasset_amount_withdrawed" && attr.value == "160"));
+}
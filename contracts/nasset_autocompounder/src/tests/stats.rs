@@ -0,0 +1,104 @@
+use crate::msg::{ExecuteMsg, GovernanceMsg, QueryMsg, StatsResponse, WithdrawalFeeConfig};
+
+use crate::testing::{Sdk, CONFIG_UPDATE_DELAY, GOVERNANCE_CONTRACT_ADDR};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{from_binary, Decimal, Uint128};
+
+fn query_stats(sdk: &Sdk) -> StatsResponse {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::Stats {
+            nasset_token_addr: None,
+        },
+    )
+    .unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+fn set_withdrawal_fee(sdk: &mut Sdk) {
+    let governance_msgs = vec![
+        GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: None,
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: Some(WithdrawalFeeConfig {
+                max_fee: Decimal::percent(10),
+                decay_period: 1_000,
+                treasury_addr: None,
+            }),
+            withdrawal_mode: None,
+        },
+        GovernanceMsg::ExecuteConfigUpdate {},
+    ];
+
+    for governance_msg in governance_msgs {
+        let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+        crate::contract::execute(
+            sdk.deps.as_mut(),
+            sdk.env.clone(),
+            info,
+            ExecuteMsg::Governance { governance_msg },
+        )
+        .unwrap();
+        sdk.increase_block_time(CONFIG_UPDATE_DELAY);
+    }
+}
+
+#[test]
+fn empty_stats() {
+    let sdk = Sdk::init();
+
+    assert_eq!(
+        StatsResponse {
+            psi_claimed: Uint128::zero(),
+            psi_swapped: Uint128::zero(),
+            nasset_acquired: Uint128::zero(),
+            compounds_count: 0,
+            last_compound_time: 0,
+            withdrawal_fees: Uint128::zero(),
+            nasset_deposited: Uint128::zero(),
+            nasset_withdrawn: Uint128::zero(),
+        },
+        query_stats(&sdk)
+    );
+}
+
+#[test]
+fn stats_accumulate_over_lifetime() {
+    let mut sdk = Sdk::init();
+    let user_address = "addr9999";
+    set_withdrawal_fee(&mut sdk);
+
+    //deposit 100
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::from(100u128));
+    sdk.user_deposit(user_address, Uint128::from(100u128))
+        .unwrap();
+    sdk.set_auto_nasset_supply(Uint256::from(100u128));
+
+    //compound with PSI already on balance
+    sdk.set_psi_balance(Uint256::from(1_000u128));
+    sdk.user_send_compound(Uint256::from(50u128)).unwrap();
+
+    //withdraw 10 anAsset, 15 nAsset worth, 10% fee
+    sdk.user_withdraw(user_address, Uint128::from(10u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        StatsResponse {
+            psi_claimed: Uint128::from(511_999_000u128),
+            psi_swapped: Uint128::from(512_000_000u128),
+            nasset_acquired: Uint128::from(50u128),
+            compounds_count: 2,
+            last_compound_time: sdk.env.block.time.seconds(),
+            withdrawal_fees: Uint128::from(1u128),
+            nasset_deposited: Uint128::from(100u128),
+            nasset_withdrawn: Uint128::from(14u128),
+        },
+        query_stats(&sdk)
+    );
+}
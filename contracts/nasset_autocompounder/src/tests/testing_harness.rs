@@ -0,0 +1,107 @@
+use crate::msg::{PendingRewardsResponse, QueryMsg, ShareTokenMode, SimulateCompoundResponse};
+use crate::testing::{
+    assert_auto_nasset_burned, assert_auto_nasset_minted, assert_claim_psi, assert_nasset_sent,
+    assert_sell_psi, Sdk,
+};
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::{from_binary, Decimal, ReplyOn, Uint128};
+
+fn query<T: serde::de::DeserializeOwned>(sdk: &Sdk, msg: QueryMsg) -> T {
+    from_binary(&crate::contract::query(sdk.deps.as_ref(), sdk.env.clone(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn builder_sets_balances_pending_psi_and_price() {
+    let sdk = Sdk::builder()
+        .nasset_balance(Uint128::from(1_000u128))
+        .auto_nasset_supply(Uint128::from(500u128))
+        .auto_nasset_balance("addr9999", Uint128::from(500u128))
+        .psi_balance(Uint128::from(20u128))
+        .accrued_rewards(Uint128::from(80u128))
+        .psi_to_nasset_price(Decimal::percent(50))
+        .build();
+
+    let total_assets: crate::msg::VaultAssetsResponse = query(
+        &sdk,
+        QueryMsg::TotalAssets {
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(total_assets.assets, Uint128::from(1_000u128));
+
+    let pending_rewards: PendingRewardsResponse = query(
+        &sdk,
+        QueryMsg::PendingRewards {
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(pending_rewards.total_psi, Uint128::from(100u128));
+
+    let simulation: SimulateCompoundResponse = query(
+        &sdk,
+        QueryMsg::SimulateCompound {
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(simulation.nasset_out, Uint128::from(50u128));
+    assert_eq!(simulation.nasset_per_auto_nasset, Decimal::percent(210));
+}
+
+#[test]
+fn builder_with_embedded_share_token() {
+    let sdk = Sdk::builder()
+        .share_token_mode(ShareTokenMode::Embedded {})
+        .build();
+
+    let config: crate::msg::ConfigResponse = query(&sdk, QueryMsg::Config {});
+    assert_eq!(config.auto_nasset_token_addr, MOCK_CONTRACT_ADDR);
+}
+
+#[test]
+fn reply_helpers_drive_compound_and_withdraw() {
+    let mut sdk = Sdk::builder()
+        .nasset_balance(Uint128::from(100u128))
+        .auto_nasset_supply(Uint128::from(100u128))
+        .build();
+
+    let response = sdk
+        .user_send_withdraw("addr9999", Uint128::from(50u128))
+        .unwrap();
+    assert_claim_psi(&response, ReplyOn::Always);
+
+    let response = sdk.reply_psi_claimed(Uint256::from(10u128)).unwrap();
+    assert_sell_psi(&response, Uint128::from(10u128));
+
+    let response = sdk.reply_psi_sold(Uint256::from(100u128)).unwrap();
+    assert_nasset_sent(&response, "addr9999", Uint128::from(100u128));
+    assert_auto_nasset_burned(&response, Uint128::from(50u128));
+}
+
+#[test]
+fn failed_claim_reply_withdraws_without_compound() {
+    let mut sdk = Sdk::builder()
+        .nasset_balance(Uint128::from(100u128))
+        .auto_nasset_supply(Uint128::from(100u128))
+        .build();
+
+    sdk.user_send_withdraw("addr9999", Uint128::from(100u128))
+        .unwrap();
+    let response = sdk
+        .reply_psi_claim_failed("No rewards have accrued yet")
+        .unwrap();
+    assert_nasset_sent(&response, "addr9999", Uint128::from(100u128));
+    assert_auto_nasset_burned(&response, Uint128::from(100u128));
+}
+
+#[test]
+fn deposit_mint_assertion() {
+    let mut sdk = Sdk::builder()
+        .nasset_balance(Uint128::from(100u128))
+        .build();
+
+    let response = sdk
+        .user_deposit("addr9999", Uint128::from(100u128))
+        .unwrap();
+    assert_auto_nasset_minted(&response, "addr9999", Uint128::from(100u128));
+}
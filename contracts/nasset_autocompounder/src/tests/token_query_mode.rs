@@ -0,0 +1,166 @@
+use crate::msg::{
+    Cw20StorageLayout, ExecuteMsg, GovernanceMsg, QueryMsg, TokenQueryMode, TokenQueryModeResponse,
+    VaultAssetsResponse,
+};
+
+use crate::testing::{Sdk, AUTO_NASSET_TOKEN_ADDR, CONFIG_UPDATE_DELAY};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Response, StdError, StdResult, Uint128};
+
+fn update_token_query_mode(
+    sdk: &mut Sdk,
+    token: &str,
+    mode: TokenQueryMode,
+) -> StdResult<Response> {
+    sdk.governance_update(GovernanceMsg::UpdateTokenQueryMode {
+        token_addr: token.to_string(),
+        mode,
+    })
+}
+
+fn query_token_query_mode(sdk: &Sdk, token: &str) -> TokenQueryMode {
+    let response: TokenQueryModeResponse = from_binary(
+        &crate::contract::query(
+            sdk.deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokenQueryMode {
+                token_addr: token.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    response.mode
+}
+
+fn query_max_withdraw(sdk: &Sdk, owner: &str) -> StdResult<Uint128> {
+    let response: VaultAssetsResponse = from_binary(&crate::contract::query(
+        sdk.deps.as_ref(),
+        mock_env(),
+        QueryMsg::MaxWithdraw {
+            owner: owner.to_string(),
+            nasset_token_addr: None,
+        },
+    )?)?;
+    Ok(response.assets)
+}
+
+fn raw_cw20_base() -> TokenQueryMode {
+    TokenQueryMode::Raw {
+        layout: Cw20StorageLayout::Cw20Base {},
+    }
+}
+
+#[test]
+fn smart_queries_by_default() {
+    let mut sdk = Sdk::init();
+    assert_eq!(
+        TokenQueryMode::Smart {},
+        query_token_query_mode(&sdk, AUTO_NASSET_TOKEN_ADDR)
+    );
+
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u64));
+    sdk.set_nasset_balance(Uint256::from(2_000u64));
+    sdk.set_auto_nasset_balance("addr9999", Uint256::from(100u64));
+    assert_eq!(
+        Uint128::new(200),
+        query_max_withdraw(&sdk, "addr9999").unwrap()
+    );
+    //cw20 returns zero balance for unknown account
+    assert_eq!(
+        Uint128::zero(),
+        query_max_withdraw(&sdk, "addr6666").unwrap()
+    );
+}
+
+#[test]
+fn raw_queries_for_token_with_known_layout() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u64));
+    sdk.set_nasset_balance(Uint256::from(2_000u64));
+    sdk.set_auto_nasset_balance("addr9999", Uint256::from(100u64));
+
+    update_token_query_mode(&mut sdk, AUTO_NASSET_TOKEN_ADDR, raw_cw20_base()).unwrap();
+    assert_eq!(
+        raw_cw20_base(),
+        query_token_query_mode(&sdk, AUTO_NASSET_TOKEN_ADDR)
+    );
+    assert_eq!(
+        Uint128::new(200),
+        query_max_withdraw(&sdk, "addr9999").unwrap()
+    );
+
+    update_token_query_mode(&mut sdk, AUTO_NASSET_TOKEN_ADDR, TokenQueryMode::Smart {}).unwrap();
+    assert_eq!(
+        TokenQueryMode::Smart {},
+        query_token_query_mode(&sdk, AUTO_NASSET_TOKEN_ADDR)
+    );
+}
+
+#[test]
+fn token_query_mode_update_is_timelocked() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u64));
+
+    sdk.governance(GovernanceMsg::UpdateTokenQueryMode {
+        token_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+        mode: raw_cw20_base(),
+    })
+    .unwrap();
+    assert_eq!(
+        TokenQueryMode::Smart {},
+        query_token_query_mode(&sdk, AUTO_NASSET_TOKEN_ADDR)
+    );
+
+    sdk.increase_block_time(CONFIG_UPDATE_DELAY);
+    sdk.governance(GovernanceMsg::ExecuteConfigUpdate {})
+        .unwrap();
+    assert_eq!(
+        raw_cw20_base(),
+        query_token_query_mode(&sdk, AUTO_NASSET_TOKEN_ADDR)
+    );
+}
+
+#[test]
+fn token_query_errors_are_propagated() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u64));
+    sdk.set_nasset_balance(Uint256::from(2_000u64));
+    update_token_query_mode(&mut sdk, AUTO_NASSET_TOKEN_ADDR, raw_cw20_base()).unwrap();
+
+    //failed balance query is not treated as zero balance
+    let res = query_max_withdraw(&sdk, "addr6666");
+    assert!(res.is_err());
+}
+
+#[test]
+fn fail_to_set_raw_query_mode_for_unknown_token() {
+    let mut sdk = Sdk::init();
+
+    let res = update_token_query_mode(&mut sdk, "addr0011", raw_cw20_base());
+    assert!(res.is_err());
+    assert_eq!(
+        TokenQueryMode::Smart {},
+        query_token_query_mode(&sdk, "addr0011")
+    );
+}
+
+#[test]
+fn fail_to_update_token_query_mode_if_sender_is_not_governance() {
+    let mut sdk = Sdk::init();
+
+    let res = crate::contract::execute(
+        sdk.deps.as_mut(),
+        mock_env(),
+        mock_info("addr0010", &[]),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::UpdateTokenQueryMode {
+                token_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+                mode: raw_cw20_base(),
+            },
+        },
+    );
+    assert_eq!(Err(StdError::generic_err("unauthorized")), res);
+}
@@ -0,0 +1,176 @@
+use crate::msg::{
+    ExecuteMsg, GovernanceMsg, QueryMsg, UnbondingResponse, UnbondingsResponse, WithdrawalMode,
+};
+
+use crate::testing::{
+    Sdk, AUTO_NASSET_TOKEN_ADDR, CONFIG_UPDATE_DELAY, GOVERNANCE_CONTRACT_ADDR, NASSET_TOKEN_ADDR,
+};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+const UNBONDING_PERIOD: u64 = 1_000;
+
+fn set_unbonding_mode(sdk: &mut Sdk) {
+    let change_config_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: None,
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: None,
+            withdrawal_mode: Some(WithdrawalMode::Unbonding {
+                unbonding_period: UNBONDING_PERIOD,
+            }),
+        },
+    };
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, change_config_msg).unwrap();
+
+    let execute_update_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+    };
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_UPDATE_DELAY);
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), env, info, execute_update_msg).unwrap();
+}
+
+fn query_unbondings(sdk: &Sdk, address: &str) -> UnbondingsResponse {
+    let query_res = crate::contract::query(
+        sdk.deps.as_ref(),
+        sdk.env.clone(),
+        QueryMsg::Unbondings {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    from_binary(&query_res).unwrap()
+}
+
+#[test]
+fn withdraw_in_unbonding_mode_creates_unbonding() {
+    let mut sdk = Sdk::init();
+    set_unbonding_mode(&mut sdk);
+
+    let user_address = "addr9999";
+    let start_time = sdk.env.block.time.seconds();
+    let response = sdk
+        .user_send_withdraw(user_address, Uint128::from(100u128))
+        .unwrap();
+    assert!(response.messages.is_empty());
+
+    sdk.increase_block_time(10);
+    sdk.user_send_withdraw(user_address, Uint128::from(50u128))
+        .unwrap();
+
+    assert_eq!(
+        UnbondingsResponse {
+            unbondings: vec![
+                UnbondingResponse {
+                    auto_nasset_amount: Uint128::from(100u128),
+                    release_at: start_time + UNBONDING_PERIOD,
+                },
+                UnbondingResponse {
+                    auto_nasset_amount: Uint128::from(50u128),
+                    release_at: start_time + 10 + UNBONDING_PERIOD,
+                },
+            ]
+        },
+        query_unbondings(&sdk, user_address)
+    );
+}
+
+#[test]
+fn fail_to_unbond_to_other_recipient() {
+    let mut sdk = Sdk::init();
+    set_unbonding_mode(&mut sdk);
+
+    // recipient would claim without withdrawal fee of anAsset owner
+    let res = sdk.user_send_withdraw_to(
+        "addr9999",
+        Some("addr6666".to_string()),
+        Uint128::from(100u128),
+    );
+    assert_eq!(
+        StdError::generic_err("recipient has to be anAsset owner in unbonding mode"),
+        res.unwrap_err()
+    );
+    assert!(query_unbondings(&sdk, "addr6666").unbondings.is_empty());
+}
+
+#[test]
+fn fail_to_claim_before_unbonding_period() {
+    let mut sdk = Sdk::init();
+    set_unbonding_mode(&mut sdk);
+
+    let user_address = "addr9999";
+    sdk.user_send_withdraw(user_address, Uint128::from(100u128))
+        .unwrap();
+
+    sdk.increase_block_time(UNBONDING_PERIOD - 1);
+    let res = sdk.user_claim(user_address, Uint256::zero());
+    assert_eq!(
+        StdError::generic_err("nothing to claim"),
+        res.err().unwrap()
+    );
+}
+
+#[test]
+fn claim_pays_with_current_rate() {
+    let mut sdk = Sdk::init();
+    set_unbonding_mode(&mut sdk);
+
+    let user_address = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+
+    sdk.user_send_withdraw(user_address, Uint128::from(100u128))
+        .unwrap();
+    sdk.increase_block_time(10);
+    sdk.user_send_withdraw(user_address, Uint128::from(50u128))
+        .unwrap();
+
+    // only first unbonding is released
+    sdk.increase_block_time(UNBONDING_PERIOD - 10);
+    // locked anAsset keeps earning: vault nAsset doubles while unbonding
+    let response = sdk
+        .user_claim(user_address, Uint256::from(1_000u128))
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NASSET_TOKEN_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: user_address.to_string(),
+                    amount: Uint128::from(200u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    let start_time = mock_env().block.time.seconds();
+    assert_eq!(
+        UnbondingsResponse {
+            unbondings: vec![UnbondingResponse {
+                auto_nasset_amount: Uint128::from(50u128),
+                release_at: start_time + 10 + UNBONDING_PERIOD,
+            }]
+        },
+        query_unbondings(&sdk, user_address)
+    );
+}
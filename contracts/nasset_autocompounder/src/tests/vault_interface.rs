@@ -0,0 +1,251 @@
+use crate::msg::{
+    GovernanceMsg, QueryMsg, VaultAssetsResponse, VaultSharesResponse, WithdrawalFeeConfig,
+};
+
+use crate::testing::{Sdk, MAX_TOTAL_NASSET};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{from_binary, Decimal, StdError, StdResult, Uint128};
+use serde::de::DeserializeOwned;
+
+fn query<T: DeserializeOwned>(sdk: &Sdk, msg: QueryMsg) -> StdResult<T> {
+    let query_res = crate::contract::query(sdk.deps.as_ref(), sdk.env.clone(), msg)?;
+    from_binary(&query_res)
+}
+
+fn shares(sdk: &Sdk, msg: QueryMsg) -> u128 {
+    query::<VaultSharesResponse>(sdk, msg)
+        .unwrap()
+        .shares
+        .u128()
+}
+
+fn assets(sdk: &Sdk, msg: QueryMsg) -> u128 {
+    query::<VaultAssetsResponse>(sdk, msg)
+        .unwrap()
+        .assets
+        .u128()
+}
+
+#[test]
+fn conversions_round_in_vault_favour() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+
+    let amount = Uint128::from(100u128);
+    // 100 * 1000 / 1500 = 66.67
+    assert_eq!(
+        66,
+        shares(
+            &sdk,
+            QueryMsg::ConvertToShares {
+                assets: amount,
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        66,
+        shares(
+            &sdk,
+            QueryMsg::PreviewDeposit {
+                assets: amount,
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        67,
+        shares(
+            &sdk,
+            QueryMsg::PreviewWithdraw {
+                assets: amount,
+                owner: None,
+                nasset_token_addr: None
+            }
+        )
+    );
+
+    // 3 * 1500 / 1000 = 4.5
+    assert_eq!(
+        4,
+        assets(
+            &sdk,
+            QueryMsg::ConvertToAssets {
+                shares: Uint128::from(3u128),
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        1_500,
+        assets(
+            &sdk,
+            QueryMsg::TotalAssets {
+                nasset_token_addr: None
+            }
+        )
+    );
+
+    // no rounding needed
+    assert_eq!(
+        30,
+        shares(
+            &sdk,
+            QueryMsg::PreviewWithdraw {
+                assets: Uint128::from(45u128),
+                owner: None,
+                nasset_token_addr: None
+            }
+        )
+    );
+}
+
+#[test]
+fn empty_vault_converts_one_to_one() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::zero());
+
+    let amount = Uint128::from(100u128);
+    assert_eq!(
+        100,
+        shares(
+            &sdk,
+            QueryMsg::ConvertToShares {
+                assets: amount,
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        100,
+        assets(
+            &sdk,
+            QueryMsg::ConvertToAssets {
+                shares: amount,
+                nasset_token_addr: None
+            }
+        )
+    );
+}
+
+#[test]
+fn fail_to_convert_to_shares_when_vault_has_no_nasset() {
+    let mut sdk = Sdk::init();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::zero());
+
+    let res = query::<VaultSharesResponse>(
+        &sdk,
+        QueryMsg::ConvertToShares {
+            assets: Uint128::from(100u128),
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(
+        StdError::generic_err("vault has no nAsset"),
+        res.err().unwrap()
+    );
+}
+
+#[test]
+fn max_deposit_and_withdraw() {
+    let mut sdk = Sdk::init();
+    let owner = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+    sdk.set_auto_nasset_balance(owner, Uint256::from(300u128));
+
+    assert_eq!(
+        MAX_TOTAL_NASSET - 1_500,
+        assets(
+            &sdk,
+            QueryMsg::MaxDeposit {
+                receiver: None,
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        450,
+        assets(
+            &sdk,
+            QueryMsg::MaxWithdraw {
+                owner: owner.to_string(),
+                nasset_token_addr: None
+            }
+        )
+    );
+    assert_eq!(
+        0,
+        assets(
+            &sdk,
+            QueryMsg::MaxWithdraw {
+                owner: "addr6666".to_string(),
+                nasset_token_addr: None
+            }
+        )
+    );
+}
+
+#[test]
+fn preview_withdraw_includes_withdrawal_fee() {
+    let mut sdk = Sdk::init();
+    sdk.governance_update(GovernanceMsg::UpdateConfig {
+        psi_token_addr: None,
+        psi_to_nasset_pair_addr: None,
+        nasset_token_rewards_addr: None,
+        config_update_delay: None,
+        withdrawal_fee: Some(WithdrawalFeeConfig {
+            max_fee: Decimal::percent(10),
+            decay_period: 1_000,
+            treasury_addr: None,
+        }),
+        withdrawal_mode: None,
+    })
+    .unwrap();
+
+    let owner = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+    sdk.user_deposit(owner, Uint128::from(1_000u128)).unwrap();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+    sdk.set_nasset_balance(Uint256::from(1_500u128));
+    sdk.set_auto_nasset_balance(owner, Uint256::from(1_000u128));
+
+    // half of decay period passed: fee = 5%, 100 nAsset = 66.67 anAsset
+    sdk.increase_block_time(500);
+    let preview_withdraw = |owner: Option<&str>| QueryMsg::PreviewWithdraw {
+        assets: Uint128::from(95u128),
+        owner: owner.map(|owner| owner.to_string()),
+        nasset_token_addr: None,
+    };
+    assert_eq!(67, shares(&sdk, preview_withdraw(Some(owner))));
+    // maximum fee = 10%, 105.56 nAsset = 70.37 anAsset
+    assert_eq!(71, shares(&sdk, preview_withdraw(None)));
+    // no deposits, anAsset was received by transfer: maximum fee
+    assert_eq!(71, shares(&sdk, preview_withdraw(Some("addr6666"))));
+
+    // same fee as MaxWithdraw: 1500 - 5%
+    let max_withdraw = assets(
+        &sdk,
+        QueryMsg::MaxWithdraw {
+            owner: owner.to_string(),
+            nasset_token_addr: None,
+        },
+    );
+    assert_eq!(1_425, max_withdraw);
+    assert_eq!(
+        1_000,
+        shares(
+            &sdk,
+            QueryMsg::PreviewWithdraw {
+                assets: Uint128::from(max_withdraw),
+                owner: Some(owner.to_string()),
+                nasset_token_addr: None
+            }
+        )
+    );
+}
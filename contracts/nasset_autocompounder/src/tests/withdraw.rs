@@ -1,6 +1,6 @@
 use crate::msg::{Cw20HookMsg, ExecuteMsg};
 
-use super::sdk::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
+use crate::testing::{Sdk, AUTO_NASSET_TOKEN_ADDR, NASSET_TOKEN_ADDR};
 
 use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::testing::{mock_env, mock_info};
@@ -15,7 +15,7 @@ fn fail_to_withdraw_wrong_cw20() {
     let cw20_withdraw_msg = Cw20ReceiveMsg {
         sender: sender_addr.to_string(),
         amount: Uint128::from(256u64),
-        msg: to_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Withdraw { recipient: None }).unwrap(),
     };
 
     let info = mock_info("some_random_addr", &[]);
@@ -293,3 +293,46 @@ fn withdraw_nasset_without_rewards() {
         );
     }
 }
+
+#[test]
+fn withdraw_nasset_to_recipient() {
+    let mut sdk = Sdk::init();
+    let sender_addr = "addr9999";
+    let recipient_addr = "addr6666";
+    let withdraw_amount = Uint128::from(1_000u128);
+
+    sdk.set_auto_nasset_supply(withdraw_amount.into());
+    sdk.set_nasset_balance(Uint256::from(2_000u128));
+
+    let response = sdk
+        .user_withdraw_to(
+            sender_addr,
+            recipient_addr,
+            withdraw_amount,
+            Uint256::zero(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NASSET_TOKEN_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient_addr.to_string(),
+                    amount: Uint128::from(2_000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: withdraw_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+}
@@ -0,0 +1,326 @@
+use crate::msg::{ExecuteMsg, GovernanceMsg, WithdrawalFeeConfig};
+
+use crate::testing::{
+    Sdk, AUTO_NASSET_TOKEN_ADDR, CONFIG_UPDATE_DELAY, GOVERNANCE_CONTRACT_ADDR, NASSET_TOKEN_ADDR,
+};
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{to_binary, CosmosMsg, Decimal, Response, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+const DECAY_PERIOD: u64 = 1_000;
+const TREASURY_ADDR: &str = "addr0007";
+const ROUTER_ADDR: &str = "addr0008";
+
+fn set_withdrawal_fee(sdk: &mut Sdk, treasury_addr: Option<String>) {
+    let change_config_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: None,
+            nasset_token_rewards_addr: None,
+            config_update_delay: None,
+            withdrawal_fee: Some(WithdrawalFeeConfig {
+                max_fee: Decimal::percent(10),
+                decay_period: DECAY_PERIOD,
+                treasury_addr,
+            }),
+            withdrawal_mode: None,
+        },
+    };
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), mock_env(), info, change_config_msg).unwrap();
+
+    let execute_update_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+    };
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(CONFIG_UPDATE_DELAY);
+    let info = mock_info(GOVERNANCE_CONTRACT_ADDR, &[]);
+    crate::contract::execute(sdk.deps.as_mut(), env, info, execute_update_msg).unwrap();
+}
+
+fn deposit(sdk: &mut Sdk, address: &str, amount: Uint256) {
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(amount);
+    sdk.user_deposit(address, amount.into()).unwrap();
+    sdk.set_auto_nasset_supply(amount);
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: NASSET_TOKEN_ADDR.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    }))
+}
+
+fn burn_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn withdrawal_fee_stays_in_vault() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, None);
+
+    let user_address = "addr9999";
+    deposit(&mut sdk, user_address, Uint256::from(1_000u128));
+
+    // 1/4 of decay period passed: fee = 10% * 3/4 = 7.5%
+    sdk.increase_block_time(DECAY_PERIOD / 4);
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![transfer_msg(user_address, 925), burn_msg(1_000)]
+    );
+}
+
+#[test]
+fn withdrawal_fee_sent_to_treasury() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let user_address = "addr9999";
+    deposit(&mut sdk, user_address, Uint256::from(1_000u128));
+
+    // withdraw right after deposit: full fee
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            transfer_msg(user_address, 900),
+            transfer_msg(TREASURY_ADDR, 100),
+            burn_msg(1_000)
+        ]
+    );
+}
+
+#[test]
+fn no_withdrawal_fee_after_decay_period() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let user_address = "addr9999";
+    deposit(&mut sdk, user_address, Uint256::from(1_000u128));
+
+    sdk.increase_block_time(DECAY_PERIOD);
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![transfer_msg(user_address, 1_000), burn_msg(1_000)]
+    );
+}
+
+#[test]
+fn full_withdrawal_fee_for_user_without_deposits() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    deposit(&mut sdk, "addr9999", Uint256::from(1_000u128));
+
+    // external anAsset token was transferred to new address, fee doesn't decay
+    // without deposit of its own
+    sdk.increase_block_time(DECAY_PERIOD);
+    let user_address = "addr6666";
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(500u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            transfer_msg(user_address, 450),
+            transfer_msg(TREASURY_ADDR, 50),
+            burn_msg(500)
+        ]
+    );
+}
+
+fn embedded_transfer(sdk: &mut Sdk, from: &str, to: &str, amount: u128) {
+    crate::contract::execute(
+        sdk.deps.as_mut(),
+        sdk.env.clone(),
+        mock_info(from, &[]),
+        ExecuteMsg::Transfer {
+            recipient: to.to_string(),
+            amount: Uint128::from(amount),
+        },
+    )
+    .unwrap();
+}
+
+fn withdrawal_fee(response: &Response) -> String {
+    response
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == "withdrawal_fee")
+        .map(|attribute| attribute.value.clone())
+        .unwrap()
+}
+
+#[test]
+fn withdrawal_fee_timer_moves_with_transferred_anasset() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let user_address = "addr9999";
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+    sdk.user_deposit(user_address, Uint128::from(1_000u128))
+        .unwrap();
+
+    // moving anAsset to new address keeps fee of depositor: 10% * 500 / 1000
+    sdk.increase_block_time(DECAY_PERIOD / 2);
+    let new_address = "addr6666";
+    embedded_transfer(&mut sdk, user_address, new_address, 1_000);
+    let response = sdk
+        .user_withdraw_embedded(new_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!("50", withdrawal_fee(&response));
+    assert_eq!(
+        response.messages,
+        vec![
+            transfer_msg(new_address, 950),
+            transfer_msg(TREASURY_ADDR, 50)
+        ]
+    );
+}
+
+#[test]
+fn anasset_dust_transfer_does_not_reset_withdrawal_fee_timer() {
+    let mut sdk = Sdk::init_with_embedded_share_token();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let victim_address = "addr9999";
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+    sdk.user_deposit(victim_address, Uint128::from(1_000u128))
+        .unwrap();
+
+    sdk.increase_block_time(DECAY_PERIOD);
+    let attacker_address = "addr6666";
+    sdk.set_nasset_balance(Uint256::from(2_000u128));
+    sdk.user_deposit(attacker_address, Uint128::from(1_000u128))
+        .unwrap();
+    embedded_transfer(&mut sdk, attacker_address, victim_address, 1);
+
+    // timer moved by 1000 * 1 / 1001 seconds only
+    let response = sdk
+        .user_withdraw_embedded(victim_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+    assert_eq!("0", withdrawal_fee(&response));
+}
+
+#[test]
+fn withdrawal_fee_is_charged_by_anasset_owner() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let user_address = "addr9999";
+    deposit(&mut sdk, user_address, Uint256::from(1_000u128));
+
+    // withdrawing to fresh address doesn't skip the fee
+    let recipient_address = "addr6666";
+    let response = sdk
+        .user_withdraw_to(
+            user_address,
+            recipient_address,
+            Uint128::from(1_000u128),
+            Uint256::zero(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            transfer_msg(recipient_address, 900),
+            transfer_msg(TREASURY_ADDR, 100),
+            burn_msg(1_000)
+        ]
+    );
+}
+
+#[test]
+fn deposit_to_other_recipient_does_not_reset_fee_timer() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+
+    let user_address = "addr9999";
+    deposit(&mut sdk, user_address, Uint256::from(1_000u128));
+    sdk.increase_block_time(DECAY_PERIOD);
+
+    // dust deposit on behalf of user
+    sdk.set_nasset_balance(Uint256::from(1_001u128));
+    sdk.user_deposit_to("addr6666", Some(user_address.to_string()), Uint128::new(1))
+        .unwrap();
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+
+    let response = sdk
+        .user_withdraw(user_address, Uint128::from(1_000u128), Uint256::zero())
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![transfer_msg(user_address, 1_000), burn_msg(1_000)]
+    );
+}
+
+#[test]
+fn router_deposits_and_withdraws_on_behalf_of_recipient() {
+    let mut sdk = Sdk::init();
+    set_withdrawal_fee(&mut sdk, Some(TREASURY_ADDR.to_string()));
+    sdk.governance_update(GovernanceMsg::UpdateRouter {
+        router_addr: Some(ROUTER_ADDR.to_string()),
+    })
+    .unwrap();
+
+    let user_address = "addr9999";
+    sdk.set_auto_nasset_supply(Uint256::zero());
+    sdk.set_nasset_balance(Uint256::from(1_000u128));
+    sdk.user_deposit_to(
+        ROUTER_ADDR,
+        Some(user_address.to_string()),
+        Uint128::from(1_000u128),
+    )
+    .unwrap();
+    sdk.set_auto_nasset_supply(Uint256::from(1_000u128));
+
+    // fee timer was started by deposit through router
+    let response = sdk
+        .user_withdraw_to(
+            ROUTER_ADDR,
+            user_address,
+            Uint128::from(1_000u128),
+            Uint256::zero(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![
+            transfer_msg(user_address, 900),
+            transfer_msg(TREASURY_ADDR, 100),
+            burn_msg(1_000)
+        ]
+    );
+}
@@ -0,0 +1,68 @@
+use crate::{
+    state::{load_config, may_load_vault, store_config, store_pending_vault},
+    SubmsgIds,
+};
+use cosmwasm_std::{
+    to_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, StdResult, SubMsg, WasmMsg,
+};
+use nasset_autocompounder_governance as governance;
+use nasset_autocompounder_interface::msg::InstantiateMsg as AutocompounderInstantiateMsg;
+
+pub use nasset_autocompounder_governance::update_governance_addr;
+
+pub fn create_vault(
+    deps: DepsMut,
+    instantiate_msg: AutocompounderInstantiateMsg,
+) -> StdResult<Response> {
+    let config = load_config(deps.storage)?;
+    let nasset_token = deps.api.addr_validate(&instantiate_msg.nasset_token_addr)?;
+
+    if may_load_vault(deps.storage, &nasset_token)?.is_some() {
+        return Err(StdError::generic_err(
+            "autocompounder for this nAsset already exists",
+        ));
+    }
+
+    // registered in reply, when address is known
+    store_pending_vault(deps.storage, &nasset_token)?;
+
+    let label = format!(
+        "n{} autocompounder",
+        instantiate_msg.collateral_token_symbol
+    );
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin: Some(config.governance_contract.to_string()),
+                code_id: config.autocompounder_code_id,
+                msg: to_binary(&instantiate_msg)?,
+                funds: vec![],
+                label,
+            }),
+            SubmsgIds::InitAutocompounder.id(),
+        ))
+        .add_attributes(vec![
+            ("action", "create_vault"),
+            ("nasset_token", nasset_token.as_ref()),
+        ]))
+}
+
+pub fn update_config(deps: DepsMut, autocompounder_code_id: Option<u64>) -> StdResult<Response> {
+    let mut config = load_config(deps.storage)?;
+
+    if let Some(autocompounder_code_id) = autocompounder_code_id {
+        config.autocompounder_code_id = autocompounder_code_id;
+    }
+
+    store_config(deps.storage, &config)?;
+    Ok(Response::default().add_attributes(vec![("action", "update_config")]))
+}
+
+pub fn accept_governance(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    governance::accept_governance(deps, env, info, |storage, governance_contract| {
+        let mut config = load_config(storage)?;
+        config.governance_contract = governance_contract;
+        store_config(storage, &config)
+    })
+}
@@ -0,0 +1,153 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
+
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, GovernanceMsg, InstantiateMsg, MigrateMsg, QueryMsg, VaultResponse,
+    VaultsResponse,
+};
+use crate::{
+    commands,
+    state::{
+        load_config, load_pending_vault, load_vaults, may_load_vault, remove_pending_vault,
+        store_config, store_vault, Config,
+    },
+    SubmsgIds,
+};
+use nasset_autocompounder_interface::reply_response::MsgInstantiateContractResponse;
+use std::convert::TryFrom;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        governance_contract: deps.api.addr_validate(&msg.governance_contract_addr)?,
+        autocompounder_code_id: msg.autocompounder_code_id,
+    };
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    let submessage_enum = SubmsgIds::try_from(msg.id)?;
+    match submessage_enum {
+        SubmsgIds::InitAutocompounder => {
+            let res = MsgInstantiateContractResponse::from_reply(msg)?;
+            let nasset_token = load_pending_vault(deps.storage)?;
+            remove_pending_vault(deps.storage);
+
+            let autocompounder_addr = res.contract_address.as_str();
+            store_vault(
+                deps.storage,
+                &nasset_token,
+                &Addr::unchecked(autocompounder_addr),
+            )?;
+
+            Ok(Response::new().add_attributes(vec![
+                ("action", "vault_created"),
+                ("nasset_token", nasset_token.as_ref()),
+                ("autocompounder_addr", autocompounder_addr),
+            ]))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::AcceptGovernance {} => commands::accept_governance(deps, env, info),
+
+        ExecuteMsg::Governance { governance_msg } => {
+            let config: Config = load_config(deps.storage)?;
+            if info.sender != config.governance_contract {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match governance_msg {
+                GovernanceMsg::CreateVault { instantiate_msg } => {
+                    commands::create_vault(deps, *instantiate_msg)
+                }
+
+                GovernanceMsg::UpdateConfig {
+                    autocompounder_code_id,
+                } => commands::update_config(deps, autocompounder_code_id),
+
+                GovernanceMsg::UpdateGovernanceContract {
+                    gov_addr,
+                    seconds_to_wait_for_accept_gov_tx,
+                } => commands::update_governance_addr(
+                    deps,
+                    env,
+                    gov_addr,
+                    seconds_to_wait_for_accept_gov_tx,
+                ),
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Vault { nasset_token_addr } => to_binary(&query_vault(deps, nasset_token_addr)?),
+        QueryMsg::Vaults { start_after, limit } => {
+            to_binary(&query_vaults(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = load_config(deps.storage)?;
+    Ok(ConfigResponse {
+        governance_contract_addr: config.governance_contract.to_string(),
+        autocompounder_code_id: config.autocompounder_code_id,
+    })
+}
+
+pub fn query_vault(deps: Deps, nasset_token_addr: String) -> StdResult<VaultResponse> {
+    let nasset_token = deps.api.addr_validate(&nasset_token_addr)?;
+    match may_load_vault(deps.storage, &nasset_token)? {
+        Some(autocompounder) => Ok(VaultResponse {
+            nasset_token,
+            autocompounder,
+        }),
+        None => Err(StdError::generic_err(format!(
+            "no autocompounder for nAsset {}",
+            nasset_token
+        ))),
+    }
+}
+
+pub fn query_vaults(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VaultsResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let vaults = load_vaults(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(nasset_token, autocompounder)| VaultResponse {
+            nasset_token,
+            autocompounder,
+        })
+        .collect();
+
+    Ok(VaultsResponse { vaults })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use std::convert::TryFrom;
+
+mod commands;
+pub mod contract;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub enum SubmsgIds {
+    InitAutocompounder,
+}
+
+impl TryFrom<u64> for SubmsgIds {
+    type Error = StdError;
+
+    fn try_from(v: u64) -> Result<Self, Self::Error> {
+        match v {
+            x if x == SubmsgIds::InitAutocompounder.id() => Ok(SubmsgIds::InitAutocompounder),
+            unknown => Err(StdError::generic_err(format!(
+                "unknown reply message id: {}",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl SubmsgIds {
+    pub const fn id(&self) -> u64 {
+        match self {
+            SubmsgIds::InitAutocompounder => 0,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub governance_contract: Addr,
+    pub autocompounder_code_id: u64,
+}
+
+static KEY_CONFIG: Item<Config> = Item::new("config");
+// nAsset of autocompounder which is being instantiated
+static KEY_PENDING_VAULT: Item<Addr> = Item::new("pending_vault");
+// nAsset -> autocompounder
+static VAULTS: Map<&Addr, Addr> = Map::new("vaults");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
+    KEY_CONFIG.load(storage)
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    KEY_CONFIG.save(storage, config)
+}
+
+pub fn load_pending_vault(storage: &dyn Storage) -> StdResult<Addr> {
+    KEY_PENDING_VAULT.load(storage)
+}
+
+pub fn store_pending_vault(storage: &mut dyn Storage, nasset_token: &Addr) -> StdResult<()> {
+    KEY_PENDING_VAULT.save(storage, nasset_token)
+}
+
+pub fn remove_pending_vault(storage: &mut dyn Storage) {
+    KEY_PENDING_VAULT.remove(storage)
+}
+
+pub fn may_load_vault(storage: &dyn Storage, nasset_token: &Addr) -> StdResult<Option<Addr>> {
+    VAULTS.may_load(storage, nasset_token)
+}
+
+pub fn store_vault(storage: &mut dyn Storage, nasset_token: &Addr, vault: &Addr) -> StdResult<()> {
+    VAULTS.save(storage, nasset_token, vault)
+}
+
+/// (nAsset, autocompounder) pairs ordered by nAsset address
+pub fn load_vaults(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Addr)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    VAULTS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (nasset_token, vault) = item?;
+            Ok((
+                Addr::unchecked(String::from_utf8_lossy(&nasset_token)),
+                vault,
+            ))
+        })
+        .collect()
+}
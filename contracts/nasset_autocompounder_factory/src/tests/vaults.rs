@@ -0,0 +1,216 @@
+use crate::msg::{
+    ExecuteMsg, GovernanceMsg, InstantiateMsg, QueryMsg, VaultResponse, VaultsResponse,
+};
+use crate::SubmsgIds;
+
+use cosmwasm_std::testing::MockQuerier;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, OwnedDeps, Reply, Response, StdError, StdResult,
+    SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use nasset_autocompounder_interface::msg::InstantiateMsg as AutocompounderInstantiateMsg;
+use nasset_autocompounder_interface::reply_response::MsgInstantiateContractResponse;
+
+const GOVERNANCE_CONTRACT_ADDR: &str = "addr0004";
+const AUTOCOMPOUNDER_CODE_ID: u64 = 128;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+fn init() -> Deps {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        governance_contract_addr: GOVERNANCE_CONTRACT_ADDR.to_string(),
+        autocompounder_code_id: AUTOCOMPOUNDER_CODE_ID,
+    };
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info("addr9999", &[]), msg)
+        .unwrap();
+    deps
+}
+
+fn autocompounder_instantiate_msg(nasset_token_addr: &str) -> AutocompounderInstantiateMsg {
+    AutocompounderInstantiateMsg {
+        nasset_token_addr: nasset_token_addr.to_string(),
+        psi_token_addr: "addr0002".to_string(),
+        psi_to_nasset_pair_addr: "addr0003".to_string(),
+        governance_contract_addr: GOVERNANCE_CONTRACT_ADDR.to_string(),
+        cw20_token_code_id: 256,
+        nasset_token_rewards_addr: "addr0005".to_string(),
+        collateral_token_symbol: "AVAX".to_string(),
+        config_update_delay: 86_400,
+        max_total_nasset: Uint128::from(1_000_000_000_000_000u128),
+        max_nasset_per_user: None,
+        withdrawal_fee: None,
+        withdrawal_mode: None,
+        share_token_mode: None,
+    }
+}
+
+fn send_create_vault(deps: &mut Deps, nasset_token_addr: &str) -> StdResult<Response> {
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GOVERNANCE_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::CreateVault {
+                instantiate_msg: Box::new(autocompounder_instantiate_msg(nasset_token_addr)),
+            },
+        },
+    )
+}
+
+fn create_vault(deps: &mut Deps, nasset_token_addr: &str, autocompounder_addr: &str) {
+    send_create_vault(deps, nasset_token_addr).unwrap();
+
+    let reply_msg = Reply {
+        id: SubmsgIds::InitAutocompounder.id(),
+        result: cosmwasm_std::ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(MsgInstantiateContractResponse::new(autocompounder_addr).to_binary()),
+        }),
+    };
+    crate::contract::reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+}
+
+fn vault(nasset_token_addr: &str, autocompounder_addr: &str) -> VaultResponse {
+    VaultResponse {
+        nasset_token: Addr::unchecked(nasset_token_addr),
+        autocompounder: Addr::unchecked(autocompounder_addr),
+    }
+}
+
+#[test]
+fn create_vault_instantiates_autocompounder() {
+    let mut deps = init();
+
+    let response = send_create_vault(&mut deps, "nluna").unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin: Some(GOVERNANCE_CONTRACT_ADDR.to_string()),
+                code_id: AUTOCOMPOUNDER_CODE_ID,
+                msg: to_binary(&autocompounder_instantiate_msg("nluna")).unwrap(),
+                funds: vec![],
+                label: "nAVAX autocompounder".to_string(),
+            }),
+            SubmsgIds::InitAutocompounder.id(),
+        )]
+    );
+}
+
+#[test]
+fn fail_to_create_vault_by_non_governance() {
+    let mut deps = init();
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr9999", &[]),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::CreateVault {
+                instantiate_msg: Box::new(autocompounder_instantiate_msg("nluna")),
+            },
+        },
+    );
+    assert_eq!(StdError::generic_err("unauthorized"), res.unwrap_err());
+}
+
+#[test]
+fn fail_to_create_second_vault_for_nasset() {
+    let mut deps = init();
+    create_vault(&mut deps, "nluna", "vault_nluna");
+
+    let res = send_create_vault(&mut deps, "nluna");
+    assert_eq!(
+        StdError::generic_err("autocompounder for this nAsset already exists"),
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn resolve_vault_by_nasset() {
+    let mut deps = init();
+    create_vault(&mut deps, "nluna", "vault_nluna");
+
+    let query_res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Vault {
+            nasset_token_addr: "nluna".to_string(),
+        },
+    )
+    .unwrap();
+    let response: VaultResponse = from_binary(&query_res).unwrap();
+    assert_eq!(vault("nluna", "vault_nluna"), response);
+
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Vault {
+            nasset_token_addr: "neth".to_string(),
+        },
+    );
+    assert_eq!(
+        StdError::generic_err("no autocompounder for nAsset neth"),
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn list_vaults() {
+    let mut deps = init();
+    create_vault(&mut deps, "nluna", "vault_nluna");
+    create_vault(&mut deps, "navax", "vault_navax");
+    create_vault(&mut deps, "neth", "vault_neth");
+
+    let query_vaults = |start_after: Option<&str>, limit: Option<u32>| -> VaultsResponse {
+        let query_res = crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Vaults {
+                start_after: start_after.map(|addr| addr.to_string()),
+                limit,
+            },
+        )
+        .unwrap();
+        from_binary(&query_res).unwrap()
+    };
+
+    assert_eq!(
+        VaultsResponse {
+            vaults: vec![
+                vault("navax", "vault_navax"),
+                vault("neth", "vault_neth"),
+                vault("nluna", "vault_nluna"),
+            ],
+        },
+        query_vaults(None, None)
+    );
+    assert_eq!(
+        VaultsResponse {
+            vaults: vec![vault("neth", "vault_neth")],
+        },
+        query_vaults(Some("navax"), Some(1))
+    );
+}
+
+#[test]
+fn update_code_id() {
+    let mut deps = init();
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GOVERNANCE_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::UpdateConfig {
+                autocompounder_code_id: Some(129),
+            },
+        },
+    )
+    .unwrap();
+
+    let config = crate::state::load_config(&deps.storage).unwrap();
+    assert_eq!(129, config.autocompounder_code_id);
+}
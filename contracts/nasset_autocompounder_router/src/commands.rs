@@ -0,0 +1,143 @@
+use crate::{
+    msg::Operation,
+    state::{
+        is_autocompounder_registered, load_config, remove_autocompounder, store_autocompounder,
+        store_config,
+    },
+};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use nasset_autocompounder_governance as governance;
+use nasset_autocompounder_interface::msg::Cw20HookMsg;
+
+pub use nasset_autocompounder_governance::update_governance_addr;
+
+pub fn execute_operations(
+    deps: DepsMut,
+    info: MessageInfo,
+    operations: Vec<Operation>,
+) -> StdResult<Response> {
+    if operations.is_empty() {
+        return Err(StdError::generic_err("no operations"));
+    }
+
+    let mut messages = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let message = match operation {
+            Operation::Deposit {
+                autocompounder_addr,
+                nasset_token_addr,
+                amount,
+            } => send_from_to_autocompounder(
+                deps.as_ref(),
+                &info.sender,
+                autocompounder_addr,
+                nasset_token_addr,
+                amount,
+                Cw20HookMsg::Deposit {
+                    recipient: Some(info.sender.to_string()),
+                },
+            )?,
+
+            Operation::Withdraw {
+                autocompounder_addr,
+                auto_nasset_token_addr,
+                amount,
+            } => send_from_to_autocompounder(
+                deps.as_ref(),
+                &info.sender,
+                autocompounder_addr,
+                auto_nasset_token_addr,
+                amount,
+                Cw20HookMsg::Withdraw {
+                    recipient: Some(info.sender.to_string()),
+                },
+            )?,
+        };
+        messages.push(message);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute_operations"),
+        ("sender", info.sender.as_ref()),
+    ]))
+}
+
+fn send_from_to_autocompounder(
+    deps: Deps,
+    owner: &Addr,
+    autocompounder_addr: String,
+    token_addr: String,
+    amount: Uint128,
+    hook_msg: Cw20HookMsg,
+) -> StdResult<CosmosMsg> {
+    let autocompounder = deps.api.addr_validate(&autocompounder_addr)?;
+    if !is_autocompounder_registered(deps.storage, &autocompounder) {
+        return Err(StdError::generic_err(format!(
+            "autocompounder {} is not registered",
+            autocompounder
+        )));
+    }
+
+    if amount.is_zero() {
+        return Err(StdError::generic_err("amount must be greater than zero"));
+    }
+
+    let token = deps.api.addr_validate(&token_addr)?;
+    // autocompounder checks that token belongs to one of its vaults
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::SendFrom {
+            owner: owner.to_string(),
+            contract: autocompounder.to_string(),
+            amount,
+            msg: to_binary(&hook_msg)?,
+        })?,
+        funds: vec![],
+    }))
+}
+
+pub fn register_autocompounder(deps: DepsMut, autocompounder_addr: String) -> StdResult<Response> {
+    let autocompounder = deps.api.addr_validate(&autocompounder_addr)?;
+    if is_autocompounder_registered(deps.storage, &autocompounder) {
+        return Err(StdError::generic_err(
+            "autocompounder is already registered",
+        ));
+    }
+
+    store_autocompounder(deps.storage, &autocompounder)?;
+    Ok(Response::default().add_attributes(vec![
+        ("action", "register_autocompounder"),
+        ("autocompounder", autocompounder.as_ref()),
+    ]))
+}
+
+pub fn deregister_autocompounder(
+    deps: DepsMut,
+    autocompounder_addr: String,
+) -> StdResult<Response> {
+    let autocompounder = deps.api.addr_validate(&autocompounder_addr)?;
+    if !is_autocompounder_registered(deps.storage, &autocompounder) {
+        return Err(StdError::generic_err(format!(
+            "autocompounder {} is not registered",
+            autocompounder
+        )));
+    }
+
+    remove_autocompounder(deps.storage, &autocompounder);
+    Ok(Response::default().add_attributes(vec![
+        ("action", "deregister_autocompounder"),
+        ("autocompounder", autocompounder.as_ref()),
+    ]))
+}
+
+pub fn accept_governance(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    governance::accept_governance(deps, env, info, |storage, governance_contract| {
+        let mut config = load_config(storage)?;
+        config.governance_contract = governance_contract;
+        store_config(storage, &config)
+    })
+}
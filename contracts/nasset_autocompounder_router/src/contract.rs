@@ -0,0 +1,179 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, StdError,
+    StdResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use nasset_autocompounder_interface::msg::VaultResponse as AutocompounderVaultResponse;
+use nasset_autocompounder_interface::querier::AutocompounderQuerier;
+
+use crate::msg::{
+    AutocompoundersResponse, ConfigResponse, ExecuteMsg, GovernanceMsg, InstantiateMsg, MigrateMsg,
+    PositionResponse, PositionsResponse, QueryMsg,
+};
+use crate::{
+    commands,
+    state::{load_all_autocompounders, load_autocompounders, load_config, store_config, Config},
+};
+
+// autocompounder 'Vaults' query page size
+const VAULTS_PAGE_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        governance_contract: deps.api.addr_validate(&msg.governance_contract_addr)?,
+    };
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::ExecuteOperations { operations } => {
+            commands::execute_operations(deps, info, operations)
+        }
+
+        ExecuteMsg::AcceptGovernance {} => commands::accept_governance(deps, env, info),
+
+        ExecuteMsg::Governance { governance_msg } => {
+            let config: Config = load_config(deps.storage)?;
+            if info.sender != config.governance_contract {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match governance_msg {
+                GovernanceMsg::RegisterAutocompounder {
+                    autocompounder_addr,
+                } => commands::register_autocompounder(deps, autocompounder_addr),
+
+                GovernanceMsg::DeregisterAutocompounder {
+                    autocompounder_addr,
+                } => commands::deregister_autocompounder(deps, autocompounder_addr),
+
+                GovernanceMsg::UpdateGovernanceContract {
+                    gov_addr,
+                    seconds_to_wait_for_accept_gov_tx,
+                } => commands::update_governance_addr(
+                    deps,
+                    env,
+                    gov_addr,
+                    seconds_to_wait_for_accept_gov_tx,
+                ),
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Autocompounders { start_after, limit } => {
+            to_binary(&query_autocompounders(deps, start_after, limit)?)
+        }
+        QueryMsg::Positions { address } => to_binary(&query_positions(deps, address)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = load_config(deps.storage)?;
+    Ok(ConfigResponse {
+        governance_contract_addr: config.governance_contract.to_string(),
+    })
+}
+
+pub fn query_autocompounders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AutocompoundersResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    Ok(AutocompoundersResponse {
+        autocompounders: load_autocompounders(deps.storage, start_after, limit),
+    })
+}
+
+pub fn query_positions(deps: Deps, address: String) -> StdResult<PositionsResponse> {
+    let user = deps.api.addr_validate(&address)?;
+
+    let mut positions = vec![];
+    for autocompounder in load_all_autocompounders(deps.storage) {
+        for vault in query_autocompounder_vaults(deps, &autocompounder)? {
+            let auto_nasset_token = deps.api.addr_validate(&vault.auto_nasset_token)?;
+            let auto_nasset_balance = query_token_balance(deps, &auto_nasset_token, &user)?;
+            if auto_nasset_balance.is_zero() {
+                continue;
+            }
+
+            let nasset_amount = if vault.auto_nasset_supply.is_zero() {
+                Uint128::zero()
+            } else {
+                auto_nasset_balance.multiply_ratio(vault.total_nasset, vault.auto_nasset_supply)
+            };
+
+            positions.push(PositionResponse {
+                autocompounder: autocompounder.clone(),
+                nasset_token: deps.api.addr_validate(&vault.nasset_token)?,
+                auto_nasset_token,
+                auto_nasset_balance,
+                nasset_amount,
+            });
+        }
+    }
+
+    Ok(PositionsResponse { positions })
+}
+
+/// Primary vault followed by all additional vaults of autocompounder
+fn query_autocompounder_vaults(
+    deps: Deps,
+    autocompounder: &Addr,
+) -> StdResult<Vec<AutocompounderVaultResponse>> {
+    let autocompounder = AutocompounderQuerier::new(&deps.querier, autocompounder.to_string());
+    let config = autocompounder.config()?;
+    let primary_vault = autocompounder.vault(config.nasset_token_addr)?;
+
+    let mut vaults = vec![primary_vault];
+    let mut start_after = None;
+    loop {
+        let page = autocompounder.vaults(start_after, Some(VAULTS_PAGE_LIMIT))?;
+
+        let page_len = page.vaults.len();
+        start_after = page.vaults.last().map(|vault| vault.nasset_token.clone());
+        vaults.extend(page.vaults);
+
+        if page_len < VAULTS_PAGE_LIMIT as usize {
+            break;
+        }
+    }
+
+    Ok(vaults)
+}
+
+fn query_token_balance(deps: Deps, token: &Addr, account: &Addr) -> StdResult<Uint128> {
+    let response: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20QueryMsg::Balance {
+            address: account.to_string(),
+        })?,
+    }))?;
+
+    Ok(response.balance)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
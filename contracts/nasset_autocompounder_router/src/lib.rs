@@ -0,0 +1,7 @@
+mod commands;
+pub mod contract;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
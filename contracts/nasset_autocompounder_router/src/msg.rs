@@ -0,0 +1,94 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub governance_contract_addr: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    //operations are executed in order, sender should give router allowance
+    //for every nAsset/anAsset token it sends. Autocompounder has to trust router
+    //('UpdateRouter'), otherwise withdrawal fee is tracked for router instead of sender
+    ExecuteOperations { operations: Vec<Operation> },
+    AcceptGovernance {},
+    Governance { governance_msg: GovernanceMsg },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    //deposit nAsset, anAsset is minted to sender
+    Deposit {
+        autocompounder_addr: String,
+        nasset_token_addr: String,
+        amount: Uint128,
+    },
+    //withdraw anAsset, nAsset is sent to sender
+    Withdraw {
+        autocompounder_addr: String,
+        auto_nasset_token_addr: String,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceMsg {
+    RegisterAutocompounder {
+        autocompounder_addr: String,
+    },
+    DeregisterAutocompounder {
+        autocompounder_addr: String,
+    },
+    UpdateGovernanceContract {
+        gov_addr: String,
+        //how long to wait for 'AcceptGovernance' transaction
+        seconds_to_wait_for_accept_gov_tx: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Autocompounders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    //user anAsset positions in all vaults of all registered autocompounders
+    Positions {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub governance_contract_addr: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutocompoundersResponse {
+    pub autocompounders: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    pub autocompounder: Addr,
+    pub nasset_token: Addr,
+    pub auto_nasset_token: Addr,
+    pub auto_nasset_balance: Uint128,
+    //nAsset which anAsset balance is worth, withdrawal fee is not included
+    pub nasset_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionsResponse {
+    pub positions: Vec<PositionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,60 @@
+use cosmwasm_std::{Addr, Empty, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub governance_contract: Addr,
+}
+
+static KEY_CONFIG: Item<Config> = Item::new("config");
+// autocompounders which router is allowed to send tokens to
+static AUTOCOMPOUNDERS: Map<&Addr, Empty> = Map::new("autocompounders");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
+    KEY_CONFIG.load(storage)
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    KEY_CONFIG.save(storage, config)
+}
+
+pub fn is_autocompounder_registered(storage: &dyn Storage, autocompounder: &Addr) -> bool {
+    AUTOCOMPOUNDERS.has(storage, autocompounder)
+}
+
+pub fn store_autocompounder(storage: &mut dyn Storage, autocompounder: &Addr) -> StdResult<()> {
+    AUTOCOMPOUNDERS.save(storage, autocompounder, &Empty {})
+}
+
+pub fn remove_autocompounder(storage: &mut dyn Storage, autocompounder: &Addr) {
+    AUTOCOMPOUNDERS.remove(storage, autocompounder)
+}
+
+/// Registered autocompounders ordered by address
+pub fn load_autocompounders(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Vec<Addr> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    AUTOCOMPOUNDERS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|autocompounder| Addr::unchecked(String::from_utf8_lossy(&autocompounder)))
+        .collect()
+}
+
+/// All registered autocompounders, used by positions query
+pub fn load_all_autocompounders(storage: &dyn Storage) -> Vec<Addr> {
+    AUTOCOMPOUNDERS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|autocompounder| Addr::unchecked(String::from_utf8_lossy(&autocompounder)))
+        .collect()
+}
@@ -0,0 +1,140 @@
+use super::{init, query, register_autocompounder, send_governance_msg, GOVERNANCE_CONTRACT_ADDR};
+use crate::msg::{AutocompoundersResponse, ConfigResponse, ExecuteMsg, GovernanceMsg, QueryMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Addr, StdError};
+
+#[test]
+fn register_autocompounders() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, "addr0201");
+    register_autocompounder(&mut deps, "addr0202");
+    register_autocompounder(&mut deps, "addr0203");
+
+    let response: AutocompoundersResponse = query(
+        &deps,
+        QueryMsg::Autocompounders {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            Addr::unchecked("addr0201"),
+            Addr::unchecked("addr0202"),
+            Addr::unchecked("addr0203")
+        ],
+        response.autocompounders
+    );
+
+    let response: AutocompoundersResponse = query(
+        &deps,
+        QueryMsg::Autocompounders {
+            start_after: Some("addr0201".to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(vec![Addr::unchecked("addr0202")], response.autocompounders);
+}
+
+#[test]
+fn fail_to_register_autocompounder_twice() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, "addr0201");
+
+    let res = send_governance_msg(
+        &mut deps,
+        GovernanceMsg::RegisterAutocompounder {
+            autocompounder_addr: "addr0201".to_string(),
+        },
+    );
+    assert_eq!(
+        Err(StdError::generic_err(
+            "autocompounder is already registered"
+        )),
+        res
+    );
+}
+
+#[test]
+fn deregister_autocompounder() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, "addr0201");
+    register_autocompounder(&mut deps, "addr0202");
+
+    send_governance_msg(
+        &mut deps,
+        GovernanceMsg::DeregisterAutocompounder {
+            autocompounder_addr: "addr0201".to_string(),
+        },
+    )
+    .unwrap();
+
+    let response: AutocompoundersResponse = query(
+        &deps,
+        QueryMsg::Autocompounders {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(vec![Addr::unchecked("addr0202")], response.autocompounders);
+
+    let res = send_governance_msg(
+        &mut deps,
+        GovernanceMsg::DeregisterAutocompounder {
+            autocompounder_addr: "addr0201".to_string(),
+        },
+    );
+    assert_eq!(
+        Err(StdError::generic_err(
+            "autocompounder addr0201 is not registered"
+        )),
+        res
+    );
+}
+
+#[test]
+fn fail_to_register_autocompounder_by_non_governance() {
+    let mut deps = init();
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0100", &[]),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::RegisterAutocompounder {
+                autocompounder_addr: "addr0201".to_string(),
+            },
+        },
+    );
+    assert_eq!(Err(StdError::generic_err("unauthorized")), res);
+}
+
+#[test]
+fn update_governance() {
+    let mut deps = init();
+    let new_governance = "addr0300";
+
+    send_governance_msg(
+        &mut deps,
+        GovernanceMsg::UpdateGovernanceContract {
+            gov_addr: new_governance.to_string(),
+            seconds_to_wait_for_accept_gov_tx: 10,
+        },
+    )
+    .unwrap();
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_governance, &[]),
+        ExecuteMsg::AcceptGovernance {},
+    )
+    .unwrap();
+
+    let config: ConfigResponse = query(&deps, QueryMsg::Config {}).unwrap();
+    assert_eq!(new_governance, config.governance_contract_addr);
+    assert_ne!(GOVERNANCE_CONTRACT_ADDR, config.governance_contract_addr);
+}
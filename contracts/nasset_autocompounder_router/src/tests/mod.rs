@@ -0,0 +1,233 @@
+mod governance;
+mod operations;
+mod positions;
+
+use crate::msg::{ExecuteMsg, GovernanceMsg, InstantiateMsg, Operation};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, Response, StdResult, SystemError, SystemResult, Uint128,
+    WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use nasset_autocompounder_interface::msg::{
+    ConfigResponse as AutocompounderConfigResponse, QueryMsg as AutocompounderQueryMsg,
+    VaultResponse as AutocompounderVaultResponse, VaultsResponse as AutocompounderVaultsResponse,
+    WithdrawalFeeConfig, WithdrawalMode,
+};
+use std::collections::HashMap;
+
+const GOVERNANCE_CONTRACT_ADDR: &str = "addr0004";
+const USER_ADDR: &str = "addr0100";
+
+type Deps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+#[derive(Clone)]
+struct MockVault {
+    nasset_token: String,
+    auto_nasset_token: String,
+    total_nasset: Uint128,
+    auto_nasset_supply: Uint128,
+}
+
+impl MockVault {
+    fn new(nasset_token: &str, auto_nasset_token: &str) -> Self {
+        Self {
+            nasset_token: nasset_token.to_string(),
+            auto_nasset_token: auto_nasset_token.to_string(),
+            total_nasset: Uint128::zero(),
+            auto_nasset_supply: Uint128::zero(),
+        }
+    }
+
+    fn with_totals(mut self, total_nasset: u128, auto_nasset_supply: u128) -> Self {
+        self.total_nasset = Uint128::new(total_nasset);
+        self.auto_nasset_supply = Uint128::new(auto_nasset_supply);
+        self
+    }
+
+    fn to_response(&self) -> AutocompounderVaultResponse {
+        AutocompounderVaultResponse {
+            nasset_token: self.nasset_token.clone(),
+            auto_nasset_token: self.auto_nasset_token.clone(),
+            psi_to_nasset_pair: "addr0003".to_string(),
+            nasset_token_rewards: "addr0005".to_string(),
+            max_total_nasset: Uint128::new(1_000_000_000_000),
+            max_nasset_per_user: None,
+            total_nasset: self.total_nasset,
+            auto_nasset_supply: self.auto_nasset_supply,
+        }
+    }
+}
+
+// autocompounder -> primary vault and additional vaults ordered by nAsset
+struct WasmMockQuerier {
+    base: MockQuerier,
+    autocompounders: HashMap<String, (MockVault, Vec<MockVault>)>,
+    // token -> (account -> balance)
+    balances: HashMap<String, HashMap<String, Uint128>>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<cosmwasm_std::Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    fn handle_query(&self, request: &QueryRequest<cosmwasm_std::Empty>) -> QuerierResult {
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Some((primary, vaults)) = self.autocompounders.get(contract_addr) {
+                    let msg: AutocompounderQueryMsg = from_binary(msg).unwrap();
+                    return SystemResult::Ok(ContractResult::Ok(
+                        Self::autocompounder_query(primary, vaults, msg).unwrap(),
+                    ));
+                }
+
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .cloned()
+                            .unwrap_or_default();
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn autocompounder_query(
+        primary: &MockVault,
+        vaults: &[MockVault],
+        msg: AutocompounderQueryMsg,
+    ) -> StdResult<cosmwasm_std::Binary> {
+        match msg {
+            AutocompounderQueryMsg::Config {} => to_binary(&AutocompounderConfigResponse {
+                nasset_token_addr: primary.nasset_token.clone(),
+                auto_nasset_token_addr: primary.auto_nasset_token.clone(),
+                psi_token_addr: "addr0002".to_string(),
+                psi_to_nasset_pair_addr: "addr0003".to_string(),
+                governance_contract_addr: GOVERNANCE_CONTRACT_ADDR.to_string(),
+                nasset_token_rewards_addr: "addr0005".to_string(),
+                config_update_delay: 0,
+                max_total_nasset: Uint128::new(1_000_000_000_000),
+                max_nasset_per_user: None,
+                withdrawal_fee: WithdrawalFeeConfig {
+                    max_fee: Decimal::zero(),
+                    decay_period: 0,
+                    treasury_addr: None,
+                },
+                withdrawal_mode: WithdrawalMode::Instant {},
+                router_addr: None,
+                paused: false,
+            }),
+            AutocompounderQueryMsg::Vault { nasset_token_addr } => {
+                let vault = std::iter::once(primary)
+                    .chain(vaults.iter())
+                    .find(|vault| vault.nasset_token == nasset_token_addr)
+                    .unwrap();
+                to_binary(&vault.to_response())
+            }
+            AutocompounderQueryMsg::Vaults { start_after, limit } => {
+                let vaults = vaults
+                    .iter()
+                    .filter(|vault| match &start_after {
+                        Some(start_after) => &vault.nasset_token > start_after,
+                        None => true,
+                    })
+                    .take(limit.unwrap_or(10) as usize)
+                    .map(MockVault::to_response)
+                    .collect();
+                to_binary(&AutocompounderVaultsResponse { vaults })
+            }
+            _ => panic!("DO NOT ENTER HERE"),
+        }
+    }
+
+    fn add_autocompounder(&mut self, addr: &str, primary: MockVault, vaults: Vec<MockVault>) {
+        self.autocompounders
+            .insert(addr.to_string(), (primary, vaults));
+    }
+
+    fn set_balance(&mut self, token: &str, account: &str, balance: u128) {
+        self.balances
+            .entry(token.to_string())
+            .or_default()
+            .insert(account.to_string(), Uint128::new(balance));
+    }
+}
+
+fn init() -> Deps {
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: WasmMockQuerier {
+            base: MockQuerier::new(&[]),
+            autocompounders: HashMap::new(),
+            balances: HashMap::new(),
+        },
+    };
+    let msg = InstantiateMsg {
+        governance_contract_addr: GOVERNANCE_CONTRACT_ADDR.to_string(),
+    };
+    crate::contract::instantiate(deps.as_mut(), mock_env(), mock_info("addr9999", &[]), msg)
+        .unwrap();
+    deps
+}
+
+fn send_governance_msg(deps: &mut Deps, governance_msg: GovernanceMsg) -> StdResult<Response> {
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GOVERNANCE_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Governance { governance_msg },
+    )
+}
+
+fn register_autocompounder(deps: &mut Deps, autocompounder_addr: &str) {
+    send_governance_msg(
+        deps,
+        GovernanceMsg::RegisterAutocompounder {
+            autocompounder_addr: autocompounder_addr.to_string(),
+        },
+    )
+    .unwrap();
+}
+
+fn execute_operations(
+    deps: &mut Deps,
+    sender: &str,
+    operations: Vec<Operation>,
+) -> StdResult<Response> {
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ExecuteOperations { operations },
+    )
+}
+
+fn query<T: serde::de::DeserializeOwned>(deps: &Deps, msg: crate::msg::QueryMsg) -> StdResult<T> {
+    from_binary(&crate::contract::query(deps.as_ref(), mock_env(), msg)?)
+}
+
+fn addr(addr: &str) -> Addr {
+    Addr::unchecked(addr)
+}
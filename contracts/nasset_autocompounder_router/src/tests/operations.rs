@@ -0,0 +1,141 @@
+use super::{addr, execute_operations, init, register_autocompounder, USER_ADDR};
+use crate::msg::Operation;
+use cosmwasm_std::{to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use nasset_autocompounder_interface::msg::Cw20HookMsg;
+
+const AUTOCOMPOUNDER_1: &str = "addr0201";
+const AUTOCOMPOUNDER_2: &str = "addr0202";
+
+fn send_from_msg(token: &str, autocompounder: &str, amount: u128, hook_msg: Cw20HookMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::SendFrom {
+            owner: USER_ADDR.to_string(),
+            contract: autocompounder.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&hook_msg).unwrap(),
+        })
+        .unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn batch_deposits_and_withdrawals() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_2);
+
+    let response = execute_operations(
+        &mut deps,
+        USER_ADDR,
+        vec![
+            Operation::Deposit {
+                autocompounder_addr: AUTOCOMPOUNDER_1.to_string(),
+                nasset_token_addr: "addr0011".to_string(),
+                amount: Uint128::new(100),
+            },
+            Operation::Withdraw {
+                autocompounder_addr: AUTOCOMPOUNDER_2.to_string(),
+                auto_nasset_token_addr: "addr0022".to_string(),
+                amount: Uint128::new(50),
+            },
+            Operation::Deposit {
+                autocompounder_addr: AUTOCOMPOUNDER_2.to_string(),
+                nasset_token_addr: "addr0021".to_string(),
+                amount: Uint128::new(10),
+            },
+        ],
+    )
+    .unwrap();
+
+    //operations are executed in the same order
+    assert_eq!(
+        vec![
+            send_from_msg(
+                "addr0011",
+                AUTOCOMPOUNDER_1,
+                100,
+                Cw20HookMsg::Deposit {
+                    recipient: Some(USER_ADDR.to_string())
+                }
+            ),
+            send_from_msg(
+                "addr0022",
+                AUTOCOMPOUNDER_2,
+                50,
+                Cw20HookMsg::Withdraw {
+                    recipient: Some(USER_ADDR.to_string())
+                }
+            ),
+            send_from_msg(
+                "addr0021",
+                AUTOCOMPOUNDER_2,
+                10,
+                Cw20HookMsg::Deposit {
+                    recipient: Some(USER_ADDR.to_string())
+                }
+            ),
+        ],
+        response.messages
+    );
+}
+
+#[test]
+fn fail_to_execute_empty_operations() {
+    let mut deps = init();
+
+    let res = execute_operations(&mut deps, USER_ADDR, vec![]);
+    assert_eq!(Err(StdError::generic_err("no operations")), res);
+}
+
+#[test]
+fn fail_to_send_to_unregistered_autocompounder() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+
+    let res = execute_operations(
+        &mut deps,
+        USER_ADDR,
+        vec![
+            Operation::Deposit {
+                autocompounder_addr: AUTOCOMPOUNDER_1.to_string(),
+                nasset_token_addr: "addr0011".to_string(),
+                amount: Uint128::new(100),
+            },
+            Operation::Withdraw {
+                autocompounder_addr: AUTOCOMPOUNDER_2.to_string(),
+                auto_nasset_token_addr: "addr0022".to_string(),
+                amount: Uint128::new(50),
+            },
+        ],
+    );
+    assert_eq!(
+        Err(StdError::generic_err(format!(
+            "autocompounder {} is not registered",
+            addr(AUTOCOMPOUNDER_2)
+        ))),
+        res
+    );
+}
+
+#[test]
+fn fail_to_execute_zero_amount_operation() {
+    let mut deps = init();
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+
+    let res = execute_operations(
+        &mut deps,
+        USER_ADDR,
+        vec![Operation::Withdraw {
+            autocompounder_addr: AUTOCOMPOUNDER_1.to_string(),
+            auto_nasset_token_addr: "addr0012".to_string(),
+            amount: Uint128::zero(),
+        }],
+    );
+    assert_eq!(
+        Err(StdError::generic_err("amount must be greater than zero")),
+        res
+    );
+}
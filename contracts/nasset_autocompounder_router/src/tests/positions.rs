@@ -0,0 +1,128 @@
+use super::{addr, init, query, register_autocompounder, MockVault, USER_ADDR};
+use crate::msg::{PositionResponse, PositionsResponse, QueryMsg};
+use cosmwasm_std::Uint128;
+
+const AUTOCOMPOUNDER_1: &str = "addr0201";
+const AUTOCOMPOUNDER_2: &str = "addr0202";
+
+fn query_positions(deps: &super::Deps) -> Vec<PositionResponse> {
+    let response: PositionsResponse = query(
+        deps,
+        QueryMsg::Positions {
+            address: USER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    response.positions
+}
+
+#[test]
+fn positions_across_autocompounders_and_vaults() {
+    let mut deps = init();
+    deps.querier.add_autocompounder(
+        AUTOCOMPOUNDER_1,
+        MockVault::new("addr0011", "addr0012").with_totals(1_500, 1_000),
+        vec![MockVault::new("addr0013", "addr0014").with_totals(300, 300)],
+    );
+    deps.querier.add_autocompounder(
+        AUTOCOMPOUNDER_2,
+        MockVault::new("addr0021", "addr0022").with_totals(1_000, 3_000),
+        vec![],
+    );
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_2);
+
+    deps.querier.set_balance("addr0012", USER_ADDR, 200);
+    deps.querier.set_balance("addr0014", USER_ADDR, 50);
+    deps.querier.set_balance("addr0022", USER_ADDR, 100);
+
+    assert_eq!(
+        vec![
+            PositionResponse {
+                autocompounder: addr(AUTOCOMPOUNDER_1),
+                nasset_token: addr("addr0011"),
+                auto_nasset_token: addr("addr0012"),
+                auto_nasset_balance: Uint128::new(200),
+                nasset_amount: Uint128::new(300),
+            },
+            PositionResponse {
+                autocompounder: addr(AUTOCOMPOUNDER_1),
+                nasset_token: addr("addr0013"),
+                auto_nasset_token: addr("addr0014"),
+                auto_nasset_balance: Uint128::new(50),
+                nasset_amount: Uint128::new(50),
+            },
+            //rounded down
+            PositionResponse {
+                autocompounder: addr(AUTOCOMPOUNDER_2),
+                nasset_token: addr("addr0021"),
+                auto_nasset_token: addr("addr0022"),
+                auto_nasset_balance: Uint128::new(100),
+                nasset_amount: Uint128::new(33),
+            },
+        ],
+        query_positions(&deps)
+    );
+}
+
+#[test]
+fn positions_skip_empty_vaults() {
+    let mut deps = init();
+    deps.querier.add_autocompounder(
+        AUTOCOMPOUNDER_1,
+        MockVault::new("addr0011", "addr0012").with_totals(1_000, 1_000),
+        vec![MockVault::new("addr0013", "addr0014").with_totals(300, 300)],
+    );
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+    deps.querier.set_balance("addr0014", USER_ADDR, 30);
+
+    assert_eq!(
+        vec![PositionResponse {
+            autocompounder: addr(AUTOCOMPOUNDER_1),
+            nasset_token: addr("addr0013"),
+            auto_nasset_token: addr("addr0014"),
+            auto_nasset_balance: Uint128::new(30),
+            nasset_amount: Uint128::new(30),
+        }],
+        query_positions(&deps)
+    );
+}
+
+#[test]
+fn positions_ignore_unregistered_autocompounders() {
+    let mut deps = init();
+    deps.querier.add_autocompounder(
+        AUTOCOMPOUNDER_1,
+        MockVault::new("addr0011", "addr0012").with_totals(1_000, 1_000),
+        vec![],
+    );
+    deps.querier.set_balance("addr0012", USER_ADDR, 30);
+
+    assert!(query_positions(&deps).is_empty());
+}
+
+#[test]
+fn positions_paginate_autocompounder_vaults() {
+    let mut deps = init();
+    //more than one 'Vaults' page
+    let vaults: Vec<MockVault> = (0..35)
+        .map(|i| {
+            MockVault::new(&format!("nasset{:02}", i), &format!("anasset{:02}", i))
+                .with_totals(100, 100)
+        })
+        .collect();
+    deps.querier.add_autocompounder(
+        AUTOCOMPOUNDER_1,
+        MockVault::new("addr0011", "addr0012"),
+        vaults,
+    );
+    register_autocompounder(&mut deps, AUTOCOMPOUNDER_1);
+    deps.querier.set_balance("anasset03", USER_ADDR, 10);
+    deps.querier.set_balance("anasset34", USER_ADDR, 20);
+
+    let positions = query_positions(&deps);
+    assert_eq!(2, positions.len());
+    assert_eq!(addr("nasset03"), positions[0].nasset_token);
+    assert_eq!(addr("nasset34"), positions[1].nasset_token);
+    assert_eq!(Uint128::new(20), positions[1].nasset_amount);
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::io::Read;
+
+pub type CliResult<T> = Result<T, String>;
+
+/// Positional arguments and '--name value' options of subcommand
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> CliResult<Self> {
+        let mut positional = vec![];
+        let mut options = HashMap::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("missing value of '--{}'", name))?;
+                    options.insert(name.to_string(), value.clone());
+                }
+                None => positional.push(arg.clone()),
+            }
+        }
+
+        Ok(Args {
+            positional,
+            options,
+        })
+    }
+
+    pub fn positional(&self, index: usize, name: &str) -> CliResult<&str> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| format!("missing <{}> argument", name))
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn required(&self, name: &str) -> CliResult<&str> {
+        self.option(name)
+            .ok_or_else(|| format!("missing '--{}' option", name))
+    }
+}
+
+/// JSON given inline, '-' for stdin or path to file
+pub fn read_json_input(input: &str) -> CliResult<String> {
+    let trimmed = input.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return Ok(input.to_string());
+    }
+
+    if input == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("cannot read stdin: {}", e))?;
+        return Ok(content);
+    }
+
+    std::fs::read_to_string(input).map_err(|e| format!("cannot read {}: {}", input, e))
+}
@@ -0,0 +1,63 @@
+use cosmwasm_std::{Addr, Event};
+use nasset_autocompounder_interface::events::AutocompounderEvent;
+use serde::Deserialize;
+
+use crate::args::{read_json_input, Args, CliResult};
+use crate::messages::to_json;
+
+// LCD 'TxResponse' (optionally wrapped into 'tx_response'), object with events or events array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TxResult {
+    Wrapped { tx_response: TxResponse },
+    Response(TxResponse),
+    Events(Vec<Event>),
+}
+
+#[derive(Deserialize)]
+struct TxResponse {
+    #[serde(default)]
+    logs: Vec<TxLog>,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+struct TxLog {
+    events: Vec<Event>,
+}
+
+pub fn decode_events(args: &Args) -> CliResult<String> {
+    let content = read_json_input(args.positional(0, "tx-json")?)?;
+    let autocompounder = Addr::unchecked(args.required("autocompounder")?);
+    to_json(&parse_tx_events(&content, &autocompounder)?)
+}
+
+/// Events emitted by 'autocompounder' from transaction result, in emission order.
+/// Any contract can emit event of autocompounder type, events of other contracts are skipped
+pub fn parse_tx_events(
+    content: &str,
+    autocompounder: &Addr,
+) -> CliResult<Vec<AutocompounderEvent>> {
+    let tx_result: TxResult =
+        serde_json::from_str(content).map_err(|e| format!("invalid transaction: {}", e))?;
+
+    let events = match tx_result {
+        TxResult::Wrapped { tx_response } | TxResult::Response(tx_response) => {
+            // logs are grouped by message, events are flat list of the same events
+            if tx_response.logs.is_empty() {
+                tx_response.events
+            } else {
+                tx_response
+                    .logs
+                    .into_iter()
+                    .flat_map(|log| log.events)
+                    .collect()
+            }
+        }
+        TxResult::Events(events) => events,
+    };
+
+    AutocompounderEvent::parse_all_from(&events, std::slice::from_ref(autocompounder))
+        .map_err(|e| e.to_string())
+}
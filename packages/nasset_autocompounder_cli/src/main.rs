@@ -0,0 +1,59 @@
+//! Operator tool for nasset autocompounder: builds execute, governance and cw20 hook
+//! messages, decodes autocompounder events of transactions and calculates anAsset value.
+mod args;
+mod decode;
+mod messages;
+mod share_value;
+
+#[cfg(test)]
+mod tests;
+
+use args::{Args, CliResult};
+
+const USAGE: &str = "usage: nasset-autocompounder-cli <command> [arguments]
+
+JSON arguments are inline JSON, '-' for stdin or path to file.
+
+commands:
+  examples                          JSON of every ExecuteMsg, GovernanceMsg and Cw20HookMsg variant
+  execute <msg> [--contract ADDR]   validate ExecuteMsg, print it with base64 and WasmMsg
+  governance <msg> [--contract ADDR]
+                                    wrap GovernanceMsg into ExecuteMsg::Governance
+  deposit --autocompounder ADDR --nasset-token ADDR --amount N [--recipient ADDR]
+                                    nAsset cw20 'Send' with Deposit hook
+  withdraw --autocompounder ADDR --auto-nasset-token ADDR --amount N [--recipient ADDR]
+                                    anAsset cw20 'Send' with Withdraw hook
+  decode-events <tx> --autocompounder ADDR
+                                    deposit, withdraw and compound events emitted by
+                                    autocompounder in transaction
+  share-value <vault> [--shares N]  nAsset value of anAsset from 'Vault' query response";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => println!("{}", output),
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(args: &[String]) -> CliResult<String> {
+    let (command, args) = args
+        .split_first()
+        .ok_or_else(|| "missing command".to_string())?;
+    let args = Args::parse(args)?;
+
+    match command.as_str() {
+        "examples" => messages::examples(),
+        "execute" => messages::encode_execute(&args),
+        "governance" => messages::encode_governance(&args),
+        "deposit" => messages::encode_deposit(&args),
+        "withdraw" => messages::encode_withdraw(&args),
+        "decode-events" => decode::decode_events(&args),
+        "share-value" => share_value::share_value(&args),
+        "help" | "--help" | "-h" => Ok(USAGE.to_string()),
+        unknown => Err(format!("unknown command '{}'", unknown)),
+    }
+}
@@ -0,0 +1,307 @@
+use cosmwasm_std::{from_binary, to_binary, Binary, CosmosMsg, Decimal, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
+use nasset_autocompounder_interface::helpers::{deposit_msg, withdraw_msg};
+use nasset_autocompounder_interface::msg::{
+    Cw20HookMsg, Cw20StorageLayout, ExecuteMsg, GovernanceMsg, TokenQueryMode, WithdrawalFeeConfig,
+    WithdrawalMode,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::args::{read_json_input, Args, CliResult};
+
+const EXAMPLE_ADDR: &str = "terra1...";
+
+/// Message JSON, its base64 form and 'WasmMsg::Execute' when contract is given
+#[derive(Serialize)]
+pub struct EncodedMsg<T> {
+    pub msg: T,
+    pub msg_base64: Binary,
+    pub wasm_msg: Option<CosmosMsg>,
+}
+
+impl<T: Serialize> EncodedMsg<T> {
+    fn new(msg: T, contract: Option<&str>) -> CliResult<Self> {
+        let msg_base64 = to_binary(&msg).map_err(|e| e.to_string())?;
+        let wasm_msg = contract.map(|contract| {
+            WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: msg_base64.clone(),
+                funds: vec![],
+            }
+            .into()
+        });
+
+        Ok(EncodedMsg {
+            msg,
+            msg_base64,
+            wasm_msg,
+        })
+    }
+}
+
+/// cw20 'Send' to autocompounder with decoded hook message
+#[derive(Serialize)]
+pub struct EncodedSend {
+    pub hook_msg: Cw20HookMsg,
+    pub cw20_msg: Cw20ExecuteMsg,
+    pub wasm_msg: CosmosMsg,
+}
+
+impl EncodedSend {
+    fn new(wasm_msg: CosmosMsg) -> CliResult<Self> {
+        let cw20_msg: Cw20ExecuteMsg = match &wasm_msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                from_binary(msg).map_err(|e| e.to_string())?
+            }
+            _ => return Err("expected wasm execute message".to_string()),
+        };
+        let hook_msg = match &cw20_msg {
+            Cw20ExecuteMsg::Send { msg, .. } => from_binary(msg).map_err(|e| e.to_string())?,
+            _ => return Err("expected cw20 'Send' message".to_string()),
+        };
+
+        Ok(EncodedSend {
+            hook_msg,
+            cw20_msg,
+            wasm_msg,
+        })
+    }
+}
+
+pub fn encode_execute(args: &Args) -> CliResult<String> {
+    let msg: ExecuteMsg = parse_json(args.positional(0, "execute-msg-json")?)?;
+    to_json(&EncodedMsg::new(msg, args.option("contract"))?)
+}
+
+/// Wraps governance message into 'ExecuteMsg::Governance'
+pub fn encode_governance(args: &Args) -> CliResult<String> {
+    let governance_msg: GovernanceMsg = parse_json(args.positional(0, "governance-msg-json")?)?;
+    let msg = ExecuteMsg::Governance { governance_msg };
+    to_json(&EncodedMsg::new(msg, args.option("contract"))?)
+}
+
+pub fn encode_deposit(args: &Args) -> CliResult<String> {
+    let msg = deposit_msg(
+        args.required("autocompounder")?,
+        args.required("nasset-token")?,
+        parse_amount(args.required("amount")?)?,
+        args.option("recipient").map(str::to_string),
+    )
+    .map_err(|e| e.to_string())?;
+    to_json(&EncodedSend::new(msg)?)
+}
+
+pub fn encode_withdraw(args: &Args) -> CliResult<String> {
+    let msg = withdraw_msg(
+        args.required("autocompounder")?,
+        args.required("auto-nasset-token")?,
+        parse_amount(args.required("amount")?)?,
+        args.option("recipient").map(str::to_string),
+    )
+    .map_err(|e| e.to_string())?;
+    to_json(&EncodedSend::new(msg)?)
+}
+
+/// Example of every message variant, keyed by message type and variant name
+pub fn examples() -> CliResult<String> {
+    let mut execute = Map::new();
+    for msg in execute_msg_examples() {
+        execute.insert(execute_msg_name(&msg).to_string(), to_value(&msg)?);
+    }
+
+    let mut governance = Map::new();
+    for governance_msg in governance_msg_examples() {
+        let name = governance_msg_name(&governance_msg).to_string();
+        let msg = ExecuteMsg::Governance { governance_msg };
+        governance.insert(name, to_value(&msg)?);
+    }
+
+    let mut hook = Map::new();
+    for hook_msg in hook_msg_examples() {
+        let name = hook_msg_name(&hook_msg).to_string();
+        let encoded = json!({
+            "hook_msg": to_value(&hook_msg)?,
+            "hook_msg_base64": to_binary(&hook_msg).map_err(|e| e.to_string())?,
+        });
+        hook.insert(name, encoded);
+    }
+
+    to_json(&json!({
+        "execute_msg": execute,
+        "governance_msg": governance,
+        "cw20_hook_msg": hook,
+    }))
+}
+
+pub fn execute_msg_examples() -> Vec<ExecuteMsg> {
+    let amount = Uint128::from(1_000_000u128);
+    let deposit_hook = to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap();
+    vec![
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: EXAMPLE_ADDR.to_string(),
+            amount,
+            msg: deposit_hook.clone(),
+        }),
+        ExecuteMsg::Governance {
+            governance_msg: GovernanceMsg::ExecuteConfigUpdate {},
+        },
+        ExecuteMsg::AcceptGovernance {},
+        ExecuteMsg::Compound {
+            nasset_token_addr: None,
+        },
+        ExecuteMsg::Claim {},
+        ExecuteMsg::Transfer {
+            recipient: EXAMPLE_ADDR.to_string(),
+            amount,
+        },
+        ExecuteMsg::Burn { amount },
+        ExecuteMsg::Send {
+            contract: EXAMPLE_ADDR.to_string(),
+            amount,
+            msg: deposit_hook.clone(),
+        },
+        ExecuteMsg::IncreaseAllowance {
+            spender: EXAMPLE_ADDR.to_string(),
+            amount,
+            expires: Some(Expiration::Never {}),
+        },
+        ExecuteMsg::DecreaseAllowance {
+            spender: EXAMPLE_ADDR.to_string(),
+            amount,
+            expires: None,
+        },
+        ExecuteMsg::TransferFrom {
+            owner: EXAMPLE_ADDR.to_string(),
+            recipient: EXAMPLE_ADDR.to_string(),
+            amount,
+        },
+        ExecuteMsg::SendFrom {
+            owner: EXAMPLE_ADDR.to_string(),
+            contract: EXAMPLE_ADDR.to_string(),
+            amount,
+            msg: deposit_hook,
+        },
+        ExecuteMsg::BurnFrom {
+            owner: EXAMPLE_ADDR.to_string(),
+            amount,
+        },
+    ]
+}
+
+pub fn governance_msg_examples() -> Vec<GovernanceMsg> {
+    vec![
+        GovernanceMsg::UpdateConfig {
+            psi_token_addr: None,
+            psi_to_nasset_pair_addr: Some(EXAMPLE_ADDR.to_string()),
+            nasset_token_rewards_addr: None,
+            config_update_delay: Some(86_400),
+            withdrawal_fee: Some(WithdrawalFeeConfig {
+                max_fee: Decimal::percent(1),
+                decay_period: 604_800,
+                treasury_addr: Some(EXAMPLE_ADDR.to_string()),
+            }),
+            withdrawal_mode: Some(WithdrawalMode::Unbonding {
+                unbonding_period: 86_400,
+            }),
+        },
+        GovernanceMsg::ExecuteConfigUpdate {},
+        GovernanceMsg::CancelConfigUpdate {},
+        GovernanceMsg::UpdateDepositCaps {
+            max_total_nasset: Uint128::from(1_000_000_000_000u128),
+            max_nasset_per_user: Some(Uint128::from(10_000_000_000u128)),
+        },
+        GovernanceMsg::UpdateGovernanceContract {
+            gov_addr: EXAMPLE_ADDR.to_string(),
+            seconds_to_wait_for_accept_gov_tx: 604_800,
+        },
+        GovernanceMsg::UpdateTokenQueryMode {
+            token_addr: EXAMPLE_ADDR.to_string(),
+            mode: TokenQueryMode::Raw {
+                layout: Cw20StorageLayout::Cw20Base {},
+            },
+        },
+        GovernanceMsg::UpdateRouter {
+            router_addr: Some(EXAMPLE_ADDR.to_string()),
+        },
+        GovernanceMsg::SetPaused { paused: true },
+        GovernanceMsg::AddVault {
+            nasset_token_addr: EXAMPLE_ADDR.to_string(),
+            psi_to_nasset_pair_addr: EXAMPLE_ADDR.to_string(),
+            nasset_token_rewards_addr: EXAMPLE_ADDR.to_string(),
+            cw20_token_code_id: 3,
+            collateral_token_symbol: "Luna".to_string(),
+            max_total_nasset: Uint128::from(1_000_000_000_000u128),
+            max_nasset_per_user: None,
+        },
+    ]
+}
+
+pub fn hook_msg_examples() -> Vec<Cw20HookMsg> {
+    vec![
+        Cw20HookMsg::Deposit { recipient: None },
+        Cw20HookMsg::Withdraw {
+            recipient: Some(EXAMPLE_ADDR.to_string()),
+        },
+    ]
+}
+
+// exhaustive, so new variant does not compile until it has an example
+pub fn execute_msg_name(msg: &ExecuteMsg) -> &'static str {
+    match msg {
+        ExecuteMsg::Receive(_) => "receive",
+        ExecuteMsg::Governance { .. } => "governance",
+        ExecuteMsg::AcceptGovernance {} => "accept_governance",
+        ExecuteMsg::Compound { .. } => "compound",
+        ExecuteMsg::Claim {} => "claim",
+        ExecuteMsg::Transfer { .. } => "transfer",
+        ExecuteMsg::Burn { .. } => "burn",
+        ExecuteMsg::Send { .. } => "send",
+        ExecuteMsg::IncreaseAllowance { .. } => "increase_allowance",
+        ExecuteMsg::DecreaseAllowance { .. } => "decrease_allowance",
+        ExecuteMsg::TransferFrom { .. } => "transfer_from",
+        ExecuteMsg::SendFrom { .. } => "send_from",
+        ExecuteMsg::BurnFrom { .. } => "burn_from",
+    }
+}
+
+pub fn governance_msg_name(msg: &GovernanceMsg) -> &'static str {
+    match msg {
+        GovernanceMsg::UpdateConfig { .. } => "update_config",
+        GovernanceMsg::ExecuteConfigUpdate {} => "execute_config_update",
+        GovernanceMsg::CancelConfigUpdate {} => "cancel_config_update",
+        GovernanceMsg::UpdateDepositCaps { .. } => "update_deposit_caps",
+        GovernanceMsg::UpdateGovernanceContract { .. } => "update_governance_contract",
+        GovernanceMsg::UpdateTokenQueryMode { .. } => "update_token_query_mode",
+        GovernanceMsg::UpdateRouter { .. } => "update_router",
+        GovernanceMsg::SetPaused { .. } => "set_paused",
+        GovernanceMsg::AddVault { .. } => "add_vault",
+    }
+}
+
+pub fn hook_msg_name(msg: &Cw20HookMsg) -> &'static str {
+    match msg {
+        Cw20HookMsg::Deposit { .. } => "deposit",
+        Cw20HookMsg::Withdraw { .. } => "withdraw",
+    }
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(input: &str) -> CliResult<T> {
+    let content = read_json_input(input)?;
+    serde_json::from_str(&content).map_err(|e| format!("invalid message: {}", e))
+}
+
+pub fn parse_amount(value: &str) -> CliResult<Uint128> {
+    value
+        .parse::<u128>()
+        .map(Uint128::from)
+        .map_err(|e| format!("invalid amount '{}': {}", value, e))
+}
+
+fn to_value<T: Serialize>(value: &T) -> CliResult<Value> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> CliResult<String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
@@ -0,0 +1,57 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Decimal, Uint128};
+use nasset_autocompounder_interface::msg::VaultResponse;
+use nasset_autocompounder_interface::share_math::{self, Rounding};
+use serde::Serialize;
+
+use crate::args::{read_json_input, Args, CliResult};
+use crate::messages::{parse_amount, to_json};
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ShareValue {
+    pub total_nasset: Uint128,
+    pub auto_nasset_supply: Uint128,
+    pub nasset_per_auto_nasset: Decimal,
+    pub auto_nasset_amount: Uint128,
+    //rounded down, as contract does on withdraw (before withdrawal fee)
+    pub nasset_amount: Uint128,
+}
+
+/// Value of anAsset from 'Vault' query response
+pub fn share_value(args: &Args) -> CliResult<String> {
+    let content = read_json_input(args.positional(0, "vault-json")?)?;
+    let vault: VaultResponse =
+        serde_json::from_str(&content).map_err(|e| format!("invalid vault snapshot: {}", e))?;
+    let auto_nasset_amount = match args.option("shares") {
+        Some(shares) => parse_amount(shares)?,
+        None => Uint128::from(1_000_000u128),
+    };
+
+    to_json(&calculate_share_value(
+        vault.total_nasset,
+        vault.auto_nasset_supply,
+        auto_nasset_amount,
+    )?)
+}
+
+pub fn calculate_share_value(
+    total_nasset: Uint128,
+    auto_nasset_supply: Uint128,
+    auto_nasset_amount: Uint128,
+) -> CliResult<ShareValue> {
+    let nasset_amount: Uint256 = share_math::convert_to_assets(
+        total_nasset.into(),
+        auto_nasset_supply.into(),
+        auto_nasset_amount.into(),
+        Rounding::Down,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(ShareValue {
+        total_nasset,
+        auto_nasset_supply,
+        nasset_per_auto_nasset: share_math::exchange_rate(total_nasset, auto_nasset_supply),
+        auto_nasset_amount,
+        nasset_amount: nasset_amount.into(),
+    })
+}
@@ -0,0 +1,131 @@
+use crate::decode::parse_tx_events;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use nasset_autocompounder_interface::events::{AutocompounderEvent, CompoundEvent, DepositEvent};
+
+const AUTOCOMPOUNDER_ADDR: &str = "addr0001";
+
+// deposit event of 'addr0666' is fake and has to be skipped
+const TX_RESPONSE: &str = r#"{
+  "tx_response": {
+    "height": "100",
+    "logs": [
+      {
+        "msg_index": 0,
+        "events": [
+          {
+            "type": "execute_contract",
+            "attributes": [{ "key": "contract_address", "value": "addr0001" }]
+          },
+          {
+            "type": "wasm-nexus_autocompounder_compound",
+            "attributes": [
+              { "key": "_contract_address", "value": "addr0001" },
+              { "key": "nasset_token", "value": "addr0002" },
+              { "key": "sender", "value": "addr0003" },
+              { "key": "psi_claimed", "value": "1000" },
+              { "key": "psi_sold", "value": "1000" },
+              { "key": "nasset_bought", "value": "100" },
+              { "key": "exchange_rate", "value": "1.1" }
+            ]
+          },
+          {
+            "type": "wasm-nexus_autocompounder_deposit",
+            "attributes": [
+              { "key": "_contract_address", "value": "addr0001" },
+              { "key": "nasset_token", "value": "addr0002" },
+              { "key": "sender", "value": "addr0003" },
+              { "key": "recipient", "value": "addr0003" },
+              { "key": "nasset_amount", "value": "110" },
+              { "key": "shares_minted", "value": "100" },
+              { "key": "exchange_rate", "value": "1.1" }
+            ]
+          },
+          {
+            "type": "wasm-nexus_autocompounder_deposit",
+            "attributes": [
+              { "key": "_contract_address", "value": "addr0666" },
+              { "key": "nasset_token", "value": "addr0002" },
+              { "key": "sender", "value": "addr0003" },
+              { "key": "recipient", "value": "addr0003" },
+              { "key": "nasset_amount", "value": "1000000" },
+              { "key": "shares_minted", "value": "1" },
+              { "key": "exchange_rate", "value": "1000000" }
+            ]
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+fn autocompounder() -> Addr {
+    Addr::unchecked(AUTOCOMPOUNDER_ADDR)
+}
+
+#[test]
+fn decode_events_of_tx_response() {
+    let events = parse_tx_events(TX_RESPONSE, &autocompounder()).unwrap();
+
+    assert_eq!(
+        events,
+        vec![
+            AutocompounderEvent::Compound(CompoundEvent {
+                nasset_token: Addr::unchecked("addr0002"),
+                sender: Addr::unchecked("addr0003"),
+                psi_claimed: Uint128::from(1_000u128),
+                psi_sold: Uint128::from(1_000u128),
+                nasset_bought: Uint128::from(100u128),
+                exchange_rate: Decimal::from_ratio(11u128, 10u128),
+            }),
+            AutocompounderEvent::Deposit(DepositEvent {
+                nasset_token: Addr::unchecked("addr0002"),
+                sender: Addr::unchecked("addr0003"),
+                recipient: Addr::unchecked("addr0003"),
+                nasset_amount: Uint128::from(110u128),
+                shares_minted: Uint128::from(100u128),
+                exchange_rate: Decimal::from_ratio(11u128, 10u128),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn decode_events_array() {
+    let events = parse_tx_events(
+        r#"[{ "type": "transfer", "attributes": [{ "key": "amount", "value": "1uluna" }] }]"#,
+        &autocompounder(),
+    )
+    .unwrap();
+
+    assert!(events.is_empty());
+}
+
+#[test]
+fn decode_fails_on_incomplete_event() {
+    let result = parse_tx_events(
+        r#"[{
+          "type": "wasm-nexus_autocompounder_deposit",
+          "attributes": [{ "key": "_contract_address", "value": "addr0001" }]
+        }]"#,
+        &autocompounder(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn decode_skips_events_of_other_contracts() {
+    let events = parse_tx_events(TX_RESPONSE, &Addr::unchecked("addr0666")).unwrap();
+
+    assert_eq!(
+        events,
+        vec![AutocompounderEvent::Deposit(DepositEvent {
+            nasset_token: Addr::unchecked("addr0002"),
+            sender: Addr::unchecked("addr0003"),
+            recipient: Addr::unchecked("addr0003"),
+            nasset_amount: Uint128::from(1_000_000u128),
+            shares_minted: Uint128::from(1u128),
+            exchange_rate: Decimal::from_ratio(1_000_000u128, 1u128),
+        })]
+    );
+}
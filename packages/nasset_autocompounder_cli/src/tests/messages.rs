@@ -0,0 +1,111 @@
+use super::args;
+use crate::messages::{
+    encode_deposit, encode_governance, execute_msg_examples, execute_msg_name,
+    governance_msg_examples, governance_msg_name, hook_msg_examples, hook_msg_name,
+};
+use cosmwasm_std::{from_binary, Binary, Uint128};
+use nasset_autocompounder_interface::msg::{Cw20HookMsg, ExecuteMsg, GovernanceMsg};
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[test]
+fn examples_have_unique_names_and_round_trip() {
+    let execute_msgs = execute_msg_examples();
+    let names: HashSet<_> = execute_msgs.iter().map(execute_msg_name).collect();
+    assert_eq!(names.len(), execute_msgs.len());
+    for msg in execute_msgs {
+        let json = serde_json::to_string(&msg).unwrap();
+        assert_eq!(serde_json::from_str::<ExecuteMsg>(&json).unwrap(), msg);
+    }
+
+    let governance_msgs = governance_msg_examples();
+    let names: HashSet<_> = governance_msgs.iter().map(governance_msg_name).collect();
+    assert_eq!(names.len(), governance_msgs.len());
+    for msg in governance_msgs {
+        let json = serde_json::to_string(&msg).unwrap();
+        assert_eq!(serde_json::from_str::<GovernanceMsg>(&json).unwrap(), msg);
+    }
+
+    let hook_msgs = hook_msg_examples();
+    let names: HashSet<_> = hook_msgs.iter().map(hook_msg_name).collect();
+    assert_eq!(names.len(), hook_msgs.len());
+}
+
+#[test]
+fn governance_msg_is_wrapped_and_encoded() {
+    let output = encode_governance(&args(&[
+        r#"{"update_deposit_caps":{"max_total_nasset":"1000","max_nasset_per_user":null}}"#,
+        "--contract",
+        "addr0001",
+    ]))
+    .unwrap();
+    let output: Value = serde_json::from_str(&output).unwrap();
+
+    let expected_msg = ExecuteMsg::Governance {
+        governance_msg: GovernanceMsg::UpdateDepositCaps {
+            max_total_nasset: Uint128::from(1_000u128),
+            max_nasset_per_user: None,
+        },
+    };
+    assert_eq!(output["msg"], serde_json::to_value(&expected_msg).unwrap());
+
+    let msg_base64: Binary = serde_json::from_value(output["msg_base64"].clone()).unwrap();
+    assert_eq!(
+        from_binary::<ExecuteMsg>(&msg_base64).unwrap(),
+        expected_msg
+    );
+    assert_eq!(
+        output["wasm_msg"]["wasm"]["execute"]["contract_addr"],
+        "addr0001"
+    );
+    assert_eq!(
+        output["wasm_msg"]["wasm"]["execute"]["msg"],
+        output["msg_base64"]
+    );
+}
+
+#[test]
+fn invalid_governance_msg_is_rejected() {
+    let result = encode_governance(&args(&[
+        r#"{"update_deposit_caps":{"max_total_nasset":1000}}"#,
+    ]));
+
+    assert!(result.unwrap_err().starts_with("invalid message"));
+}
+
+#[test]
+fn deposit_is_cw20_send_with_deposit_hook() {
+    let output = encode_deposit(&args(&[
+        "--autocompounder",
+        "addr0001",
+        "--nasset-token",
+        "addr0002",
+        "--amount",
+        "100",
+    ]))
+    .unwrap();
+    let output: Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(
+        output["hook_msg"],
+        serde_json::to_value(&Cw20HookMsg::Deposit { recipient: None }).unwrap()
+    );
+    assert_eq!(output["cw20_msg"]["send"]["contract"], "addr0001");
+    assert_eq!(output["cw20_msg"]["send"]["amount"], "100");
+    assert_eq!(
+        output["wasm_msg"]["wasm"]["execute"]["contract_addr"],
+        "addr0002"
+    );
+}
+
+#[test]
+fn deposit_requires_amount() {
+    let result = encode_deposit(&args(&[
+        "--autocompounder",
+        "addr0001",
+        "--nasset-token",
+        "addr0002",
+    ]));
+
+    assert_eq!(result.unwrap_err(), "missing '--amount' option");
+}
@@ -0,0 +1,10 @@
+mod decode;
+mod messages;
+mod share_value;
+
+use crate::args::Args;
+
+fn args(args: &[&str]) -> Args {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    Args::parse(&args).unwrap()
+}
@@ -0,0 +1,42 @@
+use super::args;
+use crate::share_value::{calculate_share_value, share_value, ShareValue};
+use cosmwasm_std::{Decimal, Uint128};
+
+#[test]
+fn share_value_rounds_down() {
+    assert_eq!(
+        calculate_share_value(
+            Uint128::from(1_000u128),
+            Uint128::from(300u128),
+            Uint128::from(100u128)
+        )
+        .unwrap(),
+        ShareValue {
+            total_nasset: Uint128::from(1_000u128),
+            auto_nasset_supply: Uint128::from(300u128),
+            nasset_per_auto_nasset: Decimal::from_ratio(1_000u128, 300u128),
+            auto_nasset_amount: Uint128::from(100u128),
+            nasset_amount: Uint128::from(333u128),
+        }
+    );
+}
+
+#[test]
+fn share_value_of_vault_snapshot() {
+    let vault = r#"{
+        "nasset_token": "addr0001",
+        "auto_nasset_token": "addr0002",
+        "psi_to_nasset_pair": "addr0003",
+        "nasset_token_rewards": "addr0004",
+        "max_total_nasset": "1000000000",
+        "max_nasset_per_user": null,
+        "total_nasset": "2000",
+        "auto_nasset_supply": "1000"
+    }"#;
+
+    let output = share_value(&args(&[vault, "--shares", "10"])).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(output["nasset_per_auto_nasset"], "2");
+    assert_eq!(output["nasset_amount"], "20");
+}
@@ -0,0 +1,84 @@
+//! Two-step governance handover shared by autocompounder, factory and router:
+//! current governance proposes new address with 'UpdateGovernanceContract',
+//! new governance takes over with 'AcceptGovernance' before proposal expires.
+use cosmwasm_std::{
+    Addr, BlockInfo, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GovernanceUpdateState {
+    pub new_governance_contract_addr: Addr,
+    pub wait_approve_until: u64,
+}
+
+static KEY_GOVERNANCE_UPDATE: Item<GovernanceUpdateState> = Item::new("gov_update");
+
+pub fn load_gov_update(storage: &dyn Storage) -> StdResult<GovernanceUpdateState> {
+    KEY_GOVERNANCE_UPDATE.load(storage)
+}
+
+pub fn store_gov_update(
+    storage: &mut dyn Storage,
+    gov_update: &GovernanceUpdateState,
+) -> StdResult<()> {
+    KEY_GOVERNANCE_UPDATE.save(storage, gov_update)
+}
+
+pub fn remove_gov_update(storage: &mut dyn Storage) {
+    KEY_GOVERNANCE_UPDATE.remove(storage)
+}
+
+pub fn update_governance_addr(
+    deps: DepsMut,
+    env: Env,
+    gov_addr: String,
+    seconds_to_wait_for_accept_gov_tx: u64,
+) -> StdResult<Response> {
+    let current_time = get_time(&env.block);
+    let gov_update = GovernanceUpdateState {
+        new_governance_contract_addr: deps.api.addr_validate(&gov_addr)?,
+        wait_approve_until: current_time + seconds_to_wait_for_accept_gov_tx,
+    };
+    store_gov_update(deps.storage, &gov_update)?;
+    Ok(Response::default())
+}
+
+/// Accepts pending governance update, 'store_governance' saves new address to contract config
+pub fn accept_governance<F>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    store_governance: F,
+) -> StdResult<Response>
+where
+    F: FnOnce(&mut dyn Storage, Addr) -> StdResult<()>,
+{
+    let gov_update = load_gov_update(deps.storage)?;
+    let current_time = get_time(&env.block);
+
+    if gov_update.wait_approve_until < current_time {
+        return Err(StdError::generic_err(
+            "too late to accept governance owning",
+        ));
+    }
+
+    if info.sender != gov_update.new_governance_contract_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let new_gov_add_str = gov_update.new_governance_contract_addr.to_string();
+
+    store_governance(deps.storage, gov_update.new_governance_contract_addr)?;
+    remove_gov_update(deps.storage);
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "change_governance_contract"),
+        ("new_address", &new_gov_add_str),
+    ]))
+}
+
+fn get_time(block: &BlockInfo) -> u64 {
+    block.time.seconds()
+}
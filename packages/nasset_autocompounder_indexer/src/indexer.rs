@@ -0,0 +1,149 @@
+use cosmwasm_std::{from_slice, Addr, Decimal, Event, StdResult, Uint128};
+use nasset_autocompounder_interface::events::{
+    AutocompounderEvent, CompoundEvent, DepositEvent, WithdrawEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::store::{Position, RatePoint, Store, UserAction, UserActionKind};
+
+/// Transaction result as it is exported from chain: block and all emitted events
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IndexedTx {
+    pub height: u64,
+    //block time in seconds
+    pub timestamp: u64,
+    pub events: Vec<Event>,
+}
+
+pub struct Indexer<S: Store> {
+    store: S,
+    //events of other contracts are dropped, anyone can emit event with autocompounder type
+    autocompounders: Vec<Addr>,
+}
+
+impl<S: Store> Indexer<S> {
+    pub fn new(store: S, autocompounders: Vec<Addr>) -> Self {
+        Indexer {
+            store,
+            autocompounders,
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Ingests JSON array of transactions, they have to be ordered by height
+    pub fn ingest_json(&mut self, json: &[u8]) -> StdResult<()> {
+        let txs: Vec<IndexedTx> = from_slice(json)?;
+        for tx in txs.iter() {
+            self.ingest_tx(tx)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn ingest_tx(&mut self, tx: &IndexedTx) -> StdResult<()> {
+        for event in AutocompounderEvent::parse_all_from(&tx.events, &self.autocompounders)? {
+            match event {
+                AutocompounderEvent::Deposit(event) => self.on_deposit(tx, event),
+                AutocompounderEvent::Withdraw(event) => self.on_withdraw(tx, event),
+                AutocompounderEvent::Compound(event) => self.on_compound(tx, event),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn user_history(&self, user: &Addr) -> Vec<UserAction> {
+        self.store.user_actions(user)
+    }
+
+    pub fn position(&self, user: &Addr, nasset_token: &Addr) -> Position {
+        self.store.position(user, nasset_token)
+    }
+
+    pub fn exchange_rates(&self, nasset_token: &Addr) -> Vec<RatePoint> {
+        self.store.rate_points(nasset_token)
+    }
+
+    /// nAsset which user would get for anAsset at last known exchange rate
+    pub fn position_value(&self, user: &Addr, nasset_token: &Addr) -> Uint128 {
+        let position = self.store.position(user, nasset_token);
+        match self.store.rate_points(nasset_token).last() {
+            Some(point) => position.shares * point.exchange_rate,
+            None => Uint128::zero(),
+        }
+    }
+
+    fn on_deposit(&mut self, tx: &IndexedTx, event: DepositEvent) {
+        let mut position = self.store.position(&event.recipient, &event.nasset_token);
+        position.shares += event.shares_minted;
+        position.cost_basis += event.nasset_amount;
+        self.store
+            .save_position(&event.recipient, &event.nasset_token, position);
+
+        self.store.add_user_action(
+            &event.recipient,
+            UserAction {
+                height: tx.height,
+                timestamp: tx.timestamp,
+                kind: UserActionKind::Deposit,
+                nasset_token: event.nasset_token.clone(),
+                nasset_amount: event.nasset_amount,
+                shares: event.shares_minted,
+                withdrawal_fee: Uint128::zero(),
+                exchange_rate: event.exchange_rate,
+            },
+        );
+        self.add_rate_point(tx, &event.nasset_token, event.exchange_rate);
+    }
+
+    fn on_withdraw(&mut self, tx: &IndexedTx, event: WithdrawEvent) {
+        let mut position = self.store.position(&event.sender, &event.nasset_token);
+        //anAsset received with cw20 'Transfer' is unknown, it is treated as free
+        let known_shares_burned = event.shares_burned.min(position.shares);
+        let burned_cost = if position.shares.is_zero() {
+            Uint128::zero()
+        } else {
+            position
+                .cost_basis
+                .multiply_ratio(known_shares_burned, position.shares)
+        };
+        position.shares -= known_shares_burned;
+        position.cost_basis -= burned_cost;
+        position.realized_profit += event.nasset_amount.u128() as i128 - burned_cost.u128() as i128;
+        self.store
+            .save_position(&event.sender, &event.nasset_token, position);
+
+        self.store.add_user_action(
+            &event.sender,
+            UserAction {
+                height: tx.height,
+                timestamp: tx.timestamp,
+                kind: UserActionKind::Withdraw,
+                nasset_token: event.nasset_token.clone(),
+                nasset_amount: event.nasset_amount,
+                shares: event.shares_burned,
+                withdrawal_fee: event.withdrawal_fee,
+                exchange_rate: event.exchange_rate,
+            },
+        );
+        self.add_rate_point(tx, &event.nasset_token, event.exchange_rate);
+    }
+
+    fn on_compound(&mut self, tx: &IndexedTx, event: CompoundEvent) {
+        self.add_rate_point(tx, &event.nasset_token, event.exchange_rate);
+    }
+
+    fn add_rate_point(&mut self, tx: &IndexedTx, nasset_token: &Addr, exchange_rate: Decimal) {
+        self.store.add_rate_point(
+            nasset_token,
+            RatePoint {
+                height: tx.height,
+                timestamp: tx.timestamp,
+                exchange_rate,
+            },
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Rebuilds per-user deposit/withdraw history, realized profit in nAsset and
+//! anAsset exchange rate timeseries from events emitted by autocompounder.
+//!
+//! Only events of given autocompounder contracts are indexed: anAsset moved with
+//! cw20 'Transfer' is not reflected in positions.
+mod indexer;
+mod store;
+
+pub use indexer::{IndexedTx, Indexer};
+pub use store::{MemoryStore, Position, RatePoint, Store, UserAction, UserActionKind};
+
+#[cfg(test)]
+mod tests;
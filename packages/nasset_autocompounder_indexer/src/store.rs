@@ -0,0 +1,108 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserActionKind {
+    Deposit,
+    Withdraw,
+}
+
+/// Deposit or withdraw of one user
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UserAction {
+    pub height: u64,
+    pub timestamp: u64,
+    pub kind: UserActionKind,
+    pub nasset_token: Addr,
+    //deposited or received nAsset, withdrawal fee excluded
+    pub nasset_amount: Uint128,
+    //minted or burned anAsset
+    pub shares: Uint128,
+    pub withdrawal_fee: Uint128,
+    //nAsset per anAsset after action
+    pub exchange_rate: Decimal,
+}
+
+/// anAsset held by user in one vault, with average cost in nAsset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Position {
+    pub shares: Uint128,
+    //nAsset paid for 'shares'
+    pub cost_basis: Uint128,
+    //received nAsset minus cost of burned anAsset over all withdrawals
+    pub realized_profit: i128,
+}
+
+/// Exchange rate after deposit, withdraw or compound
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RatePoint {
+    pub height: u64,
+    pub timestamp: u64,
+    pub exchange_rate: Decimal,
+}
+
+/// Storage of indexed data, vaults are identified by nAsset token
+pub trait Store {
+    fn add_user_action(&mut self, user: &Addr, action: UserAction);
+    fn user_actions(&self, user: &Addr) -> Vec<UserAction>;
+
+    fn position(&self, user: &Addr, nasset_token: &Addr) -> Position;
+    fn save_position(&mut self, user: &Addr, nasset_token: &Addr, position: Position);
+
+    fn add_rate_point(&mut self, nasset_token: &Addr, point: RatePoint);
+    fn rate_points(&self, nasset_token: &Addr) -> Vec<RatePoint>;
+}
+
+#[derive(Default)]
+pub struct MemoryStore {
+    user_actions: BTreeMap<Addr, Vec<UserAction>>,
+    positions: BTreeMap<(Addr, Addr), Position>,
+    rate_points: BTreeMap<Addr, Vec<RatePoint>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Store for MemoryStore {
+    fn add_user_action(&mut self, user: &Addr, action: UserAction) {
+        self.user_actions
+            .entry(user.clone())
+            .or_default()
+            .push(action);
+    }
+
+    fn user_actions(&self, user: &Addr) -> Vec<UserAction> {
+        self.user_actions.get(user).cloned().unwrap_or_default()
+    }
+
+    fn position(&self, user: &Addr, nasset_token: &Addr) -> Position {
+        self.positions
+            .get(&(user.clone(), nasset_token.clone()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn save_position(&mut self, user: &Addr, nasset_token: &Addr, position: Position) {
+        self.positions
+            .insert((user.clone(), nasset_token.clone()), position);
+    }
+
+    fn add_rate_point(&mut self, nasset_token: &Addr, point: RatePoint) {
+        self.rate_points
+            .entry(nasset_token.clone())
+            .or_default()
+            .push(point);
+    }
+
+    fn rate_points(&self, nasset_token: &Addr) -> Vec<RatePoint> {
+        self.rate_points
+            .get(nasset_token)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
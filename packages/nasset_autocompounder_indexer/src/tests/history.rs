@@ -0,0 +1,199 @@
+use super::{indexed_history, new_indexer, AUTOCOMPOUNDER, NASSET_TOKEN, USER_A, USER_B};
+use crate::{IndexedTx, Position, UserActionKind};
+use cosmwasm_std::{Addr, Decimal, Event, Uint128};
+use std::str::FromStr;
+
+#[test]
+fn user_history_is_rebuilt() {
+    let indexer = indexed_history();
+
+    let history = indexer.user_history(&Addr::unchecked(USER_A));
+    assert_eq!(2, history.len());
+    assert_eq!(UserActionKind::Deposit, history[0].kind);
+    assert_eq!(12346, history[0].height);
+    assert_eq!(1571801019, history[0].timestamp);
+    assert_eq!(Uint128::from(100u128), history[0].nasset_amount);
+    assert_eq!(Uint128::from(100u128), history[0].shares);
+    assert_eq!(UserActionKind::Withdraw, history[1].kind);
+    assert_eq!(12349, history[1].height);
+    assert_eq!(Uint128::from(110u128), history[1].nasset_amount);
+    assert_eq!(Uint128::from(50u128), history[1].shares);
+    assert_eq!(Uint128::zero(), history[1].withdrawal_fee);
+
+    let history = indexer.user_history(&Addr::unchecked(USER_B));
+    assert_eq!(1, history.len());
+    assert_eq!(UserActionKind::Deposit, history[0].kind);
+    assert_eq!(Uint128::from(50u128), history[0].nasset_amount);
+    assert_eq!(Uint128::from(25u128), history[0].shares);
+    assert_eq!(Decimal::from_ratio(2u128, 1u128), history[0].exchange_rate);
+
+    //compound sender is not a depositor
+    assert!(indexer
+        .user_history(&Addr::unchecked("addr9999"))
+        .is_empty());
+}
+
+#[test]
+fn realized_profit_is_calculated() {
+    let indexer = indexed_history();
+    let nasset_token = Addr::unchecked(NASSET_TOKEN);
+
+    //half of 100 nAsset deposit is withdrawn for 110 nAsset
+    assert_eq!(
+        Position {
+            shares: Uint128::from(50u128),
+            cost_basis: Uint128::from(50u128),
+            realized_profit: 60,
+        },
+        indexer.position(&Addr::unchecked(USER_A), &nasset_token)
+    );
+    assert_eq!(
+        Position {
+            shares: Uint128::from(25u128),
+            cost_basis: Uint128::from(50u128),
+            realized_profit: 0,
+        },
+        indexer.position(&Addr::unchecked(USER_B), &nasset_token)
+    );
+
+    //valued at last rate 2.2
+    assert_eq!(
+        Uint128::from(110u128),
+        indexer.position_value(&Addr::unchecked(USER_A), &nasset_token)
+    );
+    assert_eq!(
+        Uint128::from(55u128),
+        indexer.position_value(&Addr::unchecked(USER_B), &nasset_token)
+    );
+}
+
+#[test]
+fn exchange_rate_timeseries_is_rebuilt() {
+    let indexer = indexed_history();
+
+    let rates: Vec<(u64, Decimal)> = indexer
+        .exchange_rates(&Addr::unchecked(NASSET_TOKEN))
+        .into_iter()
+        .map(|point| (point.height, point.exchange_rate))
+        .collect();
+    assert_eq!(
+        vec![
+            (12346, Decimal::one()),
+            (12347, Decimal::from_str("2").unwrap()),
+            (12348, Decimal::from_str("2").unwrap()),
+            (12349, Decimal::from_str("2.2").unwrap()),
+            (12349, Decimal::from_str("2.2").unwrap()),
+        ],
+        rates
+    );
+
+    assert!(indexer
+        .exchange_rates(&Addr::unchecked("unknown_token"))
+        .is_empty());
+}
+
+#[test]
+fn withdraw_of_transferred_shares_has_no_cost() {
+    let mut indexer = new_indexer();
+    indexer
+        .ingest_tx(&IndexedTx {
+            height: 1,
+            timestamp: 1,
+            events: vec![Event::new("wasm-nexus_autocompounder_withdraw")
+                .add_attribute("_contract_address", AUTOCOMPOUNDER)
+                .add_attribute("nasset_token", NASSET_TOKEN)
+                .add_attribute("sender", USER_B)
+                .add_attribute("recipient", USER_B)
+                .add_attribute("shares_burned", "10")
+                .add_attribute("nasset_amount", "20")
+                .add_attribute("withdrawal_fee", "0")
+                .add_attribute("exchange_rate", "2")],
+        })
+        .unwrap();
+
+    assert_eq!(
+        Position {
+            shares: Uint128::zero(),
+            cost_basis: Uint128::zero(),
+            realized_profit: 20,
+        },
+        indexer.position(&Addr::unchecked(USER_B), &Addr::unchecked(NASSET_TOKEN))
+    );
+}
+
+#[test]
+fn foreign_events_are_skipped() {
+    let mut indexer = new_indexer();
+    indexer
+        .ingest_tx(&IndexedTx {
+            height: 1,
+            timestamp: 1,
+            events: vec![Event::new("wasm").add_attribute("action", "transfer")],
+        })
+        .unwrap();
+
+    assert!(indexer
+        .exchange_rates(&Addr::unchecked(NASSET_TOKEN))
+        .is_empty());
+}
+
+#[test]
+fn malformed_event_is_error() {
+    let mut indexer = new_indexer();
+    let result = indexer.ingest_tx(&IndexedTx {
+        height: 1,
+        timestamp: 1,
+        events: vec![Event::new("wasm-nexus_autocompounder_compound")
+            .add_attribute("_contract_address", AUTOCOMPOUNDER)
+            .add_attribute("nasset_token", NASSET_TOKEN)],
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn events_of_untrusted_contracts_are_dropped() {
+    let mut indexer = new_indexer();
+    let fake_deposit = |contract_addr: &str| {
+        Event::new("wasm-nexus_autocompounder_deposit")
+            .add_attribute("_contract_address", contract_addr)
+            .add_attribute("nasset_token", NASSET_TOKEN)
+            .add_attribute("sender", USER_A)
+            .add_attribute("recipient", USER_A)
+            .add_attribute("nasset_amount", "1000000")
+            .add_attribute("shares_minted", "1")
+            .add_attribute("exchange_rate", "1000000")
+    };
+    indexer
+        .ingest_tx(&IndexedTx {
+            height: 1,
+            timestamp: 1,
+            events: vec![
+                fake_deposit("addr6666"),
+                // event without emitter is not trusted either
+                Event::new("wasm-nexus_autocompounder_compound")
+                    .add_attribute("nasset_token", NASSET_TOKEN)
+                    .add_attribute("exchange_rate", "1000000"),
+            ],
+        })
+        .unwrap();
+
+    assert!(indexer.user_history(&Addr::unchecked(USER_A)).is_empty());
+    assert_eq!(
+        Position::default(),
+        indexer.position(&Addr::unchecked(USER_A), &Addr::unchecked(NASSET_TOKEN))
+    );
+    assert!(indexer
+        .exchange_rates(&Addr::unchecked(NASSET_TOKEN))
+        .is_empty());
+
+    // same event of trusted autocompounder is indexed
+    indexer
+        .ingest_tx(&IndexedTx {
+            height: 2,
+            timestamp: 2,
+            events: vec![fake_deposit(AUTOCOMPOUNDER)],
+        })
+        .unwrap();
+    assert_eq!(1, indexer.user_history(&Addr::unchecked(USER_A)).len());
+}
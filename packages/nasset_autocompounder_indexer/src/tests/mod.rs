@@ -0,0 +1,22 @@
+mod history;
+
+use crate::{Indexer, MemoryStore};
+use cosmwasm_std::Addr;
+
+// generated by 'indexer_fixtures' test of nasset_autocompounder contract
+pub const HISTORY_FIXTURE: &str = include_str!("fixtures/history.json");
+// mock contract address of autocompounder which emitted fixture events
+pub const AUTOCOMPOUNDER: &str = "cosmos2contract";
+pub const NASSET_TOKEN: &str = "addr0001";
+pub const USER_A: &str = "addr0100";
+pub const USER_B: &str = "addr0101";
+
+pub fn new_indexer() -> Indexer<MemoryStore> {
+    Indexer::new(MemoryStore::new(), vec![Addr::unchecked(AUTOCOMPOUNDER)])
+}
+
+pub fn indexed_history() -> Indexer<MemoryStore> {
+    let mut indexer = new_indexer();
+    indexer.ingest_json(HISTORY_FIXTURE.as_bytes()).unwrap();
+    indexer
+}
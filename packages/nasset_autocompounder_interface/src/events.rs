@@ -0,0 +1,254 @@
+//! Custom events emitted by autocompounder and their decoders for indexers.
+//!
+//! Chain prefixes custom event types with "wasm-", decoders accept both forms.
+
+use cosmwasm_std::{Addr, Decimal, Event, StdError, StdResult, Uint128};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub const DEPOSIT_EVENT: &str = "nexus_autocompounder_deposit";
+pub const WITHDRAW_EVENT: &str = "nexus_autocompounder_withdraw";
+pub const COMPOUND_EVENT: &str = "nexus_autocompounder_compound";
+
+const WASM_EVENT_PREFIX: &str = "wasm-";
+// added by chain to every event emitted by contract
+pub const CONTRACT_ADDRESS_ATTRIBUTE: &str = "_contract_address";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DepositEvent {
+    //vault nAsset token
+    pub nasset_token: Addr,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub nasset_amount: Uint128,
+    pub shares_minted: Uint128,
+    //nAsset per anAsset after deposit
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawEvent {
+    //vault nAsset token
+    pub nasset_token: Addr,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub shares_burned: Uint128,
+    //nAsset sent to recipient, fee excluded
+    pub nasset_amount: Uint128,
+    pub withdrawal_fee: Uint128,
+    //nAsset per anAsset after withdraw
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompoundEvent {
+    //vault nAsset token
+    pub nasset_token: Addr,
+    pub sender: Addr,
+    pub psi_claimed: Uint128,
+    //claimed PSI + PSI which was already on contract balance
+    pub psi_sold: Uint128,
+    pub nasset_bought: Uint128,
+    //nAsset per anAsset after compound
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutocompounderEvent {
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    Compound(CompoundEvent),
+}
+
+impl From<DepositEvent> for Event {
+    fn from(event: DepositEvent) -> Self {
+        Event::new(DEPOSIT_EVENT)
+            .add_attribute("nasset_token", event.nasset_token)
+            .add_attribute("sender", event.sender)
+            .add_attribute("recipient", event.recipient)
+            .add_attribute("nasset_amount", event.nasset_amount)
+            .add_attribute("shares_minted", event.shares_minted)
+            .add_attribute("exchange_rate", event.exchange_rate.to_string())
+    }
+}
+
+impl From<WithdrawEvent> for Event {
+    fn from(event: WithdrawEvent) -> Self {
+        Event::new(WITHDRAW_EVENT)
+            .add_attribute("nasset_token", event.nasset_token)
+            .add_attribute("sender", event.sender)
+            .add_attribute("recipient", event.recipient)
+            .add_attribute("shares_burned", event.shares_burned)
+            .add_attribute("nasset_amount", event.nasset_amount)
+            .add_attribute("withdrawal_fee", event.withdrawal_fee)
+            .add_attribute("exchange_rate", event.exchange_rate.to_string())
+    }
+}
+
+impl From<CompoundEvent> for Event {
+    fn from(event: CompoundEvent) -> Self {
+        Event::new(COMPOUND_EVENT)
+            .add_attribute("nasset_token", event.nasset_token)
+            .add_attribute("sender", event.sender)
+            .add_attribute("psi_claimed", event.psi_claimed)
+            .add_attribute("psi_sold", event.psi_sold)
+            .add_attribute("nasset_bought", event.nasset_bought)
+            .add_attribute("exchange_rate", event.exchange_rate.to_string())
+    }
+}
+
+impl TryFrom<&Event> for DepositEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        check_event_type(event, DEPOSIT_EVENT)?;
+        Ok(DepositEvent {
+            nasset_token: Addr::unchecked(attribute(event, "nasset_token")?),
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            recipient: Addr::unchecked(attribute(event, "recipient")?),
+            nasset_amount: parse_attribute(event, "nasset_amount")?,
+            shares_minted: parse_attribute(event, "shares_minted")?,
+            exchange_rate: parse_attribute(event, "exchange_rate")?,
+        })
+    }
+}
+
+impl TryFrom<&Event> for WithdrawEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        check_event_type(event, WITHDRAW_EVENT)?;
+        Ok(WithdrawEvent {
+            nasset_token: Addr::unchecked(attribute(event, "nasset_token")?),
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            recipient: Addr::unchecked(attribute(event, "recipient")?),
+            shares_burned: parse_attribute(event, "shares_burned")?,
+            nasset_amount: parse_attribute(event, "nasset_amount")?,
+            withdrawal_fee: parse_attribute(event, "withdrawal_fee")?,
+            exchange_rate: parse_attribute(event, "exchange_rate")?,
+        })
+    }
+}
+
+impl TryFrom<&Event> for CompoundEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        check_event_type(event, COMPOUND_EVENT)?;
+        Ok(CompoundEvent {
+            nasset_token: Addr::unchecked(attribute(event, "nasset_token")?),
+            sender: Addr::unchecked(attribute(event, "sender")?),
+            psi_claimed: parse_attribute(event, "psi_claimed")?,
+            psi_sold: parse_attribute(event, "psi_sold")?,
+            nasset_bought: parse_attribute(event, "nasset_bought")?,
+            exchange_rate: parse_attribute(event, "exchange_rate")?,
+        })
+    }
+}
+
+impl From<AutocompounderEvent> for Event {
+    fn from(event: AutocompounderEvent) -> Self {
+        match event {
+            AutocompounderEvent::Deposit(event) => event.into(),
+            AutocompounderEvent::Withdraw(event) => event.into(),
+            AutocompounderEvent::Compound(event) => event.into(),
+        }
+    }
+}
+
+impl AutocompounderEvent {
+    /// Returns None for events which are not emitted by autocompounder
+    pub fn parse(event: &Event) -> StdResult<Option<Self>> {
+        let parsed = match event_type(event) {
+            DEPOSIT_EVENT => AutocompounderEvent::Deposit(DepositEvent::try_from(event)?),
+            WITHDRAW_EVENT => AutocompounderEvent::Withdraw(WithdrawEvent::try_from(event)?),
+            COMPOUND_EVENT => AutocompounderEvent::Compound(CompoundEvent::try_from(event)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(parsed))
+    }
+
+    /// Decodes autocompounder events from transaction events, skipping all others
+    pub fn parse_all(events: &[Event]) -> StdResult<Vec<Self>> {
+        let mut parsed = vec![];
+        for event in events {
+            if let Some(event) = Self::parse(event)? {
+                parsed.push(event);
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Same as 'parse_all', but only for events emitted by one of 'contracts'. Any contract
+    /// can emit event of autocompounder type, so events of unknown contracts are skipped
+    pub fn parse_all_from(events: &[Event], contracts: &[Addr]) -> StdResult<Vec<Self>> {
+        let mut parsed = vec![];
+        for event in events {
+            let is_trusted = emitter(event)
+                .map(|emitter| {
+                    contracts
+                        .iter()
+                        .any(|contract| contract.as_str() == emitter)
+                })
+                .unwrap_or(false);
+            if !is_trusted {
+                continue;
+            }
+
+            if let Some(event) = Self::parse(event)? {
+                parsed.push(event);
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Contract which emitted event, None for events not emitted by contract
+pub fn emitter(event: &Event) -> Option<&str> {
+    attribute(event, CONTRACT_ADDRESS_ATTRIBUTE).ok()
+}
+
+fn event_type(event: &Event) -> &str {
+    event
+        .ty
+        .strip_prefix(WASM_EVENT_PREFIX)
+        .unwrap_or(&event.ty)
+}
+
+fn check_event_type(event: &Event, expected: &str) -> StdResult<()> {
+    if event_type(event) != expected {
+        return Err(StdError::generic_err(format!(
+            "expected '{}' event, got '{}'",
+            expected, event.ty
+        )));
+    }
+
+    Ok(())
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.as_str())
+        .ok_or_else(|| {
+            StdError::generic_err(format!("'{}' event has no '{}' attribute", event.ty, key))
+        })
+}
+
+fn parse_attribute<T>(event: &Event, key: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: ToString,
+{
+    let value = attribute(event, key)?;
+    value.parse().map_err(|err: T::Err| {
+        StdError::parse_err(key, format!("'{}': {}", value, err.to_string()))
+    })
+}
@@ -0,0 +1,66 @@
+use cosmwasm_std::{to_binary, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+use crate::msg::{Cw20HookMsg, ExecuteMsg};
+
+/// 'Send' of nAsset to autocompounder, anAsset is minted to 'recipient' (sender by default)
+pub fn deposit_msg(
+    autocompounder_addr: &str,
+    nasset_token_addr: &str,
+    amount: Uint128,
+    recipient: Option<String>,
+) -> StdResult<CosmosMsg> {
+    send_to_autocompounder(
+        autocompounder_addr,
+        nasset_token_addr,
+        amount,
+        &Cw20HookMsg::Deposit { recipient },
+    )
+}
+
+/// 'Send' of anAsset to autocompounder, nAsset is sent to 'recipient' (sender by default).
+/// With embedded share token 'auto_nasset_token_addr' is autocompounder itself
+pub fn withdraw_msg(
+    autocompounder_addr: &str,
+    auto_nasset_token_addr: &str,
+    amount: Uint128,
+    recipient: Option<String>,
+) -> StdResult<CosmosMsg> {
+    send_to_autocompounder(
+        autocompounder_addr,
+        auto_nasset_token_addr,
+        amount,
+        &Cw20HookMsg::Withdraw { recipient },
+    )
+}
+
+/// Compound primary vault when 'nasset_token_addr' is not set
+pub fn compound_msg(
+    autocompounder_addr: &str,
+    nasset_token_addr: Option<String>,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: autocompounder_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::Compound { nasset_token_addr })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn send_to_autocompounder(
+    autocompounder_addr: &str,
+    token_addr: &str,
+    amount: Uint128,
+    hook_msg: &Cw20HookMsg,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token_addr.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: autocompounder_addr.to_string(),
+            amount,
+            msg: to_binary(hook_msg)?,
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
@@ -0,0 +1,12 @@
+//! Messages, responses and events of nasset autocompounder, with its share math,
+//! helpers to build deposit/withdraw messages and typed queries for integrating contracts.
+pub mod events;
+pub mod helpers;
+pub mod msg;
+pub mod querier;
+// parses instantiate replies in autocompounder and factory contracts
+pub mod reply_response;
+pub mod share_math;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,465 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Binary, Decimal, Uint128};
+use cw20::{Cw20ReceiveMsg, Expiration};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub nasset_token_addr: String,
+    pub psi_token_addr: String,
+    pub psi_to_nasset_pair_addr: String,
+    pub governance_contract_addr: String,
+    pub cw20_token_code_id: u64,
+    pub nasset_token_rewards_addr: String,
+    pub collateral_token_symbol: String,
+    //how many seconds queued config update should wait before execution
+    pub config_update_delay: u64,
+    //deposits are rejected when vault nAsset balance would exceed this value
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+    pub withdrawal_fee: Option<WithdrawalFeeConfig>,
+    pub withdrawal_mode: Option<WithdrawalMode>,
+    //separate cw20 contract by default
+    pub share_token_mode: Option<ShareTokenMode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareTokenMode {
+    //cw20 contract instantiated from 'cw20_token_code_id'
+    External {},
+    //autocompounder implements cw20 interface of anAsset itself,
+    //withdraw by sending anAsset to autocompounder with 'Send'
+    Embedded {},
+    //cw20 contract of previous deployment, so holders keep their anAsset after redeploy.
+    //Its minter has to be this autocompounder already
+    Existing { token_addr: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalMode {
+    Instant {},
+    //anAsset is locked on withdraw and can be claimed after 'unbonding_period' seconds
+    Unbonding { unbonding_period: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenQueryMode {
+    //cw20 'Balance' and 'TokenInfo' queries
+    Smart {},
+    //cheaper raw storage reads, only for tokens with known storage layout
+    Raw { layout: Cw20StorageLayout },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20StorageLayout {
+    //cw20-base 0.6+: 'token_info' key, balances by human address
+    Cw20Base {},
+    //cw20-base 0.2: length-prefixed 'token_info' key, balances by canonical address
+    Legacy {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalFeeConfig {
+    //fee right after deposit, decays linearly to zero over 'decay_period' seconds.
+    //Embedded anAsset carries timer of its depositor, anAsset owner without deposit
+    //record pays 'max_fee'
+    pub max_fee: Decimal,
+    pub decay_period: u64,
+    //fee stays in the vault when treasury is not set
+    pub treasury_addr: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    Governance {
+        governance_msg: GovernanceMsg,
+    },
+    AcceptGovernance {},
+    //compound primary vault when 'nasset_token_addr' is not set
+    Compound {
+        nasset_token_addr: Option<String>,
+    },
+    //withdraw all anAsset which finished unbonding
+    Claim {},
+    //cw20 interface of embedded anAsset token
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceMsg {
+    //queue config update, it can be executed only after 'config_update_delay' seconds.
+    //Only one update can be queued, cancel pending one to replace it
+    UpdateConfig {
+        psi_token_addr: Option<String>,
+        psi_to_nasset_pair_addr: Option<String>,
+        nasset_token_rewards_addr: Option<String>,
+        config_update_delay: Option<u64>,
+        withdrawal_fee: Option<WithdrawalFeeConfig>,
+        withdrawal_mode: Option<WithdrawalMode>,
+    },
+    ExecuteConfigUpdate {},
+    CancelConfigUpdate {},
+    //queued like 'UpdateConfig'
+    UpdateDepositCaps {
+        max_total_nasset: Uint128,
+        max_nasset_per_user: Option<Uint128>,
+    },
+    UpdateGovernanceContract {
+        gov_addr: String,
+        //how long to wait for 'AcceptGovernance' transaction
+        seconds_to_wait_for_accept_gov_tx: u64,
+    },
+    //queued like 'UpdateConfig', raw layout is checked against smart 'TokenInfo' query
+    //when queued and again when executed
+    UpdateTokenQueryMode {
+        token_addr: String,
+        mode: TokenQueryMode,
+    },
+    //queued like 'UpdateConfig'. Router deposits and withdraws on behalf of its caller,
+    //so its 'recipient' is treated as depositor and anAsset owner
+    UpdateRouter {
+        router_addr: Option<String>,
+    },
+    //not timelocked: stops deposits and compounding immediately, withdrawals still work
+    SetPaused {
+        paused: bool,
+    },
+    //queued like 'UpdateConfig', add vault for another nAsset, instantiates its own
+    //anAsset token on execution. Additional vaults don't support withdrawal fee and unbonding,
+    //so vault can't be added while any of them is enabled and they can't be enabled after
+    AddVault {
+        nasset_token_addr: String,
+        psi_to_nasset_pair_addr: String,
+        nasset_token_rewards_addr: String,
+        cw20_token_code_id: u64,
+        collateral_token_symbol: String,
+        max_total_nasset: Uint128,
+        max_nasset_per_user: Option<Uint128>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    //anAsset is minted to 'recipient' (cw20 sender by default),
+    //withdrawal fee timer is reset only when depositing to yourself
+    Deposit { recipient: Option<String> },
+    //nAsset is sent to 'recipient' (cw20 sender by default),
+    //withdrawal fee depends on last deposit of anAsset owner (cw20 sender).
+    //In unbonding mode 'recipient' has to be anAsset owner
+    Withdraw { recipient: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    //'nasset_token_addr' selects additional vault in vault queries, primary vault is used
+    //when it is not specified
+    Config {},
+    AutoNassetValue {
+        amount: Uint128,
+        nasset_token_addr: Option<String>,
+    },
+    NAssetValue {
+        amount: Uint128,
+        nasset_token_addr: Option<String>,
+    },
+    PendingConfigUpdate {},
+    DepositCapacity {
+        address: Option<String>,
+        nasset_token_addr: Option<String>,
+    },
+    //only primary vault supports unbonding
+    Unbondings {
+        address: String,
+    },
+    // ERC-4626 like vault interface, assets are nAsset and shares are anAsset
+    ConvertToShares {
+        assets: Uint128,
+        nasset_token_addr: Option<String>,
+    },
+    ConvertToAssets {
+        shares: Uint128,
+        nasset_token_addr: Option<String>,
+    },
+    PreviewDeposit {
+        assets: Uint128,
+        nasset_token_addr: Option<String>,
+    },
+    //anAsset amount to burn to receive 'assets' after withdrawal fee, same fee as in
+    //'MaxWithdraw'. Maximum fee is assumed when 'owner' is not specified
+    PreviewWithdraw {
+        assets: Uint128,
+        owner: Option<String>,
+        nasset_token_addr: Option<String>,
+    },
+    MaxDeposit {
+        receiver: Option<String>,
+        nasset_token_addr: Option<String>,
+    },
+    MaxWithdraw {
+        owner: String,
+        nasset_token_addr: Option<String>,
+    },
+    TotalAssets {
+        nasset_token_addr: Option<String>,
+    },
+    //PSI which will be sold on next compound
+    PendingRewards {
+        nasset_token_addr: Option<String>,
+    },
+    //primary or additional vault by nAsset
+    Vault {
+        nasset_token_addr: String,
+    },
+    //additional vaults, ordered by nAsset address
+    Vaults {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    //claim PSI and sell it for nAsset, without executing anything
+    SimulateCompound {
+        nasset_token_addr: Option<String>,
+    },
+    //lifetime totals of the vault
+    Stats {
+        nasset_token_addr: Option<String>,
+    },
+    //withdrawal fee is included only when address is specified
+    SimulateWithdraw {
+        cnasset_amount: Uint128,
+        address: Option<String>,
+        nasset_token_addr: Option<String>,
+    },
+    TokenQueryMode {
+        token_addr: String,
+    },
+    //cw20 queries of embedded anAsset token
+    Balance {
+        address: String,
+    },
+    TokenInfo {},
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub nasset_token_addr: String,
+    pub auto_nasset_token_addr: String,
+    pub psi_token_addr: String,
+    pub psi_to_nasset_pair_addr: String,
+    pub governance_contract_addr: String,
+    pub nasset_token_rewards_addr: String,
+    pub config_update_delay: u64,
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+    pub withdrawal_fee: WithdrawalFeeConfig,
+    pub withdrawal_mode: WithdrawalMode,
+    pub router_addr: Option<String>,
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfigUpdateResponse {
+    pub psi_token_addr: Option<String>,
+    pub psi_to_nasset_pair_addr: Option<String>,
+    pub nasset_token_rewards_addr: Option<String>,
+    pub config_update_delay: Option<u64>,
+    pub withdrawal_fee: Option<WithdrawalFeeConfig>,
+    pub withdrawal_mode: Option<WithdrawalMode>,
+    pub deposit_caps: Option<DepositCaps>,
+    pub token_query_mode: Option<TokenQueryModeUpdate>,
+    pub new_vault: Option<NewVault>,
+    pub router: Option<RouterUpdate>,
+    pub execute_after: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouterUpdate {
+    pub router_addr: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositCaps {
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenQueryModeUpdate {
+    pub token_addr: String,
+    pub mode: TokenQueryMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NewVault {
+    pub nasset_token_addr: String,
+    pub psi_to_nasset_pair_addr: String,
+    pub nasset_token_rewards_addr: String,
+    pub cw20_token_code_id: u64,
+    pub collateral_token_symbol: String,
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutoNassetValueResponse {
+    pub nasset_amount: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NassetValueResponse {
+    pub cnasset_amount: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositCapacityResponse {
+    pub total_nasset: Uint128,
+    pub remaining_total_nasset: Uint128,
+    //only present when address is specified and per user cap is set
+    pub user_nasset: Option<Uint128>,
+    pub remaining_user_nasset: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingResponse {
+    pub auto_nasset_amount: Uint128,
+    pub release_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingsResponse {
+    pub unbondings: Vec<UnbondingResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultSharesResponse {
+    pub shares: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultAssetsResponse {
+    pub assets: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultResponse {
+    pub nasset_token: String,
+    pub auto_nasset_token: String,
+    pub psi_to_nasset_pair: String,
+    pub nasset_token_rewards: String,
+    pub max_total_nasset: Uint128,
+    pub max_nasset_per_user: Option<Uint128>,
+    pub total_nasset: Uint128,
+    pub auto_nasset_supply: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultsResponse {
+    pub vaults: Vec<VaultResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenQueryModeResponse {
+    pub mode: TokenQueryMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    //PSI which can be claimed from nAsset rewards contract
+    pub accrued_psi: Uint128,
+    //PSI already on contract balance
+    pub psi_balance: Uint128,
+    pub total_psi: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub psi_claimed: Uint128,
+    pub psi_swapped: Uint128,
+    pub nasset_acquired: Uint128,
+    pub compounds_count: u64,
+    //block time of last compound in seconds, zero if vault was never compounded
+    pub last_compound_time: u64,
+    pub withdrawal_fees: Uint128,
+    pub nasset_deposited: Uint128,
+    pub nasset_withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateCompoundResponse {
+    pub psi_to_sell: Uint128,
+    pub nasset_out: Uint128,
+    //anAsset price in nAsset after compound
+    pub nasset_per_auto_nasset: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateWithdrawResponse {
+    //amount user receives, withdrawal fee is already subtracted
+    pub nasset_amount: Uint128,
+    pub withdrawal_fee: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    //required when migrating from version without config update timelock
+    pub config_update_delay: Option<u64>,
+}
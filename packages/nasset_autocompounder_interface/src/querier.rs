@@ -0,0 +1,191 @@
+use cosmwasm_std::{QuerierWrapper, StdResult, Uint128};
+use cw20::{BalanceResponse, TokenInfoResponse};
+use serde::de::DeserializeOwned;
+
+use crate::msg::{
+    AutoNassetValueResponse, ConfigResponse, DepositCapacityResponse, NassetValueResponse,
+    PendingConfigUpdateResponse, PendingRewardsResponse, QueryMsg, SimulateCompoundResponse,
+    SimulateWithdrawResponse, StatsResponse, TokenQueryModeResponse, UnbondingsResponse,
+    VaultAssetsResponse, VaultResponse, VaultSharesResponse, VaultsResponse,
+};
+
+/// Typed smart queries to autocompounder contract, vault queries go to primary vault
+/// unless another one is selected with 'for_vault'
+pub struct AutocompounderQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+    contract_addr: String,
+    nasset_token_addr: Option<String>,
+}
+
+impl<'a> AutocompounderQuerier<'a> {
+    pub fn new(querier: &'a QuerierWrapper<'a>, contract_addr: impl Into<String>) -> Self {
+        AutocompounderQuerier {
+            querier,
+            contract_addr: contract_addr.into(),
+            nasset_token_addr: None,
+        }
+    }
+
+    /// Additional vault by its nAsset token
+    pub fn for_vault(mut self, nasset_token_addr: impl Into<String>) -> Self {
+        self.nasset_token_addr = Some(nasset_token_addr.into());
+        self
+    }
+
+    pub fn config(&self) -> StdResult<ConfigResponse> {
+        self.query(&QueryMsg::Config {})
+    }
+
+    pub fn auto_nasset_value(&self, amount: Uint128) -> StdResult<AutoNassetValueResponse> {
+        self.query(&QueryMsg::AutoNassetValue {
+            amount,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn nasset_value(&self, amount: Uint128) -> StdResult<NassetValueResponse> {
+        self.query(&QueryMsg::NAssetValue {
+            amount,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn pending_config_update(&self) -> StdResult<Option<PendingConfigUpdateResponse>> {
+        self.query(&QueryMsg::PendingConfigUpdate {})
+    }
+
+    pub fn deposit_capacity(&self, address: Option<String>) -> StdResult<DepositCapacityResponse> {
+        self.query(&QueryMsg::DepositCapacity {
+            address,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn unbondings(&self, address: impl Into<String>) -> StdResult<UnbondingsResponse> {
+        self.query(&QueryMsg::Unbondings {
+            address: address.into(),
+        })
+    }
+
+    pub fn convert_to_shares(&self, assets: Uint128) -> StdResult<VaultSharesResponse> {
+        self.query(&QueryMsg::ConvertToShares {
+            assets,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn convert_to_assets(&self, shares: Uint128) -> StdResult<VaultAssetsResponse> {
+        self.query(&QueryMsg::ConvertToAssets {
+            shares,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn preview_deposit(&self, assets: Uint128) -> StdResult<VaultSharesResponse> {
+        self.query(&QueryMsg::PreviewDeposit {
+            assets,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn preview_withdraw(
+        &self,
+        assets: Uint128,
+        owner: Option<String>,
+    ) -> StdResult<VaultSharesResponse> {
+        self.query(&QueryMsg::PreviewWithdraw {
+            assets,
+            owner,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn max_deposit(&self, receiver: Option<String>) -> StdResult<VaultAssetsResponse> {
+        self.query(&QueryMsg::MaxDeposit {
+            receiver,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn max_withdraw(&self, owner: impl Into<String>) -> StdResult<VaultAssetsResponse> {
+        self.query(&QueryMsg::MaxWithdraw {
+            owner: owner.into(),
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn total_assets(&self) -> StdResult<VaultAssetsResponse> {
+        self.query(&QueryMsg::TotalAssets {
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn pending_rewards(&self) -> StdResult<PendingRewardsResponse> {
+        self.query(&QueryMsg::PendingRewards {
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn vault(&self, nasset_token_addr: impl Into<String>) -> StdResult<VaultResponse> {
+        self.query(&QueryMsg::Vault {
+            nasset_token_addr: nasset_token_addr.into(),
+        })
+    }
+
+    pub fn vaults(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VaultsResponse> {
+        self.query(&QueryMsg::Vaults { start_after, limit })
+    }
+
+    pub fn simulate_compound(&self) -> StdResult<SimulateCompoundResponse> {
+        self.query(&QueryMsg::SimulateCompound {
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn simulate_withdraw(
+        &self,
+        cnasset_amount: Uint128,
+        address: Option<String>,
+    ) -> StdResult<SimulateWithdrawResponse> {
+        self.query(&QueryMsg::SimulateWithdraw {
+            cnasset_amount,
+            address,
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn stats(&self) -> StdResult<StatsResponse> {
+        self.query(&QueryMsg::Stats {
+            nasset_token_addr: self.nasset_token_addr.clone(),
+        })
+    }
+
+    pub fn token_query_mode(
+        &self,
+        token_addr: impl Into<String>,
+    ) -> StdResult<TokenQueryModeResponse> {
+        self.query(&QueryMsg::TokenQueryMode {
+            token_addr: token_addr.into(),
+        })
+    }
+
+    //cw20 queries, only for autocompounder with embedded anAsset token
+    pub fn balance(&self, address: impl Into<String>) -> StdResult<BalanceResponse> {
+        self.query(&QueryMsg::Balance {
+            address: address.into(),
+        })
+    }
+
+    pub fn token_info(&self) -> StdResult<TokenInfoResponse> {
+        self.query(&QueryMsg::TokenInfo {})
+    }
+
+    fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> StdResult<T> {
+        self.querier
+            .query_wasm_smart(self.contract_addr.clone(), msg)
+    }
+}
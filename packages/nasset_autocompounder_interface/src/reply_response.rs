@@ -0,0 +1,153 @@
+//! Decoder of 'MsgInstantiateContractResponse', which is returned as reply data
+//! after instantiating contract:
+//!
+//! message MsgInstantiateContractResponse {
+//!     string contract_address = 1;
+//!     bytes data = 2;
+//! }
+//!
+//! Only 'contract_address' is read, all other fields are skipped.
+
+use cosmwasm_std::{Binary, Reply, StdError, StdResult};
+
+const CONTRACT_ADDRESS_FIELD: u64 = 1;
+
+const WIRE_TYPE_VARINT: u64 = 0;
+const WIRE_TYPE_FIXED64: u64 = 1;
+const WIRE_TYPE_LENGTH_DELIMITED: u64 = 2;
+const WIRE_TYPE_FIXED32: u64 = 5;
+
+// u64 varint takes at most 10 bytes
+const VARINT_MAX_BYTES: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgInstantiateContractResponse {
+    pub contract_address: String,
+}
+
+impl MsgInstantiateContractResponse {
+    pub fn new(contract_address: impl Into<String>) -> Self {
+        MsgInstantiateContractResponse {
+            contract_address: contract_address.into(),
+        }
+    }
+
+    pub fn parse(data: &[u8]) -> StdResult<Self> {
+        let mut decoder = Decoder { data, position: 0 };
+        let mut contract_address = None;
+        while !decoder.is_empty() {
+            let key = decoder.read_varint()?;
+            let field = key >> 3;
+            let wire_type = key & 0x07;
+            match (field, wire_type) {
+                (CONTRACT_ADDRESS_FIELD, WIRE_TYPE_LENGTH_DELIMITED) => {
+                    let bytes = decoder.read_length_delimited()?;
+                    let address = String::from_utf8(bytes.to_vec())
+                        .map_err(|_| parse_err("contract_address is not valid UTF-8"))?;
+                    contract_address = Some(address);
+                }
+                (CONTRACT_ADDRESS_FIELD, _) => {
+                    return Err(parse_err("contract_address has wrong wire type"));
+                }
+                (_, wire_type) => decoder.skip(wire_type)?,
+            }
+        }
+
+        match contract_address {
+            Some(contract_address) if !contract_address.is_empty() => {
+                Ok(MsgInstantiateContractResponse { contract_address })
+            }
+            _ => Err(parse_err("contract_address is missing")),
+        }
+    }
+
+    /// Reply data of successful instantiate submessage
+    pub fn from_reply(reply: Reply) -> StdResult<Self> {
+        let response = reply
+            .result
+            .into_result()
+            .map_err(|err| StdError::generic_err(format!("instantiate failed: {}", err)))?;
+        let data = response
+            .data
+            .ok_or_else(|| parse_err("reply has no data"))?;
+        Self::parse(data.as_slice())
+    }
+
+    pub fn to_binary(&self) -> Binary {
+        let address = self.contract_address.as_bytes();
+        let mut data = vec![];
+        write_varint(
+            &mut data,
+            (CONTRACT_ADDRESS_FIELD << 3) | WIRE_TYPE_LENGTH_DELIMITED,
+        );
+        write_varint(&mut data, address.len() as u64);
+        data.extend_from_slice(address);
+        Binary::from(data)
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn read_varint(&mut self) -> StdResult<u64> {
+        let mut value = 0u64;
+        for i in 0..VARINT_MAX_BYTES {
+            let byte = *self
+                .data
+                .get(self.position)
+                .ok_or_else(|| parse_err("unexpected end of varint"))?;
+            self.position += 1;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(parse_err("varint is too long"))
+    }
+
+    fn read_bytes(&mut self, len: usize) -> StdResult<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| parse_err("unexpected end of data"))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_length_delimited(&mut self) -> StdResult<&'a [u8]> {
+        let len = self.read_varint()?;
+        self.read_bytes(len as usize)
+    }
+
+    fn skip(&mut self, wire_type: u64) -> StdResult<()> {
+        match wire_type {
+            WIRE_TYPE_VARINT => self.read_varint().map(|_| ()),
+            WIRE_TYPE_FIXED64 => self.read_bytes(8).map(|_| ()),
+            WIRE_TYPE_LENGTH_DELIMITED => self.read_length_delimited().map(|_| ()),
+            WIRE_TYPE_FIXED32 => self.read_bytes(4).map(|_| ()),
+            _ => Err(parse_err(&format!("unsupported wire type {}", wire_type))),
+        }
+    }
+}
+
+fn write_varint(data: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        data.push(((value as u8) & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
+
+fn parse_err(msg: &str) -> StdError {
+    StdError::parse_err("MsgInstantiateContractResponse", msg)
+}
@@ -0,0 +1,161 @@
+//! Conversions between nAsset (assets) and anAsset (shares).
+//!
+//! Every conversion rounds in favour of the vault: depositor never gets more
+//! anAsset and withdrawer never gets more nAsset than exact math gives.
+
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Decimal, StdError, Uint128};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShareMathError {
+    // anAsset supply is not zero, but vault has no nAsset
+    ZeroAssets,
+    // there is no anAsset to withdraw
+    ZeroSupply,
+    SharesExceedSupply,
+    // deposit is too small to get at least one anAsset
+    ZeroSharesMinted,
+    // withdrawal fee is 100%, nothing is left after it
+    WholeAmountIsFee,
+}
+
+impl fmt::Display for ShareMathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShareMathError::ZeroAssets => write!(f, "vault has no nAsset"),
+            ShareMathError::ZeroSupply => write!(f, "anAsset supply is zero"),
+            ShareMathError::SharesExceedSupply => {
+                write!(f, "anAsset amount exceeds anAsset supply")
+            }
+            ShareMathError::ZeroSharesMinted => {
+                write!(f, "deposit amount is too small to mint anAsset")
+            }
+            ShareMathError::WholeAmountIsFee => {
+                write!(f, "withdrawal fee takes whole withdrawn amount")
+            }
+        }
+    }
+}
+
+impl From<ShareMathError> for StdError {
+    fn from(err: ShareMathError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+/// anAsset amount to mint for deposit.
+/// 'total_assets' is the vault nAsset balance *before* deposit.
+pub fn shares_to_mint(
+    total_assets: Uint256,
+    total_shares: Uint256,
+    deposit_amount: Uint256,
+) -> Result<Uint256, ShareMathError> {
+    // first depositor gets anAsset 1:1, even if someone sent nAsset directly to the vault
+    if total_shares.is_zero() {
+        return Ok(deposit_amount);
+    }
+
+    let shares = convert_to_shares(total_assets, total_shares, deposit_amount, Rounding::Down)?;
+    if shares.is_zero() {
+        return Err(ShareMathError::ZeroSharesMinted);
+    }
+
+    Ok(shares)
+}
+
+/// nAsset amount to send for burned anAsset.
+pub fn assets_to_withdraw(
+    total_assets: Uint256,
+    total_shares: Uint256,
+    shares: Uint256,
+) -> Result<Uint256, ShareMathError> {
+    if total_shares.is_zero() {
+        return Err(ShareMathError::ZeroSupply);
+    }
+
+    if shares > total_shares {
+        return Err(ShareMathError::SharesExceedSupply);
+    }
+
+    convert_to_assets(total_assets, total_shares, shares, Rounding::Down)
+}
+
+/// anAsset amount for given nAsset amount, 1:1 for empty vault
+pub fn convert_to_shares(
+    total_assets: Uint256,
+    total_shares: Uint256,
+    assets: Uint256,
+    rounding: Rounding,
+) -> Result<Uint256, ShareMathError> {
+    if total_shares.is_zero() {
+        return Ok(assets);
+    }
+
+    if total_assets.is_zero() {
+        return Err(ShareMathError::ZeroAssets);
+    }
+
+    Ok(mul_div(assets, total_shares, total_assets, rounding))
+}
+
+/// nAsset amount for given anAsset amount, 1:1 for empty vault
+pub fn convert_to_assets(
+    total_assets: Uint256,
+    total_shares: Uint256,
+    shares: Uint256,
+    rounding: Rounding,
+) -> Result<Uint256, ShareMathError> {
+    if total_shares.is_zero() {
+        return Ok(shares);
+    }
+
+    Ok(mul_div(shares, total_assets, total_shares, rounding))
+}
+
+/// nAsset amount which leaves at least 'assets' after withdrawal fee of 'fee_nom / fee_denom'
+pub fn assets_before_fee(
+    assets: Uint256,
+    fee_nom: Uint256,
+    fee_denom: Uint256,
+) -> Result<Uint256, ShareMathError> {
+    if fee_nom.is_zero() {
+        return Ok(assets);
+    }
+
+    if fee_nom >= fee_denom {
+        return Err(ShareMathError::WholeAmountIsFee);
+    }
+
+    Ok(mul_div(
+        assets,
+        fee_denom,
+        fee_denom - fee_nom,
+        Rounding::Up,
+    ))
+}
+
+/// nAsset per one anAsset, 1 for empty vault
+pub fn exchange_rate(total_assets: Uint128, total_shares: Uint128) -> Decimal {
+    if total_shares.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(total_assets, total_shares)
+    }
+}
+
+/// value * nom / denom with given rounding
+pub fn mul_div(value: Uint256, nom: Uint256, denom: Uint256, rounding: Rounding) -> Uint256 {
+    let result = value.multiply_ratio(nom, denom);
+    if rounding == Rounding::Up && result * denom != value * nom {
+        result + Uint256::one()
+    } else {
+        result
+    }
+}
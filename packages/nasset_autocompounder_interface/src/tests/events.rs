@@ -0,0 +1,77 @@
+use crate::events::{AutocompounderEvent, DepositEvent, CONTRACT_ADDRESS_ATTRIBUTE, DEPOSIT_EVENT};
+
+use cosmwasm_std::{Addr, Decimal, Event, StdError, Uint128};
+
+const NASSET_TOKEN_ADDR: &str = "addr0001";
+const AUTOCOMPOUNDER_ADDR: &str = "addr0010";
+
+#[test]
+fn parse_events() {
+    let deposit_event = DepositEvent {
+        nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+        sender: Addr::unchecked("addr9999"),
+        recipient: Addr::unchecked("addr9999"),
+        nasset_amount: Uint128::from(50u128),
+        shares_minted: Uint128::from(25u128),
+        exchange_rate: Decimal::from_ratio(2u128, 1u128),
+    };
+    let mut wasm_event: Event = deposit_event.clone().into();
+    wasm_event.ty = format!("wasm-{}", DEPOSIT_EVENT);
+
+    let events = vec![
+        Event::new("transfer").add_attribute("amount", "50"),
+        wasm_event,
+    ];
+
+    assert_eq!(
+        AutocompounderEvent::parse_all(&events).unwrap(),
+        vec![AutocompounderEvent::Deposit(deposit_event)]
+    );
+}
+
+#[test]
+fn parse_event_without_attribute() {
+    let event = Event::new(DEPOSIT_EVENT)
+        .add_attribute("nasset_token", NASSET_TOKEN_ADDR)
+        .add_attribute("sender", "addr9999");
+
+    assert_eq!(
+        AutocompounderEvent::parse(&event).unwrap_err(),
+        StdError::generic_err("'nexus_autocompounder_deposit' event has no 'recipient' attribute")
+    );
+}
+
+#[test]
+fn parse_events_of_trusted_contracts() {
+    let deposit_event = DepositEvent {
+        nasset_token: Addr::unchecked(NASSET_TOKEN_ADDR),
+        sender: Addr::unchecked("addr9999"),
+        recipient: Addr::unchecked("addr9999"),
+        nasset_amount: Uint128::from(50u128),
+        shares_minted: Uint128::from(25u128),
+        exchange_rate: Decimal::from_ratio(2u128, 1u128),
+    };
+    let emitted_by = |contract: &str| {
+        let mut event = Event::new(format!("wasm-{}", DEPOSIT_EVENT))
+            .add_attribute(CONTRACT_ADDRESS_ATTRIBUTE, contract);
+        event
+            .attributes
+            .extend(Event::from(deposit_event.clone()).attributes);
+        event
+    };
+
+    let events = vec![
+        emitted_by("addr6666"),
+        emitted_by(AUTOCOMPOUNDER_ADDR),
+        // not emitted by contract at all
+        deposit_event.clone().into(),
+        // malformed event of unknown contract is not an error
+        Event::new(DEPOSIT_EVENT).add_attribute(CONTRACT_ADDRESS_ATTRIBUTE, "addr6666"),
+    ];
+
+    assert_eq!(
+        AutocompounderEvent::parse_all_from(&events, &[Addr::unchecked(AUTOCOMPOUNDER_ADDR)])
+            .unwrap(),
+        vec![AutocompounderEvent::Deposit(deposit_event)]
+    );
+}
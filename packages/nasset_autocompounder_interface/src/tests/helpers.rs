@@ -0,0 +1,78 @@
+use crate::helpers::{compound_msg, deposit_msg, withdraw_msg};
+use crate::msg::{Cw20HookMsg, ExecuteMsg};
+use cosmwasm_std::{to_binary, CosmosMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+const AUTOCOMPOUNDER_ADDR: &str = "addr0001";
+const NASSET_TOKEN_ADDR: &str = "addr0002";
+const AUTO_NASSET_TOKEN_ADDR: &str = "addr0003";
+
+#[test]
+fn deposit_sends_nasset_to_autocompounder() {
+    let msg = deposit_msg(
+        AUTOCOMPOUNDER_ADDR,
+        NASSET_TOKEN_ADDR,
+        Uint128::from(100u64),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: NASSET_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: AUTOCOMPOUNDER_ADDR.to_string(),
+                amount: Uint128::from(100u64),
+                msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn withdraw_sends_auto_nasset_to_autocompounder() {
+    let msg = withdraw_msg(
+        AUTOCOMPOUNDER_ADDR,
+        AUTO_NASSET_TOKEN_ADDR,
+        Uint128::from(100u64),
+        Some("addr0004".to_string()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: AUTO_NASSET_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: AUTOCOMPOUNDER_ADDR.to_string(),
+                amount: Uint128::from(100u64),
+                msg: to_binary(&Cw20HookMsg::Withdraw {
+                    recipient: Some("addr0004".to_string())
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn compound_executes_autocompounder() {
+    let msg = compound_msg(AUTOCOMPOUNDER_ADDR, None).unwrap();
+
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: AUTOCOMPOUNDER_ADDR.to_string(),
+            msg: to_binary(&ExecuteMsg::Compound {
+                nasset_token_addr: None
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
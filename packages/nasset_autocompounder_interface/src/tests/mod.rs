@@ -0,0 +1,5 @@
+mod events;
+mod helpers;
+mod querier;
+mod reply_response;
+mod share_math;
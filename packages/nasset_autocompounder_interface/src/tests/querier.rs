@@ -0,0 +1,102 @@
+use crate::msg::{QueryMsg, VaultAssetsResponse, VaultSharesResponse};
+use crate::querier::AutocompounderQuerier;
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, ContractResult, Empty, Querier, QuerierResult,
+    QuerierWrapper, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+
+const AUTOCOMPOUNDER_ADDR: &str = "addr0001";
+const VAULT_NASSET_TOKEN_ADDR: &str = "addr0011";
+
+// autocompounder with 2 nAsset per anAsset in primary vault and 4 in additional one
+struct MockQuerier;
+
+fn nasset_per_share(nasset_token_addr: Option<String>) -> u128 {
+    match nasset_token_addr.as_deref() {
+        None => 2,
+        Some(VAULT_NASSET_TOKEN_ADDR) => 4,
+        Some(addr) => panic!("unexpected vault: {}", addr),
+    }
+}
+
+impl Querier for MockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = from_slice(bin_request).unwrap();
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == AUTOCOMPOUNDER_ADDR =>
+            {
+                let response = match from_binary(&msg).unwrap() {
+                    QueryMsg::ConvertToShares {
+                        assets,
+                        nasset_token_addr,
+                    } => to_binary(&VaultSharesResponse {
+                        shares: assets.multiply_ratio(1u128, nasset_per_share(nasset_token_addr)),
+                    }),
+                    QueryMsg::ConvertToAssets {
+                        shares,
+                        nasset_token_addr,
+                    } => to_binary(&VaultAssetsResponse {
+                        assets: shares.multiply_ratio(nasset_per_share(nasset_token_addr), 1u128),
+                    }),
+                    msg => panic!("unexpected query: {:?}", msg),
+                };
+                SystemResult::Ok(ContractResult::from(response))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "unknown contract".to_string(),
+            }),
+        }
+    }
+}
+
+#[test]
+fn typed_queries_to_autocompounder() {
+    let querier = QuerierWrapper::new(&MockQuerier);
+    let autocompounder = AutocompounderQuerier::new(&querier, AUTOCOMPOUNDER_ADDR);
+
+    assert_eq!(
+        autocompounder
+            .convert_to_shares(Uint128::from(100u64))
+            .unwrap(),
+        VaultSharesResponse {
+            shares: Uint128::from(50u64)
+        }
+    );
+    assert_eq!(
+        autocompounder
+            .convert_to_assets(Uint128::from(100u64))
+            .unwrap(),
+        VaultAssetsResponse {
+            assets: Uint128::from(200u64)
+        }
+    );
+}
+
+#[test]
+fn typed_queries_to_additional_vault() {
+    let querier = QuerierWrapper::new(&MockQuerier);
+    let vault = AutocompounderQuerier::new(&querier, AUTOCOMPOUNDER_ADDR)
+        .for_vault(VAULT_NASSET_TOKEN_ADDR);
+
+    assert_eq!(
+        vault.convert_to_shares(Uint128::from(100u64)).unwrap(),
+        VaultSharesResponse {
+            shares: Uint128::from(25u64)
+        }
+    );
+    assert_eq!(
+        vault.convert_to_assets(Uint128::from(100u64)).unwrap(),
+        VaultAssetsResponse {
+            assets: Uint128::from(400u64)
+        }
+    );
+}
+
+#[test]
+fn query_to_other_contract_fails() {
+    let querier = QuerierWrapper::new(&MockQuerier);
+    let autocompounder = AutocompounderQuerier::new(&querier, "addr0002");
+
+    assert!(autocompounder.total_assets().is_err());
+}
@@ -0,0 +1,113 @@
+use crate::reply_response::MsgInstantiateContractResponse;
+
+use cosmwasm_std::{Binary, ContractResult, Reply, StdError, SubMsgExecutionResponse};
+
+const CONTRACT_ADDR: &str = "terra1w0lfzmr3lz0t2hlgzwqj0kfsy4ldnmq8ty6rux";
+
+fn reply(result: ContractResult<SubMsgExecutionResponse>) -> Reply {
+    Reply { id: 0, result }
+}
+
+fn reply_with_data(data: Option<Binary>) -> Reply {
+    reply(ContractResult::Ok(SubMsgExecutionResponse {
+        events: vec![],
+        data,
+    }))
+}
+
+#[test]
+fn encoded_response_is_parsed() {
+    let response = MsgInstantiateContractResponse::new(CONTRACT_ADDR);
+    let data = response.to_binary();
+    // key of field 1 with length-delimited wire type, then length
+    assert_eq!(&[0x0a, CONTRACT_ADDR.len() as u8], &data.as_slice()[..2]);
+
+    assert_eq!(
+        response,
+        MsgInstantiateContractResponse::parse(data.as_slice()).unwrap()
+    );
+}
+
+#[test]
+fn unknown_fields_are_skipped() {
+    let mut data = vec![];
+    // data = 2, bytes
+    data.extend_from_slice(&[0x12, 0x03, 0x01, 0x02, 0x03]);
+    // field 3, varint 300
+    data.extend_from_slice(&[0x18, 0xac, 0x02]);
+    // field 4, fixed64
+    data.extend_from_slice(&[0x21, 0, 0, 0, 0, 0, 0, 0, 0]);
+    // field 5, fixed32
+    data.extend_from_slice(&[0x2d, 0, 0, 0, 0]);
+    data.extend_from_slice(
+        MsgInstantiateContractResponse::new(CONTRACT_ADDR)
+            .to_binary()
+            .as_slice(),
+    );
+
+    let response = MsgInstantiateContractResponse::parse(&data).unwrap();
+    assert_eq!(CONTRACT_ADDR, response.contract_address);
+}
+
+#[test]
+fn long_address_length_is_multibyte_varint() {
+    let address = "a".repeat(300);
+    let data = MsgInstantiateContractResponse::new(address.clone()).to_binary();
+    assert_eq!(&[0x0a, 0xac, 0x02], &data.as_slice()[..3]);
+
+    let response = MsgInstantiateContractResponse::parse(data.as_slice()).unwrap();
+    assert_eq!(address, response.contract_address);
+}
+
+#[test]
+fn malformed_data_is_rejected() {
+    let cases: &[(&[u8], &str)] = &[
+        (&[], "contract_address is missing"),
+        (&[0x0a, 0x00], "contract_address is missing"),
+        (&[0x0a, 0x05, 0x61], "unexpected end of data"),
+        (&[0x0a], "unexpected end of varint"),
+        (&[0x0a, 0x80], "unexpected end of varint"),
+        (
+            &[0x0a, 0x02, 0xff, 0xfe],
+            "contract_address is not valid UTF-8",
+        ),
+        (&[0x08, 0x01], "contract_address has wrong wire type"),
+        (&[0x13], "unsupported wire type 3"),
+        (
+            &[
+                0x0a, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+            "varint is too long",
+        ),
+    ];
+
+    for (data, error) in cases {
+        assert_eq!(
+            StdError::parse_err("MsgInstantiateContractResponse", error),
+            MsgInstantiateContractResponse::parse(data).unwrap_err(),
+            "data: {:?}",
+            data
+        );
+    }
+}
+
+#[test]
+fn reply_without_data_is_rejected() {
+    let error = MsgInstantiateContractResponse::from_reply(reply_with_data(None)).unwrap_err();
+    assert_eq!(
+        StdError::parse_err("MsgInstantiateContractResponse", "reply has no data"),
+        error
+    );
+}
+
+#[test]
+fn failed_reply_is_rejected() {
+    let error = MsgInstantiateContractResponse::from_reply(reply(ContractResult::Err(
+        "out of gas".to_string(),
+    )))
+    .unwrap_err();
+    assert_eq!(
+        StdError::generic_err("instantiate failed: out of gas"),
+        error
+    );
+}
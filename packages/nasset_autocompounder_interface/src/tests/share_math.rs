@@ -0,0 +1,216 @@
+use crate::share_math::{
+    assets_before_fee, assets_to_withdraw, convert_to_assets, convert_to_shares, shares_to_mint,
+    Rounding, ShareMathError,
+};
+
+use cosmwasm_bignumber::Uint256;
+use proptest::prelude::*;
+
+const USERS_COUNT: usize = 4;
+
+#[derive(Clone, Debug)]
+enum Action {
+    Deposit { user: usize, amount: u128 },
+    // withdraw given percent of user's anAsset
+    Withdraw { user: usize, percent: u128 },
+}
+
+fn action_strategy() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (0..USERS_COUNT, 1..1_000_000_000_000u128)
+            .prop_map(|(user, amount)| Action::Deposit { user, amount }),
+        (0..USERS_COUNT, 1..=100u128)
+            .prop_map(|(user, percent)| Action::Withdraw { user, percent }),
+    ]
+}
+
+#[test]
+fn zero_supply_and_balance_return_errors() {
+    assert_eq!(
+        Err(ShareMathError::ZeroSupply),
+        assets_to_withdraw(Uint256::from(100u128), Uint256::zero(), Uint256::one())
+    );
+    assert_eq!(
+        Err(ShareMathError::SharesExceedSupply),
+        assets_to_withdraw(
+            Uint256::from(100u128),
+            Uint256::from(10u128),
+            Uint256::from(11u128)
+        )
+    );
+    assert_eq!(
+        Err(ShareMathError::ZeroAssets),
+        shares_to_mint(Uint256::zero(), Uint256::from(10u128), Uint256::one())
+    );
+    assert_eq!(
+        Err(ShareMathError::ZeroSharesMinted),
+        shares_to_mint(
+            Uint256::from(100u128),
+            Uint256::from(10u128),
+            Uint256::from(9u128)
+        )
+    );
+    assert_eq!(
+        Ok(Uint256::from(5u128)),
+        shares_to_mint(
+            Uint256::from(100u128),
+            Uint256::zero(),
+            Uint256::from(5u128)
+        )
+    );
+}
+
+#[test]
+fn rounding_directions() {
+    let total_assets = Uint256::from(1_500u128);
+    let total_shares = Uint256::from(1_000u128);
+    let amount = Uint256::from(100u128);
+
+    assert_eq!(
+        Ok(Uint256::from(66u128)),
+        convert_to_shares(total_assets, total_shares, amount, Rounding::Down)
+    );
+    assert_eq!(
+        Ok(Uint256::from(67u128)),
+        convert_to_shares(total_assets, total_shares, amount, Rounding::Up)
+    );
+    assert_eq!(
+        Ok(Uint256::from(4u128)),
+        convert_to_assets(
+            total_assets,
+            total_shares,
+            Uint256::from(3u128),
+            Rounding::Down
+        )
+    );
+    assert_eq!(
+        Ok(Uint256::from(5u128)),
+        convert_to_assets(
+            total_assets,
+            total_shares,
+            Uint256::from(3u128),
+            Rounding::Up
+        )
+    );
+}
+
+proptest! {
+    #[test]
+    fn assets_before_fee_cover_fee(
+        assets in 0..1_000_000_000_000_000u128,
+        fee_nom in 0..1_000_000u128,
+        fee_denom in 1..1_000_000u128,
+    ) {
+        let assets = Uint256::from(assets);
+        let fee_nom = Uint256::from(fee_nom);
+        let fee_denom = Uint256::from(fee_denom);
+
+        match assets_before_fee(assets, fee_nom, fee_denom) {
+            Ok(assets_before_fee) => {
+                // withdrawal fee is rounded down
+                let fee = assets_before_fee.multiply_ratio(fee_nom, fee_denom);
+                prop_assert!(assets_before_fee - fee >= assets);
+            }
+            Err(err) => {
+                prop_assert_eq!(ShareMathError::WholeAmountIsFee, err);
+                prop_assert!(fee_nom >= fee_denom);
+            }
+        }
+    }
+
+    #[test]
+    fn deposit_then_withdraw_never_returns_more(
+        total_assets in 1..1_000_000_000_000_000u128,
+        total_shares in 0..1_000_000_000_000_000u128,
+        deposit in 1..1_000_000_000_000u128,
+    ) {
+        let total_assets = Uint256::from(total_assets);
+        let total_shares = Uint256::from(total_shares);
+        let deposit = Uint256::from(deposit);
+
+        if let Ok(minted) = shares_to_mint(total_assets, total_shares, deposit) {
+            let withdrawn =
+                assets_to_withdraw(total_assets + deposit, total_shares + minted, minted).unwrap();
+            prop_assert!(withdrawn <= deposit);
+        }
+    }
+
+    #[test]
+    fn conversion_round_trip_never_returns_more(
+        total_assets in 1..1_000_000_000_000_000u128,
+        total_shares in 1..1_000_000_000_000_000u128,
+        assets in 0..1_000_000_000_000u128,
+    ) {
+        let total_assets = Uint256::from(total_assets);
+        let total_shares = Uint256::from(total_shares);
+        let assets = Uint256::from(assets);
+
+        let shares = convert_to_shares(total_assets, total_shares, assets, Rounding::Down).unwrap();
+        let assets_back =
+            convert_to_assets(total_assets, total_shares, shares, Rounding::Down).unwrap();
+        prop_assert!(assets_back <= assets);
+
+        // shares needed to get 'assets' are always enough to get them back
+        let shares = convert_to_shares(total_assets, total_shares, assets, Rounding::Up).unwrap();
+        let assets_back =
+            convert_to_assets(total_assets, total_shares, shares, Rounding::Down).unwrap();
+        prop_assert!(assets_back >= assets);
+    }
+
+    #[test]
+    fn nobody_extracts_more_than_deposited(
+        actions in proptest::collection::vec(action_strategy(), 1..64),
+    ) {
+        let mut total_assets = Uint256::zero();
+        let mut total_shares = Uint256::zero();
+        let mut shares = [Uint256::zero(); USERS_COUNT];
+        let mut deposited = [Uint256::zero(); USERS_COUNT];
+        let mut withdrawn = [Uint256::zero(); USERS_COUNT];
+
+        for action in actions {
+            let (assets_before, shares_before) = (total_assets, total_shares);
+
+            match action {
+                Action::Deposit { user, amount } => {
+                    let amount = Uint256::from(amount);
+                    if let Ok(minted) = shares_to_mint(total_assets, total_shares, amount) {
+                        total_assets += amount;
+                        total_shares += minted;
+                        shares[user] += minted;
+                        deposited[user] += amount;
+                    }
+                }
+                Action::Withdraw { user, percent } => {
+                    let to_burn = shares[user].multiply_ratio(Uint256::from(percent), Uint256::from(100u128));
+                    if to_burn.is_zero() {
+                        continue;
+                    }
+
+                    let assets = assets_to_withdraw(total_assets, total_shares, to_burn).unwrap();
+                    prop_assert!(assets <= total_assets);
+                    total_assets = total_assets - assets;
+                    total_shares = total_shares - to_burn;
+                    shares[user] = shares[user] - to_burn;
+                    withdrawn[user] += assets;
+                }
+            }
+
+            // anAsset price never goes down: assets / shares >= assets_before / shares_before
+            if !shares_before.is_zero() && !total_shares.is_zero() {
+                prop_assert!(total_assets * shares_before >= assets_before * total_shares);
+            }
+        }
+
+        // everybody exits
+        for user in 0..USERS_COUNT {
+            if !shares[user].is_zero() {
+                let assets = assets_to_withdraw(total_assets, total_shares, shares[user]).unwrap();
+                total_assets = total_assets - assets;
+                total_shares = total_shares - shares[user];
+                withdrawn[user] += assets;
+            }
+            prop_assert!(withdrawn[user] <= deposited[user]);
+        }
+        prop_assert!(total_shares.is_zero());
+    }
+}
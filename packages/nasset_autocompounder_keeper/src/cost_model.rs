@@ -0,0 +1,28 @@
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use serde::{Deserialize, Serialize};
+
+/// Fee of 'Compound' transaction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GasCostModel {
+    //gas of 'Compound' with PsiClaimed and PsiSold replies,
+    //'gas' bench of autocompounder reports it
+    pub compound_gas: u64,
+    //gas limit is 'compound_gas' multiplied by it
+    pub gas_adjustment: Decimal,
+    //'fee_denom' amount per gas unit
+    pub gas_price: Decimal,
+    pub fee_denom: String,
+}
+
+impl GasCostModel {
+    pub fn gas_limit(&self) -> u64 {
+        (Uint128::from(self.compound_gas) * self.gas_adjustment).u128() as u64
+    }
+
+    pub fn fee(&self) -> Coin {
+        Coin {
+            denom: self.fee_denom.clone(),
+            amount: Uint128::from(self.gas_limit()) * self.gas_price,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use cosmwasm_std::{Decimal, QuerierWrapper, StdResult, Uint128};
+use nasset_autocompounder_interface::msg::ExecuteMsg;
+use nasset_autocompounder_interface::querier::AutocompounderQuerier;
+use serde::{Deserialize, Serialize};
+
+use crate::cost_model::GasCostModel;
+use crate::tx::CompoundTx;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct KeeperConfig {
+    pub autocompounder_addr: String,
+    //account which signs 'Compound' transaction
+    pub keeper_addr: String,
+    pub cost_model: GasCostModel,
+    //compound only when nAsset bought is worth at least fee multiplied by it
+    pub min_profit_ratio: Decimal,
+    //seconds since last compound before next one is considered
+    pub min_compound_interval: u64,
+    //compound even if it is not profitable after this many seconds since last compound
+    //or if vault was never compounded, never by default
+    pub max_compound_interval: Option<u64>,
+}
+
+/// Primary vault state which decision is based on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VaultSnapshot {
+    //accrued and already claimed PSI
+    pub pending_psi: Uint128,
+    pub psi_to_sell: Uint128,
+    pub nasset_out: Uint128,
+    //zero if vault was never compounded
+    pub last_compound_time: u64,
+}
+
+impl VaultSnapshot {
+    //nAsset for one PSI, including pair spread and commission
+    pub fn psi_price(&self) -> Decimal {
+        if self.psi_to_sell.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(self.nasset_out, self.psi_to_sell)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Compound(Box<CompoundTx>),
+    Skip(SkipReason),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    NothingToCompound,
+    TooSoon {
+        next_compound_time: u64,
+    },
+    //both values are in fee denom
+    Unprofitable {
+        rewards_value: Uint128,
+        min_rewards_value: Uint128,
+    },
+}
+
+pub struct Keeper {
+    config: KeeperConfig,
+}
+
+impl Keeper {
+    pub fn new(config: KeeperConfig) -> Self {
+        Keeper { config }
+    }
+
+    pub fn config(&self) -> &KeeperConfig {
+        &self.config
+    }
+
+    pub fn load_snapshot(&self, querier: &QuerierWrapper) -> StdResult<VaultSnapshot> {
+        let autocompounder =
+            AutocompounderQuerier::new(querier, self.config.autocompounder_addr.clone());
+        let pending_rewards = autocompounder.pending_rewards()?;
+        let simulation = autocompounder.simulate_compound()?;
+        let stats = autocompounder.stats()?;
+
+        Ok(VaultSnapshot {
+            pending_psi: pending_rewards.total_psi,
+            psi_to_sell: simulation.psi_to_sell,
+            nasset_out: simulation.nasset_out,
+            last_compound_time: stats.last_compound_time,
+        })
+    }
+
+    /// Queries autocompounder and decides if it should be compounded at 'block_time'.
+    /// 'nasset_price' is fee denom amount for one nAsset
+    pub fn check(
+        &self,
+        querier: &QuerierWrapper,
+        block_time: u64,
+        nasset_price: Decimal,
+    ) -> StdResult<Decision> {
+        let snapshot = self.load_snapshot(querier)?;
+        Ok(self.decide(&snapshot, block_time, nasset_price))
+    }
+
+    pub fn decide(
+        &self,
+        snapshot: &VaultSnapshot,
+        block_time: u64,
+        nasset_price: Decimal,
+    ) -> Decision {
+        if snapshot.psi_to_sell.is_zero() || snapshot.nasset_out.is_zero() {
+            return Decision::Skip(SkipReason::NothingToCompound);
+        }
+
+        let seconds_since_compound = block_time.saturating_sub(snapshot.last_compound_time);
+        if snapshot.last_compound_time != 0
+            && seconds_since_compound < self.config.min_compound_interval
+        {
+            return Decision::Skip(SkipReason::TooSoon {
+                next_compound_time: snapshot.last_compound_time + self.config.min_compound_interval,
+            });
+        }
+
+        let is_overdue = match self.config.max_compound_interval {
+            Some(max_compound_interval) => {
+                snapshot.last_compound_time == 0 || seconds_since_compound >= max_compound_interval
+            }
+            None => false,
+        };
+
+        let rewards_value = snapshot.nasset_out * nasset_price;
+        let min_rewards_value = self.config.cost_model.fee().amount * self.config.min_profit_ratio;
+        if !is_overdue && rewards_value < min_rewards_value {
+            return Decision::Skip(SkipReason::Unprofitable {
+                rewards_value,
+                min_rewards_value,
+            });
+        }
+
+        Decision::Compound(Box::new(self.compound_tx(snapshot)))
+    }
+
+    fn compound_tx(&self, snapshot: &VaultSnapshot) -> CompoundTx {
+        CompoundTx {
+            sender: self.config.keeper_addr.clone(),
+            contract: self.config.autocompounder_addr.clone(),
+            msg: ExecuteMsg::Compound {
+                nasset_token_addr: None,
+            },
+            gas_limit: self.config.cost_model.gas_limit(),
+            fee: self.config.cost_model.fee(),
+            psi_to_sell: snapshot.psi_to_sell,
+            nasset_out: snapshot.nasset_out,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Off-chain keeper for nasset autocompounder.
+//! Reads pending PSI, PSI -> nAsset swap result and last compound time with contract queries,
+//! and builds 'Compound' transaction only when rewards are worth more than its fee.
+mod cost_model;
+mod keeper;
+mod tx;
+
+pub use cost_model::GasCostModel;
+pub use keeper::{Decision, Keeper, KeeperConfig, SkipReason, VaultSnapshot};
+pub use tx::CompoundTx;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,243 @@
+use super::{keeper_config, mock_pair, mock_rewards, KEEPER_ADDR, MIN_COMPOUND_INTERVAL};
+use crate::{Decision, Keeper, SkipReason};
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, MinterResponse};
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+use nasset_autocompounder::msg::{Cw20HookMsg, InstantiateMsg};
+use nasset_autocompounder_interface::querier::AutocompounderQuerier;
+
+const ADMIN: &str = "admin";
+const USER: &str = "user";
+const DEPOSIT_AMOUNT: u128 = 1_000_000_000;
+const RESERVE: u128 = 1_000_000_000_000_000;
+
+// one nAsset is 2 uusd
+fn nasset_price() -> Decimal {
+    Decimal::from_ratio(2u128, 1u128)
+}
+
+fn autocompounder_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            nasset_autocompounder::contract::execute,
+            nasset_autocompounder::contract::instantiate,
+            nasset_autocompounder::contract::query,
+        )
+        .with_reply(nasset_autocompounder::contract::reply),
+    )
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
+//real autocompounder with cw20-base nAsset and PSI tokens,
+//1_000 PSI per second from rewards contract, PSI price is 0.1 nAsset:
+//rewards are worth 200 uusd per second, compound fee with 2x margin is 3_000_000 uusd
+fn init() -> (App, Keeper) {
+    let mut app = AppBuilder::new().build();
+    let admin = Addr::unchecked(ADMIN);
+    let cw20_code_id = app.store_code(cw20_contract());
+    let rewards_code_id = app.store_code(mock_rewards::contract());
+    let pair_code_id = app.store_code(mock_pair::contract());
+    let autocompounder_code_id = app.store_code(autocompounder_contract());
+
+    let nasset_token = instantiate_token(&mut app, cw20_code_id, "nLuna", USER);
+    let psi_token = instantiate_token(&mut app, cw20_code_id, "PSI", ADMIN);
+
+    let nasset_token_rewards = app
+        .instantiate_contract(
+            rewards_code_id,
+            admin.clone(),
+            &mock_rewards::InstantiateMsg {
+                psi_token: psi_token.to_string(),
+                psi_per_second: Uint128::from(1_000u128),
+            },
+            &[],
+            "nasset rewards",
+            None,
+        )
+        .unwrap();
+    let psi_to_nasset_pair = app
+        .instantiate_contract(
+            pair_code_id,
+            admin.clone(),
+            &mock_pair::InstantiateMsg {
+                psi_token: psi_token.to_string(),
+                nasset_token: nasset_token.to_string(),
+                psi_price: Decimal::from_ratio(1u128, 10u128),
+            },
+            &[],
+            "psi to nasset pair",
+            None,
+        )
+        .unwrap();
+    mint(&mut app, &psi_token, &nasset_token_rewards);
+    mint(&mut app, &nasset_token, &psi_to_nasset_pair);
+
+    let autocompounder = app
+        .instantiate_contract(
+            autocompounder_code_id,
+            admin,
+            &InstantiateMsg {
+                nasset_token_addr: nasset_token.to_string(),
+                psi_token_addr: psi_token.to_string(),
+                psi_to_nasset_pair_addr: psi_to_nasset_pair.to_string(),
+                governance_contract_addr: "governance".to_string(),
+                cw20_token_code_id: cw20_code_id,
+                nasset_token_rewards_addr: nasset_token_rewards.to_string(),
+                collateral_token_symbol: "Luna".to_string(),
+                config_update_delay: 0,
+                max_total_nasset: Uint128::MAX,
+                max_nasset_per_user: None,
+                withdrawal_fee: None,
+                withdrawal_mode: None,
+                share_token_mode: None,
+            },
+            &[],
+            "nasset autocompounder",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(USER),
+        nasset_token,
+        &Cw20ExecuteMsg::Send {
+            contract: autocompounder.to_string(),
+            amount: Uint128::from(DEPOSIT_AMOUNT),
+            msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let keeper = Keeper::new(keeper_config(autocompounder.as_str()));
+    (app, keeper)
+}
+
+//'holder' gets initial deposit
+fn instantiate_token(app: &mut App, code_id: u64, symbol: &str, holder: &str) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(ADMIN),
+        &cw20_base::msg::InstantiateMsg {
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_string(),
+                amount: Uint128::from(DEPOSIT_AMOUNT),
+            }],
+            mint: Some(MinterResponse {
+                minter: ADMIN.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+    )
+    .unwrap()
+}
+
+fn mint(app: &mut App, token: &Addr, recipient: &Addr) {
+    app.execute_contract(
+        Addr::unchecked(ADMIN),
+        token.clone(),
+        &Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(RESERVE),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn increase_block_time(app: &mut App, seconds: u64) {
+    app.update_block(|block| block.time = block.time.plus_seconds(seconds));
+}
+
+fn check(app: &App, keeper: &Keeper) -> Decision {
+    keeper
+        .check(&app.wrap(), app.block_info().time.seconds(), nasset_price())
+        .unwrap()
+}
+
+fn execute(app: &mut App, decision: Decision) {
+    let tx = match decision {
+        Decision::Compound(tx) => tx,
+        decision => panic!("unexpected decision: {:?}", decision),
+    };
+    app.execute(Addr::unchecked(KEEPER_ADDR), tx.cosmos_msg().unwrap())
+        .unwrap();
+}
+
+#[test]
+fn keeper_waits_until_compound_is_profitable() {
+    let (mut app, keeper) = init();
+    //never compounded vault is overdue otherwise
+    let mut keeper_config = keeper.config().clone();
+    keeper_config.max_compound_interval = None;
+    let keeper = Keeper::new(keeper_config);
+
+    //200 * 14_000 = 2_800_000 uusd
+    increase_block_time(&mut app, 14_000);
+    assert!(matches!(
+        check(&app, &keeper),
+        Decision::Skip(SkipReason::Unprofitable { .. })
+    ));
+
+    //200 * 15_000 = 3_000_000 uusd
+    increase_block_time(&mut app, 1_000);
+    let decision = check(&app, &keeper);
+    execute(&mut app, decision);
+
+    //15_000_000 PSI sold for 1_500_000 nAsset
+    let querier = app.wrap();
+    let autocompounder =
+        AutocompounderQuerier::new(&querier, keeper.config().autocompounder_addr.clone());
+    let stats = autocompounder.stats().unwrap();
+    assert_eq!(stats.compounds_count, 1);
+    assert_eq!(stats.psi_swapped, Uint128::from(15_000_000u128));
+    assert_eq!(stats.nasset_acquired, Uint128::from(1_500_000u128));
+
+    let snapshot = keeper.load_snapshot(&app.wrap()).unwrap();
+    assert_eq!(snapshot.psi_to_sell, Uint128::zero());
+    assert_eq!(snapshot.last_compound_time, app.block_info().time.seconds());
+    assert_eq!(
+        check(&app, &keeper),
+        Decision::Skip(SkipReason::NothingToCompound)
+    );
+}
+
+#[test]
+fn keeper_respects_min_interval_between_compounds() {
+    let (mut app, keeper) = init();
+
+    increase_block_time(&mut app, 86_400);
+    let decision = check(&app, &keeper);
+    execute(&mut app, decision);
+    let compound_time = app.block_info().time.seconds();
+
+    //rewards are profitable again, but interval did not pass
+    increase_block_time(&mut app, MIN_COMPOUND_INTERVAL - 1);
+    let mut keeper_config = keeper.config().clone();
+    keeper_config.min_profit_ratio = Decimal::zero();
+    let keeper = Keeper::new(keeper_config);
+    assert_eq!(
+        check(&app, &keeper),
+        Decision::Skip(SkipReason::TooSoon {
+            next_compound_time: compound_time + MIN_COMPOUND_INTERVAL,
+        })
+    );
+
+    increase_block_time(&mut app, 1);
+    let decision = check(&app, &keeper);
+    execute(&mut app, decision);
+}
@@ -0,0 +1,167 @@
+use super::{keeper_config, AUTOCOMPOUNDER_ADDR, KEEPER_ADDR, MAX_COMPOUND_INTERVAL};
+use crate::{CompoundTx, Decision, Keeper, SkipReason, VaultSnapshot};
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use nasset_autocompounder_interface::msg::ExecuteMsg;
+
+const LAST_COMPOUND_TIME: u64 = 1_600_000_000;
+
+fn snapshot(nasset_out: u128) -> VaultSnapshot {
+    VaultSnapshot {
+        pending_psi: Uint128::from(nasset_out * 10),
+        psi_to_sell: Uint128::from(nasset_out * 10),
+        nasset_out: Uint128::from(nasset_out),
+        last_compound_time: LAST_COMPOUND_TIME,
+    }
+}
+
+// one nAsset is 2 uusd
+fn nasset_price() -> Decimal {
+    Decimal::from_ratio(2u128, 1u128)
+}
+
+#[test]
+fn compound_when_rewards_cover_fee() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+
+    //3_000_000 uusd worth of nAsset, fee 1_500_000 uusd multiplied by 2
+    let decision = keeper.decide(
+        &snapshot(1_500_000),
+        LAST_COMPOUND_TIME + 86_400,
+        nasset_price(),
+    );
+
+    assert_eq!(
+        decision,
+        Decision::Compound(Box::new(CompoundTx {
+            sender: KEEPER_ADDR.to_string(),
+            contract: AUTOCOMPOUNDER_ADDR.to_string(),
+            msg: ExecuteMsg::Compound {
+                nasset_token_addr: None
+            },
+            gas_limit: 1_500_000,
+            fee: Coin::new(1_500_000, "uusd"),
+            psi_to_sell: Uint128::from(15_000_000u128),
+            nasset_out: Uint128::from(1_500_000u128),
+        }))
+    );
+}
+
+#[test]
+fn skip_unprofitable_compound() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+
+    let decision = keeper.decide(
+        &snapshot(1_499_999),
+        LAST_COMPOUND_TIME + 86_400,
+        nasset_price(),
+    );
+
+    assert_eq!(
+        decision,
+        Decision::Skip(SkipReason::Unprofitable {
+            rewards_value: Uint128::from(2_999_998u128),
+            min_rewards_value: Uint128::from(3_000_000u128),
+        })
+    );
+}
+
+#[test]
+fn skip_compound_before_min_interval() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+
+    let decision = keeper.decide(
+        &snapshot(1_000_000_000),
+        LAST_COMPOUND_TIME + 60,
+        nasset_price(),
+    );
+
+    assert_eq!(
+        decision,
+        Decision::Skip(SkipReason::TooSoon {
+            next_compound_time: LAST_COMPOUND_TIME + 3_600,
+        })
+    );
+}
+
+#[test]
+fn skip_when_nothing_to_sell() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+
+    let decision = keeper.decide(&snapshot(0), LAST_COMPOUND_TIME + 86_400, nasset_price());
+
+    assert_eq!(decision, Decision::Skip(SkipReason::NothingToCompound));
+}
+
+#[test]
+fn compound_unprofitable_after_max_interval() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+
+    let decision = keeper.decide(
+        &snapshot(1),
+        LAST_COMPOUND_TIME + MAX_COMPOUND_INTERVAL,
+        nasset_price(),
+    );
+
+    assert!(matches!(decision, Decision::Compound(_)));
+}
+
+#[test]
+fn never_compounded_vault_is_not_too_soon() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+    let snapshot = VaultSnapshot {
+        last_compound_time: 0,
+        ..snapshot(1_500_000)
+    };
+
+    let decision = keeper.decide(&snapshot, 60, nasset_price());
+
+    assert!(matches!(decision, Decision::Compound(_)));
+}
+
+#[test]
+fn never_compounded_vault_is_overdue() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+    let snapshot = VaultSnapshot {
+        last_compound_time: 0,
+        ..snapshot(1)
+    };
+
+    let decision = keeper.decide(&snapshot, 60, nasset_price());
+    assert!(matches!(decision, Decision::Compound(_)));
+
+    //without max interval only profitable compound is done
+    let mut config = keeper_config(AUTOCOMPOUNDER_ADDR);
+    config.max_compound_interval = None;
+    let keeper = Keeper::new(config);
+    let decision = keeper.decide(&snapshot, 60, nasset_price());
+    assert!(matches!(
+        decision,
+        Decision::Skip(SkipReason::Unprofitable { .. })
+    ));
+}
+
+#[test]
+fn compound_tx_payload_json() {
+    let keeper = Keeper::new(keeper_config(AUTOCOMPOUNDER_ADDR));
+    let tx = match keeper.decide(
+        &snapshot(1_500_000),
+        LAST_COMPOUND_TIME + 86_400,
+        nasset_price(),
+    ) {
+        Decision::Compound(tx) => tx,
+        decision => panic!("unexpected decision: {:?}", decision),
+    };
+
+    let json: serde_json::Value = serde_json::to_value(&tx).unwrap();
+    assert_eq!(json["contract"], AUTOCOMPOUNDER_ADDR);
+    assert_eq!(json["sender"], KEEPER_ADDR);
+    assert_eq!(
+        json["msg"],
+        serde_json::json!({ "compound": { "nasset_token_addr": null } })
+    );
+    assert_eq!(json["gas_limit"], 1_500_000);
+    assert_eq!(
+        json["fee"],
+        serde_json::json!({ "denom": "uusd", "amount": "1500000" })
+    );
+}
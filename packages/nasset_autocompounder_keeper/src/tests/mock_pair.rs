@@ -0,0 +1,97 @@
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use nasset_autocompounder::msg::{
+    AstroportAssetInfo, AstroportCw20HookMsg, AstroportQueryMsg, AstroportSimulationResponse,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+//PSI -> nAsset pair with fixed 'psi_price' and no commission,
+//sold nAsset is paid from pair balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub psi_token: String,
+    pub nasset_token: String,
+    pub psi_price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Config {
+    psi_token: Addr,
+    nasset_token: Addr,
+    psi_price: Decimal,
+}
+
+const CONFIG: Item<Config> = Item::new("config");
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            psi_token: deps.api.addr_validate(&msg.psi_token)?,
+            nasset_token: deps.api.addr_validate(&msg.nasset_token)?,
+            psi_price: msg.psi_price,
+        },
+    )?;
+    Ok(Response::new())
+}
+
+fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    let ExecuteMsg::Receive(cw20_msg) = msg;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.psi_token {
+        return Err(StdError::generic_err("only PSI can be offered"));
+    }
+
+    let to = match from_binary(&cw20_msg.msg)? {
+        AstroportCw20HookMsg::Swap { to, .. } => to.unwrap_or(cw20_msg.sender),
+        AstroportCw20HookMsg::WithdrawLiquidity {} => {
+            return Err(StdError::generic_err("liquidity is not supported"))
+        }
+    };
+
+    Ok(Response::new().add_message(WasmMsg::Execute {
+        contract_addr: config.nasset_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: to,
+            amount: cw20_msg.amount * config.psi_price,
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn query(deps: Deps, _env: Env, msg: AstroportQueryMsg) -> StdResult<Binary> {
+    let AstroportQueryMsg::Simulation { offer_asset } = msg;
+    let config = CONFIG.load(deps.storage)?;
+    match offer_asset.info {
+        AstroportAssetInfo::Token { contract_addr }
+            if contract_addr == config.psi_token.as_str() => {}
+        _ => return Err(StdError::generic_err("only PSI can be offered")),
+    }
+
+    to_binary(&AstroportSimulationResponse {
+        return_amount: offer_asset.amount * config.psi_price,
+        spread_amount: Uint128::zero(),
+        commission_amount: Uint128::zero(),
+    })
+}
@@ -0,0 +1,98 @@
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::{Item, Map};
+use nasset_autocompounder::msg::{
+    NAssetTokenRewardsAccruedRewardsResponse, NAssetTokenRewardsAnyoneMsg,
+    NAssetTokenRewardsExecuteMsg, NAssetTokenRewardsQueryMsg,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+//nAsset rewards contract which accrues 'psi_per_second' PSI to every holder
+//since instantiate or its last claim, regardless of nAsset balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub psi_token: String,
+    pub psi_per_second: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Config {
+    psi_token: Addr,
+    psi_per_second: Uint128,
+    start_time: u64,
+}
+
+const CONFIG: Item<Config> = Item::new("config");
+const LAST_CLAIM_TIME: Map<&Addr, u64> = Map::new("last_claim_time");
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            psi_token: deps.api.addr_validate(&msg.psi_token)?,
+            psi_per_second: msg.psi_per_second,
+            start_time: env.block.time.seconds(),
+        },
+    )?;
+    Ok(Response::new())
+}
+
+fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: NAssetTokenRewardsExecuteMsg,
+) -> StdResult<Response> {
+    let NAssetTokenRewardsExecuteMsg::Anyone { anyone_msg } = msg;
+    let recipient = match anyone_msg {
+        NAssetTokenRewardsAnyoneMsg::ClaimRewards { recipient: None } => info.sender.clone(),
+        _ => return Err(StdError::generic_err("unsupported message")),
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let rewards = accrued_rewards(deps.as_ref(), &env, &config, &info.sender)?;
+    if rewards.is_zero() {
+        return Err(StdError::generic_err("No rewards have accrued yet"));
+    }
+    LAST_CLAIM_TIME.save(deps.storage, &info.sender, &env.block.time.seconds())?;
+
+    Ok(Response::new().add_message(WasmMsg::Execute {
+        contract_addr: config.psi_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: rewards,
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn query(deps: Deps, env: Env, msg: NAssetTokenRewardsQueryMsg) -> StdResult<Binary> {
+    let NAssetTokenRewardsQueryMsg::AccruedRewards { address } = msg;
+    let config = CONFIG.load(deps.storage)?;
+    let holder = deps.api.addr_validate(&address)?;
+    to_binary(&NAssetTokenRewardsAccruedRewardsResponse {
+        rewards: accrued_rewards(deps, &env, &config, &holder)?,
+    })
+}
+
+fn accrued_rewards(deps: Deps, env: &Env, config: &Config, holder: &Addr) -> StdResult<Uint128> {
+    let last_claim_time = LAST_CLAIM_TIME
+        .may_load(deps.storage, holder)?
+        .unwrap_or(config.start_time);
+    let elapsed = env.block.time.seconds().saturating_sub(last_claim_time);
+    Ok(config.psi_per_second * Uint128::from(elapsed))
+}
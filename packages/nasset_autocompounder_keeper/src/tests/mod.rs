@@ -0,0 +1,30 @@
+mod chain;
+mod decision;
+mod mock_pair;
+mod mock_rewards;
+
+use crate::{GasCostModel, KeeperConfig};
+use cosmwasm_std::Decimal;
+
+pub const AUTOCOMPOUNDER_ADDR: &str = "addr0001";
+pub const KEEPER_ADDR: &str = "keeper";
+pub const COMPOUND_GAS: u64 = 1_000_000;
+pub const MIN_COMPOUND_INTERVAL: u64 = 3_600;
+pub const MAX_COMPOUND_INTERVAL: u64 = 7 * 86_400;
+
+//fee is 1_500_000 uusd: 1_000_000 gas * 1.5 adjustment * 1 uusd
+pub fn keeper_config(autocompounder_addr: &str) -> KeeperConfig {
+    KeeperConfig {
+        autocompounder_addr: autocompounder_addr.to_string(),
+        keeper_addr: KEEPER_ADDR.to_string(),
+        cost_model: GasCostModel {
+            compound_gas: COMPOUND_GAS,
+            gas_adjustment: Decimal::from_ratio(3u128, 2u128),
+            gas_price: Decimal::one(),
+            fee_denom: "uusd".to_string(),
+        },
+        min_profit_ratio: Decimal::from_ratio(2u128, 1u128),
+        min_compound_interval: MIN_COMPOUND_INTERVAL,
+        max_compound_interval: Some(MAX_COMPOUND_INTERVAL),
+    }
+}
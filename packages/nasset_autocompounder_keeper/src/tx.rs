@@ -0,0 +1,27 @@
+use cosmwasm_std::{to_binary, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use nasset_autocompounder_interface::msg::ExecuteMsg;
+use serde::{Deserialize, Serialize};
+
+/// 'Compound' transaction payload, 'msg' is JSON of autocompounder 'ExecuteMsg'
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompoundTx {
+    pub sender: String,
+    pub contract: String,
+    pub msg: ExecuteMsg,
+    pub gas_limit: u64,
+    pub fee: Coin,
+    //expected result, for logs only
+    pub psi_to_sell: Uint128,
+    pub nasset_out: Uint128,
+}
+
+impl CompoundTx {
+    pub fn cosmos_msg(&self) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: self.contract.clone(),
+            msg: to_binary(&self.msg)?,
+            funds: vec![],
+        }
+        .into())
+    }
+}